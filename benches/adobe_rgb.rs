@@ -0,0 +1,41 @@
+//! Benchmarks converting a large slice of Adobe RGB colors to XYZ and back, which undoes the Adobe
+//! RGB matrix with an LU decomposition computed once instead of inverting it on every conversion.
+
+#[macro_use]
+extern crate criterion;
+extern crate scarlet;
+
+use criterion::{black_box, Criterion};
+use scarlet::color::XYZColor;
+use scarlet::colors::AdobeRGBColor;
+use scarlet::prelude::*;
+
+fn bench_adobe_rgb(c: &mut Criterion) {
+    let colors: Vec<AdobeRGBColor> = (0..100_000)
+        .map(|i| AdobeRGBColor {
+            r: (i % 100) as f64 / 99.0,
+            g: ((i / 100) % 100) as f64 / 99.0,
+            b: (i / 10_000) as f64 / 9.0,
+        })
+        .collect();
+    let xyzs: Vec<XYZColor> = colors.iter().map(|color| color.convert()).collect();
+    c.bench_function("Adobe RGB to XYZ 100k colors", |bench| {
+        bench.iter(|| {
+            black_box(&colors)
+                .iter()
+                .map(|color| color.to_xyz(Illuminant::D65))
+                .collect::<Vec<XYZColor>>()
+        })
+    });
+    c.bench_function("XYZ to Adobe RGB 100k colors", |bench| {
+        bench.iter(|| {
+            black_box(&xyzs)
+                .iter()
+                .map(|&xyz| AdobeRGBColor::from_xyz(xyz))
+                .collect::<Vec<AdobeRGBColor>>()
+        })
+    });
+}
+
+criterion_group!(benches, bench_adobe_rgb);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Benchmarks applying a colormap to a million values serially and across threads with
+//! `transform_par`. Run with `cargo bench --features rayon`.
+
+#[macro_use]
+extern crate criterion;
+extern crate scarlet;
+
+use criterion::{black_box, Criterion};
+use scarlet::colormap::{ColorMap, ListedColorMap};
+use scarlet::prelude::*;
+
+/// The number of values to map.
+const VALUES: usize = 1_000_000;
+
+fn bench_colormap_par(c: &mut Criterion) {
+    let cmap = ListedColorMap::viridis();
+    let inputs: Vec<f64> = (0..VALUES).map(|i| i as f64 / VALUES as f64).collect();
+
+    let mut group = c.benchmark_group("viridis");
+    group.sample_size(20);
+    group.bench_function("transform 1M values", |bench| {
+        bench.iter(|| -> Vec<RGBColor> { cmap.transform(black_box(&inputs).iter().cloned()) })
+    });
+    group.bench_function("transform_par 1M values", |bench| {
+        bench.iter(|| -> Vec<RGBColor> { cmap.transform_par(black_box(&inputs).clone()) })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_colormap_par);
+criterion_main!(benches);
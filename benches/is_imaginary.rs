@@ -0,0 +1,40 @@
+//! Benchmarks checking many colors against the spectral locus, which is cached so that checking a
+//! whole image doesn't rebuild it for every pixel.
+
+#[macro_use]
+extern crate criterion;
+extern crate scarlet;
+
+use criterion::{black_box, Criterion};
+use scarlet::colors::CIELABColor;
+use scarlet::prelude::*;
+
+fn bench_is_imaginary(c: &mut Criterion) {
+    // a grid of CIELAB colors, some of which are outside the visible gamut
+    let colors: Vec<CIELABColor> = (0..10_000)
+        .map(|i| CIELABColor {
+            l: (i % 10) as f64 * 10.0 + 5.0,
+            a: ((i / 10) % 30) as f64 * 10.0 - 150.0,
+            b: (i / 300) as f64 * 10.0 - 150.0,
+        })
+        .collect();
+    c.bench_function("is_imaginary 10k CIELAB colors", |bench| {
+        bench.iter(|| {
+            black_box(&colors)
+                .iter()
+                .filter(|color| color.is_imaginary())
+                .count()
+        })
+    });
+    c.bench_function("closest_real_color 10k CIELAB colors", |bench| {
+        bench.iter(|| {
+            black_box(&colors)
+                .iter()
+                .map(|color| color.closest_real_color())
+                .collect::<Vec<CIELABColor>>()
+        })
+    });
+}
+
+criterion_group!(benches, bench_is_imaginary);
+criterion_main!(benches);
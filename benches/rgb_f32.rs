@@ -0,0 +1,59 @@
+//! Benchmarks for `RGBColorF32`: how fast a megapixel image converts between it and `RGBColor`, and
+//! how long a pass over each buffer takes, since the `f32` buffer is half the size.
+
+#[macro_use]
+extern crate criterion;
+extern crate scarlet;
+
+use criterion::{black_box, Criterion, Throughput};
+use scarlet::color::RGBColorF32;
+use scarlet::prelude::*;
+use std::mem::size_of;
+
+/// The number of pixels in each buffer.
+const PIXELS: usize = 1_000_000;
+
+fn bench_rgb_f32(c: &mut Criterion) {
+    let colors: Vec<RGBColor> = (0..PIXELS)
+        .map(|i| RGBColor::from(((i % 256) as u8, ((i / 256) % 256) as u8, (i % 97) as u8)))
+        .collect();
+    let compact: Vec<RGBColorF32> = colors.iter().map(|&color| color.into()).collect();
+
+    let mut group = c.benchmark_group("RGBColorF32 conversion");
+    group.sample_size(20);
+    group.bench_function("RGBColor to RGBColorF32 1M pixels", |bench| {
+        bench.iter(|| {
+            black_box(&colors)
+                .iter()
+                .map(|&color| RGBColorF32::from(color))
+                .collect::<Vec<RGBColorF32>>()
+        })
+    });
+    group.bench_function("RGBColorF32 to RGBColor 1M pixels", |bench| {
+        bench.iter(|| {
+            black_box(&compact)
+                .iter()
+                .map(|&color| RGBColor::from(color))
+                .collect::<Vec<RGBColor>>()
+        })
+    });
+    group.finish();
+
+    // summing the red channel is bound by memory bandwidth, so the smaller buffer is faster
+    let mut group = c.benchmark_group("buffer pass");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes((PIXELS * size_of::<RGBColor>()) as u64));
+    group.bench_function("sum red of RGBColor 1M pixels", |bench| {
+        bench.iter(|| black_box(&colors).iter().map(|color| color.r).sum::<f64>())
+    });
+    group.throughput(Throughput::Bytes(
+        (PIXELS * size_of::<RGBColorF32>()) as u64,
+    ));
+    group.bench_function("sum red of RGBColorF32 1M pixels", |bench| {
+        bench.iter(|| black_box(&compact).iter().map(|color| color.r).sum::<f32>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_rgb_f32);
+criterion_main!(benches);
@@ -0,0 +1,237 @@
+//! This module provides [`Alpha`], a wrapper that pairs a color with an opacity. Colors in Scarlet
+//! describe light, and light has no notion of transparency, so none of the color spaces carry an
+//! alpha channel themselves: instead, anything that needs one, like a palette file or a CSS color
+//! with transparency, wraps its color in `Alpha`.
+
+use color::{css_number, css_percent, srgb_decode, srgb_encode, Color, RGBColor};
+
+/// The size, in pixels, of each square of the checkerboard used by
+/// [`composite_on_checker`](struct.Alpha.html#method.composite_on_checker).
+pub const CHECKER_SIZE: usize = 8;
+
+/// A color together with an alpha value, its opacity. The alpha value ranges from 0, fully
+/// transparent, to 1, fully opaque, and is straight (not premultiplied): the wrapped color is the
+/// color as it would appear if fully opaque.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::alpha::Alpha;
+/// let translucent_red = Alpha::new(RGBColor{r: 1., g: 0., b: 0.}, 0.5);
+/// assert_eq!(translucent_red.color.to_string(), "#FF0000");
+/// assert!(!translucent_red.is_opaque());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alpha<T: Color> {
+    /// The color, as it would appear with no transparency.
+    pub color: T,
+    /// The opacity, from 0 (fully transparent) to 1 (fully opaque).
+    pub alpha: f64,
+}
+
+impl<T: Color> Alpha<T> {
+    /// Wraps the given color with the given alpha value, which is clamped to lie between 0 and 1.
+    pub fn new(color: T, alpha: f64) -> Alpha<T> {
+        Alpha {
+            color,
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+    /// Wraps the given color with full opacity.
+    pub fn opaque(color: T) -> Alpha<T> {
+        Alpha { color, alpha: 1.0 }
+    }
+    /// Returns `true` if this color is fully opaque, and `false` otherwise.
+    pub fn is_opaque(&self) -> bool {
+        self.alpha >= 1.0
+    }
+}
+
+impl Alpha<RGBColor> {
+    /// Writes this color in CSS functional notation with its alpha value, as in
+    /// `rgba(255, 0, 128, 0.5)`. The components are as in
+    /// [`RGBColor::to_css`](../color/struct.RGBColor.html#method.to_css), and the alpha value is
+    /// clamped to lie between 0 and 1 and
+    /// given to at most two decimal places.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let color = Alpha::new(RGBColor::from_hex_code("#FF0080").unwrap(), 0.5);
+    /// assert_eq!(color.to_css(), "rgba(255, 0, 128, 0.5)");
+    /// ```
+    pub fn to_css(&self) -> String {
+        format!(
+            "rgba({}, {}, {}, {})",
+            self.color.int_r(),
+            self.color.int_g(),
+            self.color.int_b(),
+            css_number(self.alpha.clamp(0.0, 1.0))
+        )
+    }
+    /// Writes this color in CSS functional notation with percentages and its alpha value, as in
+    /// `rgba(100%, 0%, 50%, 0.5)`. The components are as in
+    /// [`RGBColor::to_css_percent`](../color/struct.RGBColor.html#method.to_css_percent), and the
+    /// alpha value is as in [`to_css`](#method.to_css).
+    pub fn to_css_percent(&self) -> String {
+        format!(
+            "rgba({}%, {}%, {}%, {})",
+            css_percent(self.color.r),
+            css_percent(self.color.g),
+            css_percent(self.color.b),
+            css_number(self.alpha.clamp(0.0, 1.0))
+        )
+    }
+    /// Flattens this translucent color onto an opaque background, giving the opaque color that shows
+    /// up when it's drawn over it: the "source-over" operation. This is how to export translucent
+    /// colors to formats without alpha. The blend is done in linear light, which is how light
+    /// actually mixes, so it's a bit lighter than the naive blend of the sRGB components that many
+    /// programs do.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let half_white = Alpha::new(white, 0.5);
+    /// // half as much light as white, which in sRGB is well above #808080
+    /// assert_eq!(half_white.flatten_on(black).to_string(), "#BCBCBC");
+    /// ```
+    pub fn flatten_on(&self, background: RGBColor) -> RGBColor {
+        let blend = |fg: f64, bg: f64| {
+            srgb_encode(self.alpha * srgb_decode(fg) + (1.0 - self.alpha) * srgb_decode(bg))
+        };
+        RGBColor {
+            r: blend(self.color.r, background.r),
+            g: blend(self.color.g, background.g),
+            b: blend(self.color.b, background.b),
+        }
+    }
+    /// Flattens this translucent color onto the checkerboard that image editors show behind
+    /// transparent areas, giving the color of the pixel at `at`, as `(x, y)`. The checkerboard starts
+    /// with a `light` square at `(0, 0)` and alternates with `dark` every
+    /// [`CHECKER_SIZE`](constant.CHECKER_SIZE.html) pixels in each direction. Each pixel is blended
+    /// as in [`flatten_on`](#method.flatten_on).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let light = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let dark = RGBColor::from_hex_code("#CCCCCC").unwrap();
+    /// let red = Alpha::new(RGBColor::from_hex_code("#FF0000").unwrap(), 0.5);
+    /// let preview: Vec<RGBColor> = (0..16).map(|x| red.composite_on_checker(light, dark, (x, 0))).collect();
+    /// assert_eq!(preview[0], red.flatten_on(light));
+    /// assert_eq!(preview[8], red.flatten_on(dark));
+    /// ```
+    pub fn composite_on_checker(
+        &self,
+        light: RGBColor,
+        dark: RGBColor,
+        at: (usize, usize),
+    ) -> RGBColor {
+        let (x, y) = at;
+        match (x / CHECKER_SIZE + y / CHECKER_SIZE) % 2 {
+            0 => self.flatten_on(light),
+            _ => self.flatten_on(dark),
+        }
+    }
+}
+
+impl<T: Color> From<T> for Alpha<T> {
+    fn from(color: T) -> Alpha<T> {
+        Alpha::opaque(color)
+    }
+}
+
+impl From<[u8; 4]> for Alpha<RGBColor> {
+    /// Reads an 8-bit RGBA pixel, as image buffers often store them, with the alpha byte last.
+    fn from(rgba: [u8; 4]) -> Alpha<RGBColor> {
+        Alpha::new(
+            RGBColor::from([rgba[0], rgba[1], rgba[2]]),
+            f64::from(rgba[3]) / 255.0,
+        )
+    }
+}
+
+impl From<Alpha<RGBColor>> for [u8; 4] {
+    fn from(val: Alpha<RGBColor>) -> Self {
+        let [r, g, b]: [u8; 3] = val.color.into();
+        [r, g, b, (val.alpha * 255.0).round() as u8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use illuminants::Illuminant;
+
+    #[test]
+    fn test_alpha_clamping() {
+        let color = RGBColor {
+            r: 0.2,
+            g: 0.4,
+            b: 0.6,
+        };
+        assert_eq!(Alpha::new(color, 1.5).alpha, 1.0);
+        assert_eq!(Alpha::new(color, -0.5).alpha, 0.0);
+        assert!(Alpha::from(color).is_opaque());
+        assert_eq!(Alpha::from(color).color, color);
+    }
+
+    #[test]
+    fn test_rgba_array_conversion() {
+        let color = Alpha::from([255, 0, 128, 64]);
+        assert_eq!(color.color, RGBColor::from((255, 0, 128)));
+        assert!((color.alpha - 64. / 255.).abs() <= 1e-12);
+        let rgba: [u8; 4] = color.into();
+        assert_eq!(rgba, [255, 0, 128, 64]);
+    }
+
+    #[test]
+    fn test_alpha_to_css() {
+        let color = RGBColor::from_hex_code("#FF0080").unwrap();
+        assert_eq!(Alpha::new(color, 0.5).to_css(), "rgba(255, 0, 128, 0.5)");
+        assert_eq!(Alpha::opaque(color).to_css(), "rgba(255, 0, 128, 1)");
+        assert_eq!(
+            Alpha::new(color, 0.25).to_css_percent(),
+            "rgba(100%, 0%, 50.2%, 0.25)"
+        );
+        // the field is public, so alpha can end up out of range without going through new
+        let over = Alpha { color, alpha: 1.5 };
+        let under = Alpha { color, alpha: -0.5 };
+        assert_eq!(over.to_css(), "rgba(255, 0, 128, 1)");
+        assert_eq!(under.to_css_percent(), "rgba(100%, 0%, 50.2%, 0)");
+    }
+
+    #[test]
+    fn test_flatten_on() {
+        let fg = RGBColor::from_hex_code("#3366CC").unwrap();
+        let bg = RGBColor::from_hex_code("#F0E0D0").unwrap();
+        assert!(Alpha::new(fg, 0.).flatten_on(bg).distance(&bg) <= 1e-10);
+        assert!(Alpha::new(fg, 1.).flatten_on(bg).distance(&fg) <= 1e-10);
+        // halfway mixes the light, not the sRGB components
+        let half = Alpha::new(fg, 0.5).flatten_on(bg);
+        let y = |c: RGBColor| c.to_xyz(Illuminant::D65).y;
+        assert!((y(half) - (y(fg) + y(bg)) / 2.).abs() <= 1e-10);
+        // the checkerboard alternates every CHECKER_SIZE pixels
+        let light = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        let dark = RGBColor::from_hex_code("#999999").unwrap();
+        let clear = Alpha::new(fg, 0.);
+        let at = |x, y| clear.composite_on_checker(light, dark, (x, y)).to_string();
+        assert_eq!(at(0, 0), "#FFFFFF");
+        assert_eq!(at(7, 7), "#FFFFFF");
+        assert_eq!(at(8, 0), "#999999");
+        assert_eq!(at(0, 8), "#999999");
+        assert_eq!(at(8, 8), "#FFFFFF");
+        assert_eq!(
+            Alpha::opaque(fg)
+                .composite_on_checker(light, dark, (9, 3))
+                .to_string(),
+            fg.to_string()
+        );
+    }
+}
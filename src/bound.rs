@@ -5,6 +5,7 @@
 
 use color::{Color, RGBColor};
 use colorpoint::ColorPoint;
+use colors::{CIELABColor, CIELCHColor};
 use coord::Coord;
 
 /// Describes a color space in which the total space of representable colors has explicit bounds
@@ -65,6 +66,147 @@ pub trait Bound: Color + ColorPoint {
         let point: Coord = converted_color.into();
         Self::from(Self::clamp_coord(point)).convert()
     }
+    /// Returns the largest CIELCH chroma that a color with the given CIELCH lightness and hue (in
+    /// degrees) can have while staying inside this gamut, found by binary search to within about
+    /// 1e-9. This traces out the edge of the gamut, so it's the building block for the most vivid
+    /// palettes a gamut allows, or for drawing a slice of the gamut in the CIELAB a-b plane.
+    /// Lightnesses outside of the range 0 to 100 have no colors at all, and give a chroma of 0.
+    ///
+    /// A color counts as in the gamut if its coordinates in this space are within the bounds and
+    /// converting it into this space doesn't change it, which accounts for spaces like Adobe RGB that
+    /// clip colors on conversion.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{AdobeRGBColor, CIELCHColor};
+    /// // the most vivid sRGB green at a lightness of 60
+    /// let c = RGBColor::max_chroma(60., 135.);
+    /// let green: RGBColor = CIELCHColor{l: 60., c, h: 135.}.convert();
+    /// assert!(green.r >= -1e-6 && green.g <= 1. + 1e-6);
+    /// // Adobe RGB is famously better at greens
+    /// assert!(AdobeRGBColor::max_chroma(60., 135.) > c + 10.);
+    /// ```
+    fn max_chroma(l: f64, h: f64) -> f64 {
+        if !(0.0..=100.0).contains(&l) {
+            return 0.0;
+        }
+        let h = h.rem_euclid(360.0);
+        let eps = 1e-9;
+        let in_gamut = |c: f64| {
+            let lch = CIELCHColor { l, c, h };
+            let converted: Self = lch.convert();
+            let coord: Coord = converted.into();
+            let in_bounds = Self::bounds()
+                .iter()
+                .zip([coord.x, coord.y, coord.z].iter())
+                .all(|(&(min, max), &x)| x >= min - eps && x <= max + eps);
+            in_bounds && lch.distance(&converted) <= 1e-6
+        };
+        // no visible color has a CIELCH chroma of more than about 200, so start well above that
+        let (mut lo, mut hi) = (0.0, 256.0);
+        if !in_gamut(lo) {
+            return 0.0;
+        }
+        while hi - lo > eps {
+            let mid = (lo + hi) / 2.0;
+            if in_gamut(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+}
+
+/// Estimates the volume of the gamut `B` in CIELAB, in cubic CIELAB units: a standard way of
+/// comparing how many colors different gamuts can show. The box of `B`'s bounds is split into
+/// `samples_per_axis` slices along each axis, each corner of the resulting grid is converted to
+/// CIELAB, and the volumes of the distorted cells are added up, so the estimate gets more precise
+/// as `samples_per_axis` grows: 16 is already within about half a percent for the RGB spaces. The
+/// gamut is measured as defined, so spaces like ROMM RGB that reach beyond human vision count
+/// those imaginary colors too.
+///
+/// # Panics
+/// Panics if any of `B`'s bounds is infinite, as the volume would be too.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::bound::gamut_volume;
+/// # use scarlet::colors::AdobeRGBColor;
+/// let srgb = gamut_volume::<RGBColor>(16);
+/// let adobe = gamut_volume::<AdobeRGBColor>(16);
+/// // sRGB covers about 830,000 cubic units, and Adobe RGB around 40% more
+/// assert!((srgb - 830_000.).abs() <= 20_000.);
+/// assert!(adobe / srgb > 1.3);
+/// ```
+pub fn gamut_volume<B: Bound>(samples_per_axis: usize) -> f64 {
+    let bounds = B::bounds();
+    assert!(
+        bounds
+            .iter()
+            .all(|&(min, max)| min.is_finite() && max.is_finite()),
+        "Gamut must have finite bounds"
+    );
+    let n = samples_per_axis.max(1);
+    let step = |axis: usize, i: usize| {
+        let (min, max) = bounds[axis];
+        min + (max - min) * i as f64 / n as f64
+    };
+    // the CIELAB coordinates of every grid point, indexed by (i * (n + 1) + j) * (n + 1) + k
+    let mut grid = Vec::with_capacity((n + 1).pow(3));
+    for i in 0..=n {
+        for j in 0..=n {
+            for k in 0..=n {
+                let point = Coord {
+                    x: step(0, i),
+                    y: step(1, j),
+                    z: step(2, k),
+                };
+                let lab: CIELABColor = B::from(point).convert();
+                grid.push(Coord::from(lab));
+            }
+        }
+    }
+    let at = |i: usize, j: usize, k: usize| grid[(i * (n + 1) + j) * (n + 1) + k];
+    // each cell is split into six tetrahedra along its main diagonal, one for each order of taking
+    // a step along the three axes: signing each by the parity of that order gives them all the same
+    // orientation, so the signed volumes add up to the volume of the whole gamut
+    let orders = [
+        ([0, 1, 2], 1.),
+        ([1, 2, 0], 1.),
+        ([2, 0, 1], 1.),
+        ([0, 2, 1], -1.),
+        ([2, 1, 0], -1.),
+        ([1, 0, 2], -1.),
+    ];
+    let mut volume = 0.0;
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                let origin = at(i, j, k);
+                for &(order, sign) in orders.iter() {
+                    let mut index = [i, j, k];
+                    let mut edges = [Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: 0.,
+                    }; 3];
+                    for (edge, &axis) in edges.iter_mut().zip(order.iter()) {
+                        index[axis] += 1;
+                        *edge = at(index[0], index[1], index[2]) - origin;
+                    }
+                    let det = edges[0].x * (edges[1].y * edges[2].z - edges[1].z * edges[2].y)
+                        - edges[0].y * (edges[1].x * edges[2].z - edges[1].z * edges[2].x)
+                        + edges[0].z * (edges[1].x * edges[2].y - edges[1].y * edges[2].x);
+                    volume += sign * det / 6.0;
+                }
+            }
+        }
+    }
+    volume.abs()
 }
 
 // implement Bound for the base colors in the color module, to avoid cluttering that more than it
@@ -77,11 +219,12 @@ impl Bound for RGBColor {
 
 #[cfg(test)]
 mod tests {
-    use super::Bound;
+    use super::{gamut_volume, Bound};
     use color::Color;
     use color::RGBColor;
     use colors::hslcolor::HSLColor;
     use colors::hsvcolor::HSVColor;
+    use colors::{AdobeRGBColor, CIELCHColor};
 
     #[test]
     fn test_zero_one_bounds() {
@@ -117,13 +260,12 @@ mod tests {
             v: 0.7,
         };
         assert!(color3.visually_indistinguishable(&HSVColor::clamp(color3)));
-        assert!(
-            HSVColor::clamp(color2).visually_indistinguishable(&HSVColor {
-                h: 360.,
-                s: 0.2,
-                v: 0.5,
-            },)
-        );
+        // clamping converts to HSV first, which always gives a hue from 0 to 360, so nothing
+        // needs to change
+        let clamped2 = HSVColor::clamp(color2);
+        assert!(clamped2.visually_indistinguishable(&color2));
+        let clamped2_hsv: HSVColor = clamped2.convert();
+        assert!(clamped2_hsv.h >= 0. && clamped2_hsv.h <= 360.);
         assert!(
             HSLColor::clamp(color1).visually_indistinguishable(&HSLColor {
                 h: 0.,
@@ -132,4 +274,42 @@ mod tests {
             },)
         );
     }
+
+    #[test]
+    fn test_max_chroma() {
+        let in_srgb = |rgb: RGBColor| {
+            [rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|&x| (-1e-6..=1. + 1e-6).contains(&x))
+        };
+        for l in (1..10).map(|x| f64::from(x) * 10.) {
+            for h in (0..8).map(|x| f64::from(x) * 45.) {
+                let c = RGBColor::max_chroma(l, h);
+                assert!(c > 0.);
+                // right at the edge of the gamut: a little more chroma is out of it
+                assert!(in_srgb(CIELCHColor { l, c, h }.convert()));
+                assert!(!in_srgb(CIELCHColor { l, c: c + 0.01, h }.convert()));
+                // and HSL is just another way of writing sRGB
+                assert!((HSLColor::max_chroma(l, h) - c).abs() <= 1e-3);
+            }
+        }
+        // Adobe RGB reaches much further into the greens and cyans
+        for &h in [135., 180.].iter() {
+            assert!(AdobeRGBColor::max_chroma(50., h) >= RGBColor::max_chroma(50., h) + 5.);
+        }
+        assert_eq!(RGBColor::max_chroma(-5., 30.), 0.);
+        assert_eq!(RGBColor::max_chroma(105., 30.), 0.);
+    }
+
+    #[test]
+    fn test_gamut_volume() {
+        let srgb = gamut_volume::<RGBColor>(16);
+        let adobe = gamut_volume::<AdobeRGBColor>(16);
+        assert!(adobe > srgb);
+        // the estimate settles down quickly as the grid gets finer
+        assert!((gamut_volume::<RGBColor>(24) - srgb).abs() / srgb <= 1e-3);
+        // HSL is the same gamut as sRGB, just with different coordinates
+        let hsl = gamut_volume::<HSLColor>(32);
+        assert!((hsl - srgb).abs() / srgb <= 0.01);
+    }
 }
@@ -29,10 +29,14 @@
 //! patterns simple to do.
 //!
 
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::convert::From;
+use std::env;
 use std::error::Error;
+use std::f64::consts::PI;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::Sized;
 use std::num::ParseIntError;
 use std::result::Result::Err;
@@ -40,6 +44,10 @@ use std::str::FromStr;
 use std::string::ToString;
 
 use super::coord::Coord;
+use bound::Bound;
+use colormap::ColorMap;
+use colorpoint::{ColorPoint, Mix};
+use colors::cam16ucscolor::CAM16UCSColor;
 use colors::cielabcolor::CIELABColor;
 use colors::cielchcolor::CIELCHColor;
 use consts;
@@ -49,13 +57,10 @@ use consts::STANDARD_RGB_TRANSFORM as SRGB;
 use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
 use csscolor::{parse_rgb_str, CSSParseError};
 use illuminants::Illuminant;
+use observer::Observer;
 
-use nalgebra::base::Vector;
 use nalgebra::vector;
 
-#[cfg(feature = "terminal")]
-use termion::color::{Bg, Fg, Reset, Rgb};
-
 /// A point in the CIE 1931 XYZ color space. Although any point in XYZ coordinate space is technically
 /// valid, in this library XYZ colors are treated as normalized so that Y=1 is the white point of
 /// whatever illuminant is being worked with.
@@ -154,8 +159,11 @@ impl XYZColor {
             // get the RGB values for the white point of the illuminant we are currently using and
             // the one we want: wr here stands for "white reference", i.e., the one we're converting
             // to
-            let rgb_w = *BRADFORD * Vector::from(self.illuminant.white_point().to_vec());
-            let rgb_wr = *BRADFORD * Vector::from(other_illuminant.white_point().to_vec());
+            // build the vectors directly: this runs for every conversion, so avoid allocating
+            let wp = self.illuminant.white_point();
+            let wp_r = other_illuminant.white_point();
+            let rgb_w = *BRADFORD * vector![wp[0], wp[1], wp[2]];
+            let rgb_wr = *BRADFORD * vector![wp_r[0], wp_r[1], wp_r[2]];
 
             // perform the transform
             // this usually includes a parameter indicating how much you want to adapt, but it's
@@ -242,8 +250,735 @@ impl XYZColor {
             illuminant,
         }
     }
+    /// Computes the color of a surface from its spectral reflectance, as seen under the given
+    /// illuminant by the CIE 1931 2° standard observer. `samples` is a list of `(wavelength,
+    /// reflectance)` pairs, with wavelengths in nanometers and reflectances from 0 to 1, in any
+    /// order. The spectrum, illuminant, and observer are all linearly interpolated to a 1 nm grid
+    /// from 360 to 830 nm and integrated, with reflectances beyond the sampled range held at the
+    /// nearest sample. The result is normalized so that a perfect white reflector has Y = 1.
+    ///
+    /// The D-series illuminants use their actual spectra, as given by
+    /// [`Illuminant::relative_spd`](../illuminants/enum.Illuminant.html#method.relative_spd). Custom
+    /// illuminants don't have a spectrum, so the integration is done under an equal-energy illuminant
+    /// and the result is chromatically adapted to the custom white point.
+    ///
+    /// To use the 10° observer instead, see
+    /// [`from_reflectance_with_observer`](#method.from_reflectance_with_observer).
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// // a surface that reflects long wavelengths and absorbs short ones looks orange-red
+    /// let samples: Vec<(f64, f64)> = (38..=73)
+    ///     .map(|i| {
+    ///         let wavelength = f64::from(i) * 10.;
+    ///         (wavelength, if wavelength >= 590. { 0.9 } else { 0.05 })
+    ///     })
+    ///     .collect();
+    /// let color = XYZColor::from_reflectance(&samples, Illuminant::D65);
+    /// let rgb: RGBColor = color.convert();
+    /// assert!(rgb.r > rgb.g && rgb.g > rgb.b);
+    /// ```
+    pub fn from_reflectance(samples: &[(f64, f64)], illuminant: Illuminant) -> XYZColor {
+        XYZColor::from_reflectance_with_observer(samples, illuminant, Observer::TwoDegree)
+    }
+    /// Computes the color of a surface from its spectral reflectance exactly as
+    /// [`from_reflectance`](#method.from_reflectance) does, but with the given standard observer. The
+    /// 10° observer is the standard for large color fields like paint and textiles. Note that the
+    /// rest of Scarlet assumes the 2° observer: the XYZ coordinates this gives with the 10° observer
+    /// are meant for colorimetry, like comparing samples, not for converting to other color spaces.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::observer::Observer;
+    /// // a perfect white reflector gives the white point for that observer
+    /// let white = XYZColor::from_reflectance_with_observer(
+    ///     &[(360., 1.), (830., 1.)],
+    ///     Illuminant::D65,
+    ///     Observer::TenDegree,
+    /// );
+    /// let wp = Observer::TenDegree.white_point(Illuminant::D65);
+    /// assert!((white.x - wp[0]).abs() <= 0.015);
+    /// assert!((white.z - wp[2]).abs() <= 0.015);
+    /// ```
+    pub fn from_reflectance_with_observer(
+        samples: &[(f64, f64)],
+        illuminant: Illuminant,
+        observer: Observer,
+    ) -> XYZColor {
+        assert!(!samples.is_empty(), "No reflectance samples given");
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Wavelengths can't be NaN"));
+        // piecewise-linear interpolation, holding the ends constant
+        let reflectance = |wavelength: f64| {
+            let i = sorted.partition_point(|&(w, _)| w < wavelength);
+            if i == 0 {
+                sorted[0].1
+            } else if i == sorted.len() {
+                sorted[sorted.len() - 1].1
+            } else {
+                let (w0, r0) = sorted[i - 1];
+                let (w1, r1) = sorted[i];
+                r0 + (r1 - r0) * (wavelength - w0) / (w1 - w0)
+            }
+        };
+
+        let mut xyz = [0.0; 3];
+        let mut white = [0.0; 3];
+        for wavelength in 360..=830 {
+            let wavelength = f64::from(wavelength);
+            let power = illuminant.relative_spd(wavelength).unwrap_or(1.0);
+            let cmf = observer.color_matching_functions(wavelength);
+            let r = reflectance(wavelength);
+            for k in 0..3 {
+                xyz[k] += power * r * cmf[k];
+                white[k] += power * cmf[k];
+            }
+        }
+        let color = XYZColor {
+            x: xyz[0] / white[1],
+            y: xyz[1] / white[1],
+            z: xyz[2] / white[1],
+            illuminant,
+        };
+        match illuminant {
+            // this was integrated under equal-energy light: adapt from that white
+            Illuminant::Custom(_) => XYZColor {
+                illuminant: Illuminant::Custom(white),
+                ..color
+            }
+            .color_adapt(illuminant),
+            _ => color,
+        }
+    }
+    /// Checks how badly two surfaces that match under one light fail to match under another: the
+    /// problem of *metamerism*, familiar from fabric or paint that matches in the store and not at
+    /// home. Different reflectance spectra can give the same color, but usually only under a
+    /// particular light. Given two spectra as in [`from_reflectance`](#method.from_reflectance),
+    /// this returns their CIEDE2000 [`distance`](trait.Color.html#method.distance) under `illum2`,
+    /// or `None` if they aren't a match under `illum1` to begin with, meaning they're more than 1
+    /// apart, about the smallest visible difference.
+    ///
+    /// Only the D-series illuminants have a spectrum: custom illuminants are treated as
+    /// [`from_reflectance`](#method.from_reflectance) treats them, which makes them of limited use
+    /// here.
+    ///
+    /// # Panics
+    /// Panics if either set of samples is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// // identical spectra match under any light
+    /// let gray = [(360., 0.5), (830., 0.5)];
+    /// let mismatch = XYZColor::metameric_mismatch(&gray, &gray, Illuminant::D65, Illuminant::D50);
+    /// assert_eq!(mismatch, Some(0.));
+    /// // a gray and a red don't match at all
+    /// let red = [(360., 0.1), (590., 0.1), (600., 0.9), (830., 0.9)];
+    /// assert!(XYZColor::metameric_mismatch(&gray, &red, Illuminant::D65, Illuminant::D50).is_none());
+    /// ```
+    pub fn metameric_mismatch(
+        spec_a: &[(f64, f64)],
+        spec_b: &[(f64, f64)],
+        illum1: Illuminant,
+        illum2: Illuminant,
+    ) -> Option<f64> {
+        let a1 = XYZColor::from_reflectance(spec_a, illum1);
+        let b1 = XYZColor::from_reflectance(spec_b, illum1);
+        if a1.distance(&b1) > 1.0 {
+            return None;
+        }
+        let a2 = XYZColor::from_reflectance(spec_a, illum2);
+        let b2 = XYZColor::from_reflectance(spec_b, illum2);
+        Some(a2.distance(&b2))
+    }
+    /// Mixes `weight` of `other` into this color in the color space `C`, returning the result under
+    /// this color's illuminant. A weight of 0 gives this color and 1 gives `other`. Mixing XYZ
+    /// colors directly, as [`Mix`](../colorpoint/trait.Mix.html) does, mixes them like light, which
+    /// is physically right but rarely what looks like "halfway" between two colors: mixing in a
+    /// perceptually uniform space like CIELAB usually is. `other` is adapted to this color's
+    /// illuminant first, so the colors can be given under any illuminants.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::colors::CIELABColor;
+    /// let black = XYZColor{x: 0., y: 0., z: 0., illuminant: Illuminant::D50};
+    /// let white = XYZColor::white_point(Illuminant::D65);
+    /// let gray = black.mix_in::<CIELABColor>(white, 0.5);
+    /// // halfway in lightness is much darker than halfway in luminance
+    /// assert!((gray.lightness() - 50.).abs() <= 1e-6);
+    /// assert!(gray.y < 0.2);
+    /// ```
+    pub fn mix_in<C: ColorPoint>(self, other: XYZColor, weight: f64) -> XYZColor {
+        let c1 = C::from_xyz(self);
+        let c2 = C::from_xyz(other.color_adapt(self.illuminant));
+        c1.mix_weighted(c2, weight).to_xyz(self.illuminant)
+    }
+    /// Gets the X, Y, and Z components of this color as a [`Coord`](../coord/struct.Coord.html),
+    /// dropping the illuminant. `XYZColor` deliberately doesn't implement `Into<Coord>` and
+    /// `From<Coord>`, which would make it a [`ColorPoint`](../colorpoint/trait.ColorPoint.html):
+    /// there'd be no way to know what illuminant a `Coord` coming back belongs to, and mixing or
+    /// averaging colors under different illuminants without adapting them first gives the wrong
+    /// answer. This method and [`from_coord`](#method.from_coord) are for doing point math on XYZ
+    /// colors anyway, keeping track of the illuminant yourself: make sure every color involved uses
+    /// the same one, using [`color_adapt`](#method.color_adapt) if needed.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// let a = XYZColor{x: 0.2, y: 0.3, z: 0.4, illuminant: Illuminant::D65};
+    /// let b = XYZColor{x: 0.4, y: 0.5, z: 0.2, illuminant: Illuminant::D65};
+    /// let mid = a.to_coord().lerp(&b.to_coord(), 0.5);
+    /// let mid_xyz = XYZColor::from_coord(mid, Illuminant::D65);
+    /// assert!((mid_xyz.y - 0.4).abs() <= 1e-12);
+    /// ```
+    pub fn to_coord(&self) -> Coord {
+        Coord {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+    /// Makes an XYZ color from a [`Coord`](../coord/struct.Coord.html) holding its X, Y, and Z
+    /// components and the illuminant it's under. The inverse of [`to_coord`](#method.to_coord):
+    /// see that method for why there's no `From<Coord>` implementation.
+    pub fn from_coord(c: Coord, illuminant: Illuminant) -> XYZColor {
+        XYZColor {
+            x: c.x,
+            y: c.y,
+            z: c.z,
+            illuminant,
+        }
+    }
+}
+
+/// Maps a hue in degrees, as used by [`Color::hue`](trait.Color.html#method.hue), to a position on
+/// a 12-hour clock face, with red (hue 0) at 12 o'clock and hue increasing clockwise. The result is
+/// in the range (0, 12], so a hue of 0 or 360 gives 12 rather than 0. Hues outside of 0-360 are
+/// wrapped around first. This is handy for radial color pickers that show hue as an angle.
+/// # Example
+///
+/// ```
+/// # use scarlet::color::hue_to_oclock;
+/// assert_eq!(hue_to_oclock(0.), 12.);
+/// assert_eq!(hue_to_oclock(90.), 3.);
+/// assert_eq!(hue_to_oclock(-90.), 9.);
+/// ```
+pub fn hue_to_oclock(hue: f64) -> f64 {
+    let oclock = hue.rem_euclid(360.0) / 30.0;
+    if oclock == 0.0 {
+        12.0
+    } else {
+        oclock
+    }
+}
+
+/// The inverse of [`hue_to_oclock`](fn.hue_to_oclock.html): maps a position on a 12-hour clock
+/// face, with red at 12 o'clock, to a hue in degrees between 0 and 360. Positions outside of 0-12 are
+/// wrapped around first.
+/// # Example
+///
+/// ```
+/// # use scarlet::color::oclock_to_hue;
+/// assert_eq!(oclock_to_hue(12.), 0.);
+/// assert_eq!(oclock_to_hue(6.), 180.);
+/// assert_eq!(oclock_to_hue(1.5), 45.);
+/// ```
+pub fn oclock_to_hue(oclock: f64) -> f64 {
+    (oclock * 30.0).rem_euclid(360.0)
+}
+
+/// Measures how varied a set of colors is, as the mean CIEDE2000
+/// [`distance`](trait.Color.html#method.distance) between every pair of colors in it. A palette of
+/// near-identical colors has a spread near 0, while a rainbow has a spread in the dozens. This is
+/// useful for checking palette consistency, or for tuning palette generators toward a target
+/// spread. Sets with fewer than two colors have a spread of 0.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::perceptual_spread;
+/// let blues: Vec<RGBColor> = ["#1F4E79", "#2E75B6", "#9DC3E6"]
+///     .iter()
+///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+///     .collect();
+/// let mixed: Vec<RGBColor> = ["#1F4E79", "#C00000", "#FFC000"]
+///     .iter()
+///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+///     .collect();
+/// assert!(perceptual_spread(&blues) < perceptual_spread(&mixed));
+/// ```
+pub fn perceptual_spread(colors: &[RGBColor]) -> f64 {
+    if colors.len() < 2 {
+        return 0.0;
+    }
+    let mut total = 0.0;
+    for (i, c1) in colors.iter().enumerate() {
+        for c2 in colors.iter().skip(i + 1) {
+            total += c1.distance(c2);
+        }
+    }
+    let pairs = colors.len() * (colors.len() - 1) / 2;
+    total / pairs as f64
+}
+
+/// The CIELCH chroma below which [`harmony_score`](fn.harmony_score.html) treats a color as neutral.
+const HARMONY_NEUTRAL_CHROMA: f64 = 10.0;
+
+/// Gives a heuristic score, from 0 to 1, of how well a set of colors goes together, for ranking
+/// candidate palettes automatically. Higher is more harmonious. Taste can't be reduced to a
+/// formula, so treat this as a rough filter, not a verdict: it's most useful for throwing out the
+/// worst of a batch of generated palettes.
+///
+/// The score is computed from the CIELCH lightness, chroma, and hue of each color, ignoring colors
+/// with a chroma below 10: grays, blacks, and whites go with anything. It's the product of two
+/// parts:
+///
+/// - **Hue harmony**: for every pair of colors, the angle between their hues is compared to the
+///   classic harmonious relationships: *analogous* (30 degrees apart or less), *triadic* (120
+///   degrees), and *complementary* (180 degrees). A pair scores 1 at one of these, and falls off as
+///   a bell curve with a standard deviation of 15 degrees as it moves away from the closest one.
+///   Hue harmony is the average over all pairs.
+/// - **Lightness clash**: strongly colored hues that are far apart but have about the same
+///   lightness seem to vibrate against each other where they meet, the way pure red and green do.
+///   Each pair is given a clash from 0 to 1 that's larger the closer their CIELAB lightnesses are
+///   (none past 20 apart), the more colorful the weaker of the two is (full strength at a chroma of
+///   50), and the farther apart their hues are past 90 degrees. The score is multiplied by 1 minus
+///   half of the average clash.
+///
+/// Palettes with fewer than two colorful colors have nothing to clash and score 1.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::harmony_score;
+/// let to_rgb = |hexes: &[&str]| -> Vec<RGBColor> {
+///     hexes.iter().map(|hex| RGBColor::from_hex_code(hex).unwrap()).collect()
+/// };
+/// // a triadic palette
+/// let triadic = to_rgb(&["#C0504D", "#4D9C50", "#4F6FC0"]);
+/// // hues 50 to 80 degrees apart, which don't relate to each other
+/// let clashing = to_rgb(&["#E03C8A", "#D98A1E", "#5EB53A", "#2F8FC9"]);
+/// assert!(harmony_score(&triadic) > harmony_score(&clashing));
+/// ```
+pub fn harmony_score(colors: &[RGBColor]) -> f64 {
+    let lchs: Vec<CIELCHColor> = colors
+        .iter()
+        .map(|color| color.convert::<CIELCHColor>())
+        .filter(|lch| lch.c >= HARMONY_NEUTRAL_CHROMA)
+        .collect();
+    if lchs.len() < 2 {
+        return 1.0;
+    }
+    let mut hue_harmony = 0.0;
+    let mut clash = 0.0;
+    for (i, c1) in lchs.iter().enumerate() {
+        for c2 in lchs.iter().skip(i + 1) {
+            // the angle between the hues, from 0 to 180
+            let diff = (c1.h - c2.h).rem_euclid(360.0);
+            let diff = diff.min(360.0 - diff);
+            // how far that is from analogous, triadic, or complementary
+            let off = [
+                (diff - 30.0).max(0.0),
+                (diff - 120.0).abs(),
+                (diff - 180.0).abs(),
+            ]
+            .iter()
+            .cloned()
+            .fold(f64::INFINITY, f64::min);
+            hue_harmony += (-off.powi(2) / (2.0 * 15.0_f64.powi(2))).exp();
+
+            let lightness_closeness = (1.0 - (c1.l - c2.l).abs() / 20.0).max(0.0);
+            let colorfulness = (c1.c.min(c2.c) / 50.0).min(1.0);
+            let opposition = ((diff - 90.0) / 90.0).max(0.0);
+            clash += lightness_closeness * colorfulness * opposition;
+        }
+    }
+    let pairs = (lchs.len() * (lchs.len() - 1) / 2) as f64;
+    (hue_harmony / pairs) * (1.0 - 0.5 * clash / pairs)
+}
+
+/// Builds a ramp of `n` grays from black to white, evenly spaced in CIELAB lightness. Unlike a ramp
+/// that's even in RGB, which bunches up in the lights and spreads out in the darks, each step of
+/// this ramp looks about as big as any other, which makes it good for legends and test patterns. A
+/// ramp of one color is just middle gray, at lightness 50.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::grayscale_ramp;
+/// let ramp = grayscale_ramp(5);
+/// let hexes: Vec<String> = ramp.iter().map(|c| c.to_string()).collect();
+/// assert_eq!(hexes, vec!["#000000", "#3B3B3B", "#777777", "#B9B9B9", "#FFFFFF"]);
+/// ```
+pub fn grayscale_ramp(n: usize) -> Vec<RGBColor> {
+    let lightness = |i: usize| {
+        if n == 1 {
+            50.0
+        } else {
+            100.0 * i as f64 / (n - 1) as f64
+        }
+    };
+    (0..n)
+        .map(|i| {
+            let rgb: RGBColor = CIELCHColor {
+                l: lightness(i),
+                c: 0.0,
+                h: 0.0,
+            }
+            .convert();
+            // grays should be exactly neutral in sRGB, without any rounding error in the chroma
+            let v = (rgb.r + rgb.g + rgb.b) / 3.0;
+            RGBColor {
+                r: v.clamp(0.0, 1.0),
+                g: v.clamp(0.0, 1.0),
+                b: v.clamp(0.0, 1.0),
+            }
+        })
+        .collect()
+}
+
+/// Tints an image to a single hue, in place: each pixel keeps its CIELAB lightness, but its CIELCH
+/// hue and chroma are replaced by `target_hue` (in degrees) and `chroma`. This is the classic
+/// single-color tint effect, like a sepia or cyanotype print, and keeps all of the detail carried by
+/// lightness. Very light and very dark pixels can't have much chroma in sRGB, so a large `chroma`
+/// can push some pixels out of gamut: see [`clamp`](../bound/trait.Bound.html#method.clamp) to bring
+/// them back.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::tint_preserving_luminance;
+/// let mut pixels = vec![
+///     RGBColor::from_hex_code("#CC3333").unwrap(),
+///     RGBColor::from_hex_code("#33CC33").unwrap(),
+/// ];
+/// // a blue tint
+/// tint_preserving_luminance(&mut pixels, 260., 30.);
+/// for pixel in pixels.iter() {
+///     assert!((pixel.hue() - 260.).abs() <= 1e-6);
+///     assert!((pixel.chroma() - 30.).abs() <= 1e-6);
+/// }
+/// ```
+pub fn tint_preserving_luminance(pixels: &mut [RGBColor], target_hue: f64, chroma: f64) {
+    for pixel in pixels.iter_mut() {
+        let lab: CIELABColor = pixel.convert();
+        *pixel = CIELCHColor {
+            l: lab.l,
+            c: chroma,
+            h: target_hue.rem_euclid(360.0),
+        }
+        .convert();
+    }
+}
+
+/// Applies a duotone to an image in place, the two-ink effect popular in graphic design: each pixel
+/// is replaced by a color on the gradient from `shadow` to `highlight` in CIELAB, chosen by the
+/// pixel's CIELAB lightness. Black becomes `shadow`, white becomes `highlight`, and everything else
+/// falls in between. For a single-hue tint, see
+/// [`tint_preserving_luminance`](fn.tint_preserving_luminance.html).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::duotone;
+/// let navy = RGBColor::from_hex_code("#1B2A49").unwrap();
+/// let peach = RGBColor::from_hex_code("#FFC9A0").unwrap();
+/// let mut pixels = vec![
+///     RGBColor::from_hex_code("#000000").unwrap(),
+///     RGBColor::from_hex_code("#FFFFFF").unwrap(),
+/// ];
+/// duotone(&mut pixels, navy, peach);
+/// assert_eq!(pixels[0].to_string(), "#1B2A49");
+/// assert_eq!(pixels[1].to_string(), "#FFC9A0");
+/// ```
+pub fn duotone(pixels: &mut [RGBColor], shadow: RGBColor, highlight: RGBColor) {
+    let shadow_lab: CIELABColor = shadow.convert();
+    let highlight_lab: CIELABColor = highlight.convert();
+    let gradient = shadow_lab.gradient(&highlight_lab);
+    for pixel in pixels.iter_mut() {
+        let t = (pixel.lightness() / 100.0).clamp(0.0, 1.0);
+        *pixel = gradient(t).convert();
+    }
+}
+
+/// Formats a number for CSS with at most two decimal places and no trailing zeros, so 50.0 is
+/// written `50` and 50.196 is written `50.2`.
+pub(crate) fn css_number(x: f64) -> String {
+    // adding 0.0 turns -0.0 into 0.0, which would otherwise print as "-0"
+    format!("{}", (x * 100.0).round() / 100.0 + 0.0)
+}
+
+/// Formats an RGB component, clamped to lie between 0 and 1, as a CSS percentage without the `%`
+/// sign, as in [`RGBColor::to_css_percent`](struct.RGBColor.html#method.to_css_percent).
+pub(crate) fn css_percent(x: f64) -> String {
+    css_number(x.clamp(0.0, 1.0) * 100.0)
+}
+
+/// Decodes a single sRGB component, undoing the sRGB transfer function ("gamma") to give linear
+/// light: the inverse of [`srgb_encode`](fn.srgb_encode.html). Components of
+/// [`RGBColor`](struct.RGBColor.html) are encoded, so this is what's needed to, say, average pixels
+/// the way light adds up. Values at or below 0.04045, including negative ones, are on the curve's
+/// linear segment.
+/// # Example
+///
+/// ```
+/// # use scarlet::color::{srgb_decode, srgb_encode};
+/// // sRGB mid-gray is only about a fifth of the light of white
+/// assert!((srgb_decode(0.5) - 0.2140).abs() <= 1e-4);
+/// assert!((srgb_encode(srgb_decode(0.5)) - 0.5).abs() <= 1e-12);
+/// ```
+pub fn srgb_decode(x: f64) -> f64 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a single linear light value with the sRGB transfer function, giving a component as
+/// stored in an [`RGBColor`](struct.RGBColor.html): the inverse of
+/// [`srgb_decode`](fn.srgb_decode.html). Values at or below 0.0031308, including negative ones, are
+/// on the curve's linear segment.
+pub fn srgb_encode(x: f64) -> f64 {
+    if x <= 0.0031308 {
+        12.92 * x
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Decodes a buffer of sRGB components to linear light in place, exactly as
+/// [`srgb_decode`](fn.srgb_decode.html) does for each one. The components can be laid out in any
+/// way, like interleaved `[r, g, b, r, g, b, ...]` pixels, which is handy for raw image buffers
+/// that were never `RGBColor`s to begin with.
+/// # Example
+///
+/// ```
+/// # use scarlet::color::{srgb_decode_slice, srgb_encode_slice};
+/// let mut pixels = vec![0., 0.5, 1., 0.25, 0.75, 0.02];
+/// srgb_decode_slice(&mut pixels);
+/// assert!((pixels[1] - 0.2140).abs() <= 1e-4);
+/// srgb_encode_slice(&mut pixels);
+/// assert!((pixels[1] - 0.5).abs() <= 1e-12);
+/// ```
+pub fn srgb_decode_slice(values: &mut [f64]) {
+    for x in values.iter_mut() {
+        *x = srgb_decode(*x);
+    }
+}
+
+/// Encodes a buffer of linear light values with the sRGB transfer function in place, exactly as
+/// [`srgb_encode`](fn.srgb_encode.html) does for each one. See
+/// [`srgb_decode_slice`](fn.srgb_decode_slice.html) for the inverse.
+pub fn srgb_encode_slice(values: &mut [f64]) {
+    for x in values.iter_mut() {
+        *x = srgb_encode(*x);
+    }
+}
+
+lazy_static! {
+    // The linear light value of each 8-bit sRGB level, so decoding 8-bit pixels is a lookup instead
+    // of a powf per channel.
+    static ref SRGB_U8_TO_LINEAR: [f64; 256] = {
+        let mut table = [0.0; 256];
+        for (i, x) in table.iter_mut().enumerate() {
+            *x = srgb_decode(i as f64 / 255.0);
+        }
+        table
+    };
+    // The xterm 256-color palette from index 16 on: a 6x6x6 color cube followed by a ramp of 24
+    // grays. The first 16 entries are left out, because terminals let users change them freely.
+    static ref XTERM_256_PALETTE: Vec<RGBColor> = {
+        let cube_level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+        let mut palette = Vec::with_capacity(240);
+        for r in 0..6 {
+            for g in 0..6 {
+                for b in 0..6 {
+                    palette.push(RGBColor::from((cube_level(r), cube_level(g), cube_level(b))));
+                }
+            }
+        }
+        for i in 0..24 {
+            let gray = 8 + 10 * i;
+            palette.push(RGBColor::from((gray, gray, gray)));
+        }
+        palette
+    };
+    // Every X11 color name, in alphabetical order, paired with its color.
+    static ref X11_NAMED_COLORS: Vec<(&'static str, RGBColor)> = consts::X11_NAMES
+        .iter()
+        .zip(consts::X11_COLOR_CODES.iter())
+        .map(|(&name, code)| (name, RGBColor::from_hex_code(code).expect("X11 color codes are valid.")))
+        .collect();
+}
+
+/// Reduces an image to the colors in `palette` in place, using Floyd–Steinberg error diffusion. The
+/// image is given row by row, `width` pixels to a row. Each pixel, in order, is replaced with the
+/// nearest palette color as given by
+/// [`nearest_in_palette`](struct.RGBColor.html#method.nearest_in_palette), and the difference
+/// between the two is spread to the pixels that haven't been visited yet: 7/16 to the right, and 3/16,
+/// 5/16, and 1/16 to the lower left, below, and lower right. Error is tracked in linear light, so
+/// that a dithered area has the same average brightness as the original.
+///
+/// # Panics
+/// Panics if `width` is 0 or `palette` is empty.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::dither_floyd_steinberg;
+/// let black = RGBColor{r: 0., g: 0., b: 0.};
+/// let white = RGBColor{r: 1., g: 1., b: 1.};
+/// // a 16x16 image of a gray that's 25% as bright as white
+/// let gray = RGBColor{r: 0.5371, g: 0.5371, b: 0.5371};
+/// let mut pixels = vec![gray; 256];
+/// dither_floyd_steinberg(&mut pixels, 16, &[black, white]);
+/// // about a quarter of the pixels become white, and the rest black
+/// let num_white = pixels.iter().filter(|&&p| p == white).count();
+/// let num_black = pixels.iter().filter(|&&p| p == black).count();
+/// assert!(num_white >= 48 && num_white <= 80);
+/// assert_eq!(num_white + num_black, 256);
+/// ```
+pub fn dither_floyd_steinberg(pixels: &mut [RGBColor], width: usize, palette: &[RGBColor]) {
+    assert!(width > 0, "Image width must be positive");
+    assert!(!palette.is_empty(), "Palette is empty");
+    let to_linear = |c: &RGBColor| [srgb_decode(c.r), srgb_decode(c.g), srgb_decode(c.b)];
+    let palette_linear: Vec<[f64; 3]> = palette.iter().map(to_linear).collect();
+    let mut linear: Vec<[f64; 3]> = pixels.iter().map(to_linear).collect();
+    let n = pixels.len();
+    for i in 0..n {
+        // accumulated error can push values out of range: clamp, so the error can't snowball
+        let value = linear[i].map(|x| x.clamp(0.0, 1.0));
+        let current = RGBColor {
+            r: srgb_encode(value[0]),
+            g: srgb_encode(value[1]),
+            b: srgb_encode(value[2]),
+        };
+        let index = current.nearest_in_palette(palette);
+        pixels[i] = palette[index];
+        let error: Vec<f64> = (0..3)
+            .map(|k| value[k] - palette_linear[index][k])
+            .collect();
+
+        let (x, y) = (i % width, i / width);
+        let mut diffuse = |dx: isize, dy: usize, weight: f64| {
+            let nx = x as isize + dx;
+            if nx < 0 || nx as usize >= width {
+                return;
+            }
+            let j = (y + dy) * width + nx as usize;
+            if j < n {
+                for k in 0..3 {
+                    linear[j][k] += error[k] * weight;
+                }
+            }
+        };
+        diffuse(1, 0, 7.0 / 16.0);
+        diffuse(-1, 1, 3.0 / 16.0);
+        diffuse(0, 1, 5.0 / 16.0);
+        diffuse(1, 1, 1.0 / 16.0);
+    }
+}
+
+/// Remaps an image to a palette in place, keeping smooth gradients smooth. Replacing each pixel
+/// with its nearest palette color turns gradients into flat bands, so instead each pixel becomes
+/// the closest blend of two palette colors: it's projected in CIELAB onto each segment between two
+/// palette colors, and the nearest of these projections is used. Colors that are already in the
+/// palette, or exactly between two of its colors, are left unchanged. Because every pair of colors
+/// is checked, this takes time quadratic in the size of the palette, so it's meant for small
+/// palettes. For hard quantization, see [`dither_floyd_steinberg`](fn.dither_floyd_steinberg.html)
+/// or [`nearest_in_palette`](struct.RGBColor.html#method.nearest_in_palette).
+///
+/// # Panics
+/// Panics if `palette` is empty.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::remap_to_palette_smooth;
+/// let palette: Vec<RGBColor> = ["#000080", "#FFFFFF", "#FFA500"]
+///     .iter()
+///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+///     .collect();
+/// let mut pixels = vec![palette[0], RGBColor::from_hex_code("#7F7FC0").unwrap()];
+/// remap_to_palette_smooth(&mut pixels, &palette);
+/// assert_eq!(pixels[0].to_string(), "#000080");
+/// // a blend, rather than a palette color
+/// assert!(palette.iter().all(|c| c.distance(&pixels[1]) > 5.));
+/// ```
+pub fn remap_to_palette_smooth(pixels: &mut [RGBColor], palette: &[RGBColor]) {
+    assert!(!palette.is_empty(), "Palette is empty");
+    let labs: Vec<Coord> = palette
+        .iter()
+        .map(|color| {
+            let lab: CIELABColor = color.convert();
+            lab.into()
+        })
+        .collect();
+    let dot = |a: Coord, b: Coord| a.x * b.x + a.y * b.y + a.z * b.z;
+    for pixel in pixels.iter_mut() {
+        let lab: CIELABColor = pixel.convert();
+        let point: Coord = lab.into();
+        // a lone palette color is its own closest point
+        let mut best = labs[0];
+        let mut best_dist = point.euclidean_distance(&best);
+        for (i, &start) in labs.iter().enumerate() {
+            for &end in labs[i + 1..].iter() {
+                let edge = end - start;
+                let length_sq = dot(edge, edge);
+                let t = if length_sq == 0.0 {
+                    0.0
+                } else {
+                    (dot(point - start, edge) / length_sq).clamp(0.0, 1.0)
+                };
+                let projection = start + edge * t;
+                let dist = point.euclidean_distance(&projection);
+                if dist < best_dist {
+                    best = projection;
+                    best_dist = dist;
+                }
+            }
+        }
+        *pixel = CIELABColor::from(best).convert();
+    }
+}
+
+/// Returns `false` if the user has asked for output without color by setting the `NO_COLOR`
+/// environment variable to anything other than the empty string, following the convention at
+/// <https://no-color.org>, and `true` otherwise. Pass the result to
+/// [`write_color_if`](trait.Color.html#method.write_color_if) and
+/// [`write_colored_str_if`](trait.Color.html#method.write_colored_str_if) to respect it. This
+/// doesn't check whether output is going to a terminal at all: that's up to the caller.
+pub fn terminal_colors_enabled() -> bool {
+    match env::var_os("NO_COLOR") {
+        Some(val) => val.is_empty(),
+        None => true,
+    }
 }
 
+/// The point in the CIE 1960 UCS diagram that [`duv`](trait.Color.html#method.duv) measures from:
+/// roughly the center of the blackbody locus's curvature, as given in ANSI C78.377.
+const DUV_REFERENCE_UV: (f64, f64) = (0.292, 0.24);
+
 /// A trait that represents any color representation that can be converted to and from the CIE 1931 XYZ
 /// color space. See module-level documentation for more information and examples.
 pub trait Color: Sized {
@@ -290,10 +1025,15 @@ pub trait Color: Sized {
     /// ```
     fn to_xyz(&self, illuminant: Illuminant) -> XYZColor;
     /// Converts generic colors from one representation to another. This is done by going back and
-    /// forth from the CIE 1931 XYZ space, using the illuminant D50 (although this should not affect
-    /// the results). Just like [`collect()`] and other methods in the standard library, the use of
-    /// type inference will usually allow for clean syntax, but occasionally the turbofish is
-    /// necessary.
+    /// forth from the CIE 1931 XYZ space, using the illuminant D50. Spaces defined under another
+    /// illuminant, like sRGB under D65, chromatically adapt to and from D50 on the way, but the
+    /// adaptation is undone exactly up to floating-point error: converting a color between two D65
+    /// spaces and back a hundred times over drifts by around 1e-13 whether or not it goes through
+    /// D50. The illuminant only changes the result when converting to
+    /// [`XYZColor`](struct.XYZColor.html), which keeps whatever illuminant it's given: to choose it,
+    /// use [`convert_with_illuminant`](#method.convert_with_illuminant). Just like [`collect()`] and
+    /// other methods in the standard library, the use of type inference will usually allow for clean
+    /// syntax, but occasionally the turbofish is necessary.
     ///
     /// # Example
     ///
@@ -313,14 +1053,37 @@ pub trait Color: Sized {
     ///
     /// [`collect()`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.collect
     fn convert<T: Color>(&self) -> T {
-        // theoretically, the illuminant shouldn't matter as long as the color conversions are
-        // correct. D50 is a common gamut for use in internal conversions, so for spaces like CIELAB
-        // it will produce the least error
+        // the illuminant doesn't matter as long as the color conversions are correct, except for
+        // XYZColor itself. D50 is a common gamut for use in internal conversions, so for spaces like
+        // CIELAB it will produce the least error: see test_convert_intermediate_illuminant for how
+        // little the trip to D50 costs spaces defined under D65
         T::from_xyz(self.to_xyz(Illuminant::D50))
     }
+    /// Converts this color into another color space like [`convert`](#method.convert), but goes
+    /// through XYZ under the given illuminant instead of D50. For most color spaces this makes no
+    /// visible difference, as each converts from XYZ under whatever illuminant it's given, but it
+    /// does for [`XYZColor`](struct.XYZColor.html) itself, which keeps the illuminant it's given,
+    /// and going between two spaces defined under D65, like sRGB and Adobe RGB, avoids adapting to
+    /// D50 and back.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// let d50: XYZColor = red.convert();
+    /// let d65: XYZColor = red.convert_with_illuminant(Illuminant::D65);
+    /// assert_eq!(d50.illuminant, Illuminant::D50);
+    /// assert_eq!(d65.illuminant, Illuminant::D65);
+    /// // sRGB red is defined under D65 to have these coordinates
+    /// assert!((d65.x - 0.4124).abs() <= 1e-3 && (d65.y - 0.2126).abs() <= 1e-3);
+    /// ```
+    fn convert_with_illuminant<T: Color>(&self, illuminant: Illuminant) -> T {
+        T::from_xyz(self.to_xyz(illuminant))
+    }
     /// "Colors" a given piece of text with terminal escape codes to allow it to be printed out in the
-    /// given foreground color. Will cause problems with terminals that do not support truecolor.
-    /// Requires the `terminal` feature.
+    /// given foreground color. Will cause problems with terminals that do not support truecolor:
+    /// see [`write_color_256`](#method.write_color_256) for those.
     ///
     /// # Example
     /// This demo prints out a square of colors that have the same luminance in CIELAB and HSL to
@@ -351,14 +1114,12 @@ pub trait Color: Sized {
     ///     println!("{}", line);
     /// }
     /// ```
-    #[cfg(feature = "terminal")]
     fn write_colored_str(&self, text: &str) -> String {
         let rgb: RGBColor = self.convert();
         rgb.base_write_colored_str(text)
     }
     /// Returns a string which, when printed in a truecolor-supporting terminal, will have both the
-    /// foreground and background of the desired color, appearing as a complete square. Requires the
-    /// `terminal` feature
+    /// foreground and background of the desired color, appearing as a complete square.
     ///
     /// # Example
     /// This is the same one as above, but with a complete block of color instead of the # mark.
@@ -386,11 +1147,86 @@ pub trait Color: Sized {
     ///     println!("{}", line);
     /// }
     /// ```
-    #[cfg(feature = "terminal")]
     fn write_color(&self) -> String {
         let rgb: RGBColor = self.convert();
         rgb.base_write_color()
     }
+    /// Like [`write_colored_str`](#method.write_colored_str), but returns the text unchanged, with
+    /// no escape codes at all, if `enabled` is `false`. This is for output that may not be going to
+    /// a terminal, like a log file: see
+    /// [`terminal_colors_enabled`](fn.terminal_colors_enabled.html) to follow the user's wishes.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// assert_eq!(red.write_colored_str_if("error", false), "error");
+    /// assert_eq!(red.write_colored_str_if("error", true), red.write_colored_str("error"));
+    /// ```
+    fn write_colored_str_if(&self, text: &str, enabled: bool) -> String {
+        if enabled {
+            self.write_colored_str(text)
+        } else {
+            text.to_string()
+        }
+    }
+    /// Like [`write_color`](#method.write_color), but if `enabled` is `false` returns the color's
+    /// sRGB hex code, like `#FF0000`, instead of a colored square, so the output still says which
+    /// color it was.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::terminal_colors_enabled;
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// assert_eq!(red.write_color_if(false), "#FF0000");
+    /// // respects NO_COLOR
+    /// println!("{}", red.write_color_if(terminal_colors_enabled()));
+    /// ```
+    fn write_color_if(&self, enabled: bool) -> String {
+        if enabled {
+            self.write_color()
+        } else {
+            self.convert::<RGBColor>().to_string()
+        }
+    }
+    /// Returns a string which, when printed in a terminal, shows a complete square of the closest
+    /// color in the xterm 256-color palette, as found by
+    /// [`RGBColor::to_xterm_256`](struct.RGBColor.html#method.to_xterm_256). This is like
+    /// [`write_color`](#method.write_color), but works on the many terminals that support 256 colors
+    /// and not truecolor.
+    fn write_color_256(&self) -> String {
+        let rgb: RGBColor = self.convert();
+        let index = rgb.to_xterm_256();
+        format!("\x1b[48;5;{index}m\x1b[38;5;{index}m■\x1b[39m\x1b[49m")
+    }
+    /// Returns a minimal standalone SVG image of the given size in pixels, consisting of a single
+    /// rectangle filled with this color's sRGB hex code. This is handy for emitting color swatches
+    /// in documentation or tooling output without pulling in any rendering dependency.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let swatch = RGBColor::from_hex_code("#B22222").unwrap().to_svg_rect(40, 20);
+    /// assert_eq!(
+    ///     swatch,
+    ///     concat!(
+    ///         r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20">"#,
+    ///         r##"<rect width="40" height="20" fill="#B22222"/></svg>"##
+    ///     )
+    /// );
+    /// ```
+    fn to_svg_rect(&self, width: u32, height: u32) -> String {
+        let rgb: RGBColor = self.convert();
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\">\
+             <rect width=\"{w}\" height=\"{h}\" fill=\"{fill}\"/></svg>",
+            w = width,
+            h = height,
+            fill = rgb.to_string()
+        )
+    }
 
     /// Gets the generally most accurate version of hue for a given color: the hue coordinate in
     /// CIELCH. There are generally considered four "unique hues" that humans perceive as not
@@ -444,6 +1280,22 @@ pub trait Color: Sized {
     /// assert!(!red.visually_indistinguishable(&RGBColor{r: 1., g: 0., b: 0.}));
     /// ```
     fn set_hue(&mut self, new_hue: f64) {
+        *self = self.with_hue(new_hue);
+    }
+
+    /// Returns a copy of this color with the given hue, leaving this color unchanged. This works
+    /// exactly like [`set_hue`](#method.set_hue), but is more convenient for chaining.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor::from_hex_code("#CC3333").unwrap();
+    /// let green = red.with_hue(140.).with_lightness(60.);
+    /// assert!((green.hue() - 140.).abs() <= 1e-6);
+    /// assert!((green.lightness() - 60.).abs() <= 1e-6);
+    /// assert_eq!(red.to_string(), "#CC3333");
+    /// ```
+    fn with_hue(&self, new_hue: f64) -> Self {
         let mut lch: CIELCHColor = self.convert();
         lch.h = if (0.0..=360.0).contains(&new_hue) {
             new_hue
@@ -452,7 +1304,62 @@ pub trait Color: Sized {
         } else {
             new_hue - 360.0 * (new_hue / 360.0).ceil()
         };
-        *self = lch.convert();
+        lch.convert()
+    }
+
+    /// Recolors this color to the given CIELCH hue, for things like matching an icon set to a brand
+    /// color. Lightness is kept, and so is how colorful this color is *relative to the most colorful
+    /// sRGB color at its lightness and hue*: simply keeping the chroma, as
+    /// [`with_hue`](#method.with_hue) does, can leave the sRGB gamut, as some hues reach much higher
+    /// chroma than others. A color at the edge of the gamut stays at the edge, a muted color stays just
+    /// as muted, and grays stay gray. As with `with_hue`, the hue wraps around to lie from 0 to 360.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// // the most vivid red there is, recolored to blue
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// let blue = red.recolor_to_hue(300.);
+    /// assert!((blue.hue() - 300.).abs() <= 1e-6);
+    /// assert!((blue.lightness() - red.lightness()).abs() <= 1e-6);
+    /// // stays in gamut, unlike red.with_hue(300.)
+    /// assert!(blue.in_rgb_gamut::<RGBColor>());
+    /// assert!(!red.with_hue(300.).in_rgb_gamut::<RGBColor>());
+    /// ```
+    fn recolor_to_hue(&self, target_hue: f64) -> Self {
+        let mut lch: CIELCHColor = self.convert();
+        let target_hue = target_hue.rem_euclid(360.0);
+        let old_max = RGBColor::max_chroma(lch.l, lch.h);
+        if old_max > 0.0 {
+            lch.c *= RGBColor::max_chroma(lch.l, target_hue) / old_max;
+        }
+        lch.h = target_hue;
+        lch.convert()
+    }
+
+    /// Moves this color toward another by the given fraction of the way, in a straight line in CIELAB:
+    /// 0 gives this color, 1 gives the other, and 0.2 tints this color lightly with the other. CIELAB
+    /// is close to perceptually uniform, so equal steps look roughly equal. `amount` isn't clamped,
+    /// so values past 1 or below 0 extrapolate.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let gray = RGBColor::from_hex_code("#808080").unwrap();
+    /// let brand = RGBColor::from_hex_code("#E4002B").unwrap();
+    /// let tinted = gray.tint_toward(&brand, 0.25);
+    /// assert!((tinted.hue() - brand.hue()).abs() <= 1.);
+    /// assert!(tinted.chroma() < brand.chroma());
+    /// ```
+    fn tint_toward(&self, other: &Self, amount: f64) -> Self {
+        let from: CIELABColor = self.convert();
+        let to: CIELABColor = other.convert();
+        CIELABColor {
+            l: from.l + (to.l - from.l) * amount,
+            a: from.a + (to.a - from.a) * amount,
+            b: from.b + (to.b - from.b) * amount,
+        }
+        .convert()
     }
 
     /// Gets a perceptually-accurate version of lightness as a value from 0 to 100, where 0 is black
@@ -526,6 +1433,13 @@ pub trait Color: Sized {
     /// assert!(yellow.s < 0.4);  // saturation has decreased a lot
     /// ```
     fn set_lightness(&mut self, new_lightness: f64) {
+        *self = self.with_lightness(new_lightness);
+    }
+
+    /// Returns a copy of this color with the given lightness, leaving this color unchanged. This
+    /// works exactly like [`set_lightness`](#method.set_lightness), but is more convenient for
+    /// chaining.
+    fn with_lightness(&self, new_lightness: f64) -> Self {
         let mut lab: CIELABColor = self.convert();
         lab.l = if (0.0..=100.0).contains(&new_lightness) {
             new_lightness
@@ -534,7 +1448,70 @@ pub trait Color: Sized {
         } else {
             100.0
         };
-        *self = lab.convert()
+        lab.convert()
+    }
+
+    /// Returns a copy of this color that's lighter by the given amount, where an amount of 1 is the
+    /// full range of CIELAB lightness: `lighten(0.2)` adds 20 to the lightness, clamping it to 100,
+    /// just as [`with_lightness`](#method.with_lightness) does. Because this keeps the CIELAB `a`
+    /// and `b` components, the hue and chroma stay put, unlike lightening in HSL, which makes hues
+    /// drift. Negative amounts darken.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let teal = RGBColor::from_hex_code("#00808F").unwrap();
+    /// let lighter = teal.lighten(0.2);
+    /// assert!((lighter.lightness() - teal.lightness() - 20.).abs() <= 1e-6);
+    /// assert!((lighter.hue() - teal.hue()).abs() <= 1e-6);
+    /// ```
+    fn lighten(&self, amount: f64) -> Self {
+        self.with_lightness(self.lightness() + amount * 100.0)
+    }
+
+    /// Returns a copy of this color that's darker by the given amount: the opposite of
+    /// [`lighten`](#method.lighten), so `darken(0.2)` subtracts 20 from the CIELAB lightness,
+    /// clamping it to 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let teal = RGBColor::from_hex_code("#00808F").unwrap();
+    /// let darker = teal.darken(0.2);
+    /// assert!((teal.lightness() - darker.lightness() - 20.).abs() <= 1e-6);
+    /// ```
+    fn darken(&self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Gets a color for drawing an outline around a swatch of this color, so that the swatch stays
+    /// visible even against a background of nearly the same color. The outline is this color shifted
+    /// by 12 units of CIELAB lightness, darker for light colors and lighter for dark colors, which is
+    /// a subtle but clearly visible difference. The result is clamped to the sRGB gamut.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let swatch = RGBColor::from_hex_code("#F4E8D0").unwrap();
+    /// let outline = swatch.outline_color();
+    /// // light colors get a darker outline of the same hue
+    /// assert!(outline.lightness() < swatch.lightness());
+    /// assert!((outline.hue() - swatch.hue()).abs() <= 5.);
+    /// ```
+    fn outline_color(&self) -> RGBColor {
+        let mut lab: CIELABColor = self.convert();
+        // go whichever way has more room
+        lab.l = if lab.l >= 50.0 {
+            (lab.l - 12.0).max(0.0)
+        } else {
+            (lab.l + 12.0).min(100.0)
+        };
+        let rgb: RGBColor = lab.convert();
+        RGBColor {
+            r: rgb.r.clamp(0.0, 1.0),
+            g: rgb.g.clamp(0.0, 1.0),
+            b: rgb.b.clamp(0.0, 1.0),
+        }
     }
 
     /// Gets a perceptually-accurate version of *chroma*, defined as colorfulness relative to a
@@ -578,9 +1555,15 @@ pub trait Color: Sized {
     /// // prints #CC00CC #AC4FA8
     /// ```
     fn set_chroma(&mut self, new_chroma: f64) {
+        *self = self.with_chroma(new_chroma);
+    }
+
+    /// Returns a copy of this color with the given chroma, leaving this color unchanged. This works
+    /// exactly like [`set_chroma`](#method.set_chroma), but is more convenient for chaining.
+    fn with_chroma(&self, new_chroma: f64) -> Self {
         let mut lch: CIELCHColor = self.convert();
         lch.c = if new_chroma < 0.0 { 0.0 } else { new_chroma };
-        *self = lch.convert();
+        lch.convert()
     }
 
     /// Gets a perceptually-accurate version of *saturation*, defined as chroma relative to
@@ -625,14 +1608,54 @@ pub trait Color: Sized {
     /// // prints #803333 #8B262C
     /// ```
     fn set_saturation(&mut self, new_sat: f64) {
+        *self = self.with_saturation(new_sat);
+    }
+
+    /// Returns a copy of this color with the given saturation, leaving this color unchanged. This
+    /// works exactly like [`set_saturation`](#method.set_saturation), but is more convenient for
+    /// chaining.
+    fn with_saturation(&self, new_sat: f64) -> Self {
         let mut lch: CIELCHColor = self.convert();
         lch.c = if new_sat < 0.0 { 0.0 } else { new_sat * lch.l };
-        *self = lch.convert();
+        lch.convert()
     }
-    /// Returns a new [`Color`] of the same type as before, but with chromaticity removed: effectively,
-    /// a color created solely using a mix of black and white that has the same lightness as
-    /// before. This uses the CIELAB luminance definition, which is considered a good standard and is
-    /// perceptually accurate for the most part.
+
+    /// Returns a copy of this color with its CIELCH chroma scaled up by the given relative amount:
+    /// `saturate(0.2)` gives 20% more chroma, keeping lightness and hue, as in LESS or Sass. This
+    /// uses [`with_chroma`](#method.with_chroma), so negative amounts desaturate, and the chroma
+    /// never goes below 0. Vivid colors can easily leave the gamut of a space like sRGB.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let dusty_rose = RGBColor::from_hex_code("#B07080").unwrap();
+    /// let vivid = dusty_rose.saturate(0.5);
+    /// assert!((vivid.chroma() - 1.5 * dusty_rose.chroma()).abs() <= 1e-6);
+    /// ```
+    fn saturate(&self, amount: f64) -> Self {
+        self.with_chroma(self.chroma() * (1.0 + amount))
+    }
+
+    /// Returns a copy of this color with its CIELCH chroma scaled down by the given relative amount:
+    /// the opposite of [`saturate`](#method.saturate). `desaturate(0.2)` gives 20% less chroma, and
+    /// `desaturate(1.0)` removes it entirely, the same as [`grayscale`](#method.grayscale).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let dusty_rose = RGBColor::from_hex_code("#B07080").unwrap();
+    /// assert_eq!(
+    ///     dusty_rose.desaturate(1.0).to_string(),
+    ///     dusty_rose.grayscale().to_string()
+    /// );
+    /// ```
+    fn desaturate(&self, amount: f64) -> Self {
+        self.saturate(-amount)
+    }
+    /// Returns a new [`Color`] of the same type as before, but with chromaticity removed: effectively,
+    /// a color created solely using a mix of black and white that has the same lightness as
+    /// before. This uses the CIELAB luminance definition, which is considered a good standard and is
+    /// perceptually accurate for the most part.
     /// # Example
     ///
     /// ```
@@ -661,6 +1684,382 @@ pub trait Color: Sized {
         lch.convert()
     }
 
+    /// Returns `true` if this color is gray to within the given tolerance: that is, if its CIELCH
+    /// [`chroma`](#method.chroma) is less than `tolerance`, and `false` otherwise. A chroma of about 1
+    /// is the least that can be noticed at all, while conversions between spaces can leave grays
+    /// with a tiny chroma: even exact sRGB grays measure about 0.008, because of rounding in the
+    /// white points the standards give. A tolerance of 0.1 is a good choice for most uses.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let almost_gray = RGBColor{r: 0.5, g: 0.5, b: 0.500001};
+    /// assert!(almost_gray.is_neutral(0.1));
+    /// assert!(!RGBColor{r: 0.5, g: 0.4, b: 0.5}.is_neutral(0.1));
+    /// ```
+    fn is_neutral(&self, tolerance: f64) -> bool {
+        self.chroma() < tolerance
+    }
+
+    /// If this color is gray to within the given tolerance, as decided by
+    /// [`is_neutral`](#method.is_neutral), returns the exact gray of the same lightness from
+    /// [`grayscale`](#method.grayscale). Otherwise, returns a copy of this color unchanged. This is
+    /// for cleaning up grays that picked up a tiny tint from rounding error in a chain of conversions.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELCHColor;
+    /// let almost_gray = CIELCHColor{l: 50., c: 0.02, h: 80.};
+    /// assert_eq!(almost_gray.snap_neutral(0.1).c, 0.);
+    /// let teal = RGBColor{r: 0., g: 0.5, b: 0.5};
+    /// assert_eq!(teal.snap_neutral(0.1), teal);
+    /// ```
+    fn snap_neutral(&self, tolerance: f64) -> Self
+    where
+        Self: Clone,
+    {
+        if self.is_neutral(tolerance) {
+            self.grayscale()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns `true` if this color can be represented in the gamut of the given color space `B`,
+    /// like sRGB or Adobe RGB: that is, if converting to `B` gives components that are all within
+    /// the [`Bound`](../bound/trait.Bound.html) of that space. Some spaces, like Adobe RGB, clip
+    /// colors to their gamut when converting, so this also checks that converting to `B` didn't
+    /// visibly change the color. To keep rounding error in the conversion matrices from rejecting
+    /// colors right on the edge of a gamut, components may be up to 0.001 outside the bounds, which
+    /// is well under a single 8-bit step for RGB spaces.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{AdobeRGBColor, CIELABColor};
+    /// // Adobe RGB has a much wider range of greens than sRGB
+    /// let adobe_green = AdobeRGBColor{r: 0., g: 1., b: 0.};
+    /// assert!(adobe_green.in_rgb_gamut::<AdobeRGBColor>());
+    /// assert!(!adobe_green.in_rgb_gamut::<RGBColor>());
+    /// // but neither has the most vivid colors of all
+    /// let lab = CIELABColor{l: 50., a: 0., b: -120.};
+    /// assert!(!lab.in_rgb_gamut::<RGBColor>() && !lab.in_rgb_gamut::<AdobeRGBColor>());
+    /// ```
+    fn in_rgb_gamut<B: Bound>(&self) -> bool {
+        let converted: B = self.convert();
+        let coord: Coord = converted.into();
+        let eps = 1e-3;
+        let in_bounds = B::bounds()
+            .iter()
+            .zip([coord.x, coord.y, coord.z].iter())
+            .all(|(&(min, max), &x)| x >= min - eps && x <= max + eps);
+        in_bounds && self.distance(&B::from(coord)) <= 0.1
+    }
+
+    /// Converts this color to sRGB for display, clipping any channels outside the range 0 to 1, and
+    /// reports which channels were clipped and how far the displayed color is from the real one. This
+    /// gives everything needed to decide whether clipping is acceptable, or whether something like
+    /// [`complement_in_gamut`](#method.complement_in_gamut)'s gamut mapping is called for. Channels
+    /// within 1e-6 of the range only have rounding error, and don't count as clipped.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// // a green more vivid than sRGB can show
+    /// let green = CIELABColor{l: 60., a: -90., b: 60.};
+    /// let info = green.to_srgb_info();
+    /// assert_eq!(info.clipped, [true, false, true]);
+    /// assert!(info.delta_e > 5.);
+    /// ```
+    fn to_srgb_info(&self) -> SrgbConversion {
+        let rgb: RGBColor = self.convert();
+        let eps = 1e-6;
+        let channels = [rgb.r, rgb.g, rgb.b];
+        let mut clipped = [false; 3];
+        for (c, &x) in clipped.iter_mut().zip(channels.iter()) {
+            *c = x < -eps || x > 1.0 + eps;
+        }
+        let clipped_rgb = RGBColor {
+            r: rgb.r.clamp(0.0, 1.0),
+            g: rgb.g.clamp(0.0, 1.0),
+            b: rgb.b.clamp(0.0, 1.0),
+        };
+        SrgbConversion {
+            rgb: clipped_rgb,
+            clipped,
+            delta_e: self.distance(&clipped_rgb),
+        }
+    }
+
+    /// Posterizes the color, the classic flat-design effect: each sRGB channel is snapped to the
+    /// nearest of `levels` evenly-spaced values from 0 to 1, so `posterize(2)` allows only 0 and 1
+    /// for each channel. Out-of-gamut channels are clamped first. See
+    /// [`posterize_lightness`](#method.posterize_lightness) for a perceptual variant.
+    ///
+    /// # Panics
+    /// Panics if `levels` is less than 2.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor{r: 0.3, g: 0.55, b: 0.9};
+    /// let poster = color.posterize(3);
+    /// assert_eq!(poster.to_string(), "#8080FF");
+    /// ```
+    fn posterize(&self, levels: usize) -> RGBColor {
+        assert!(levels >= 2, "Posterizing needs at least 2 levels");
+        let steps = (levels - 1) as f64;
+        let rgb: RGBColor = self.convert();
+        let quantize = |x: f64| (x.clamp(0.0, 1.0) * steps).round() / steps;
+        RGBColor {
+            r: quantize(rgb.r),
+            g: quantize(rgb.g),
+            b: quantize(rgb.b),
+        }
+    }
+
+    /// A perceptual take on [`posterize`](#method.posterize): instead of quantizing the sRGB
+    /// channels, this divides CIELAB lightness into `levels` equal bands and snaps the color to the
+    /// middle of its band, keeping its hue and chroma. The result has smooth, even tonal steps, and
+    /// isn't restricted to the sRGB gamut.
+    ///
+    /// # Panics
+    /// Panics if `levels` is 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let dark = RGBColor::from_hex_code("#101820").unwrap();
+    /// let light = RGBColor::from_hex_code("#E0E8F0").unwrap();
+    /// // with 2 levels, everything ends up at lightness 25 or 75
+    /// assert!((dark.posterize_lightness(2).lightness() - 25.).abs() <= 1e-6);
+    /// assert!((light.posterize_lightness(2).lightness() - 75.).abs() <= 1e-6);
+    /// ```
+    fn posterize_lightness(&self, levels: usize) -> Self {
+        assert!(levels >= 1, "Posterizing needs at least 1 level");
+        let width = 100.0 / levels as f64;
+        let mut lch: CIELCHColor = self.convert();
+        let band = (lch.l / width).floor().clamp(0.0, (levels - 1) as f64);
+        lch.l = width * (band + 0.5);
+        lch.convert()
+    }
+
+    /// Returns an *analogous* color scheme: `count` colors whose CIELCH hues are `spacing` degrees
+    /// apart and centered on the hue of this color, all sharing its lightness and chroma. With an odd
+    /// `count`, this color itself is in the middle of the returned list; with an even `count`, the
+    /// two middle colors are `spacing / 2` degrees on either side of it. Hues are wrapped to stay
+    /// between 0 and 360. Because chroma is kept the same, some of the returned colors might be
+    /// outside of a given gamut, just like with [`set_hue`](#method.set_hue).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let teal = RGBColor::from_hex_code("#2a9d8f").unwrap();
+    /// let scheme = teal.analogous_scheme(5, 20.);
+    /// assert_eq!(scheme.len(), 5);
+    /// // the middle color is the one we started with
+    /// assert_eq!(scheme[2].to_string(), teal.to_string());
+    /// for (c1, c2) in scheme.iter().zip(scheme.iter().skip(1)) {
+    ///     assert!((c2.hue() - c1.hue() - 20.).abs() <= 1e-6);
+    /// }
+    /// ```
+    fn analogous_scheme(&self, count: usize, spacing: f64) -> Vec<Self> {
+        let lch: CIELCHColor = self.convert();
+        // offset from the center of the scheme, in multiples of spacing
+        let center = (count as f64 - 1.0) / 2.0;
+        (0..count)
+            .map(|i| {
+                let mut new_lch = lch;
+                new_lch.h = (lch.h + (i as f64 - center) * spacing).rem_euclid(360.0);
+                new_lch.convert()
+            })
+            .collect()
+    }
+
+    /// Generates `count` mutually distinguishable sRGB colors whose CIELCH hues all lie in
+    /// `hue_range`, for when the hues in a palette are fixed, as in a brand's design system.
+    /// `hue_range` is given as `(start, end)` in degrees, running counterclockwise from `start` to
+    /// `end`: if `start` is larger than `end`, the range wraps through 0, so `(330., 30.)` is the
+    /// reds. Because the hues can't vary much, the colors are kept apart by varying lightness and
+    /// chroma.
+    ///
+    /// This works greedily: candidates are sampled on a grid of hue, lightness, and chroma, and each
+    /// new color is the in-gamut candidate furthest by CIEDE2000 [`distance`](#method.distance) from
+    /// all of the colors chosen so far. The first color is this one if it's in the sRGB gamut and its
+    /// hue is in range, and the closest candidate to it otherwise. Narrow ranges and large counts
+    /// naturally make for less distinct colors.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let brand_blue = RGBColor::from_hex_code("#1565C0").unwrap();
+    /// let scheme = brand_blue.constrained_scheme(5, (250., 300.));
+    /// assert_eq!(scheme.len(), 5);
+    /// assert_eq!(scheme[0].to_string(), "#1565C0");
+    /// for color in scheme.iter() {
+    ///     assert!(color.hue() >= 250. && color.hue() <= 300.);
+    /// }
+    /// ```
+    fn constrained_scheme(&self, count: usize, hue_range: (f64, f64)) -> Vec<RGBColor> {
+        let (start, end) = (hue_range.0.rem_euclid(360.0), hue_range.1.rem_euclid(360.0));
+        let width = (end - start).rem_euclid(360.0);
+        let in_range = |h: f64| (h - start).rem_euclid(360.0) <= width;
+        let in_srgb = |rgb: &RGBColor| {
+            [rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|&x| (0.0..=1.0).contains(&x))
+        };
+        // sample the middles of the hue bins, to stay safely inside the range
+        let hue_steps = 9;
+        let mut candidates: Vec<RGBColor> = Vec::new();
+        for i in 0..hue_steps {
+            let h = (start + width * (i as f64 + 0.5) / hue_steps as f64).rem_euclid(360.0);
+            for l in (2..=9).map(|x| f64::from(x) * 10.0) {
+                for c in (1..=13).map(|x| f64::from(x) * 10.0) {
+                    let rgb: RGBColor = CIELCHColor { l, c, h }.convert();
+                    if in_srgb(&rgb) {
+                        candidates.push(rgb);
+                    }
+                }
+            }
+        }
+
+        let mut chosen: Vec<RGBColor> = Vec::with_capacity(count);
+        if count == 0 || candidates.is_empty() {
+            return chosen;
+        }
+        let this: RGBColor = self.convert();
+        let first = if in_srgb(&this) && in_range(this.hue()) {
+            this
+        } else {
+            *candidates
+                .iter()
+                .min_by(|a, b| {
+                    this.distance(*a)
+                        .partial_cmp(&this.distance(*b))
+                        .expect("Distances can't be NaN")
+                })
+                .expect("There are candidates")
+        };
+        chosen.push(first);
+        // the distance from each candidate to the closest chosen color
+        let mut min_dists: Vec<f64> = candidates.iter().map(|c| c.distance(&first)).collect();
+        while chosen.len() < count {
+            let (best, _) = min_dists
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).expect("Distances can't be NaN"))
+                .expect("There are candidates");
+            let next = candidates[best];
+            chosen.push(next);
+            for (dist, candidate) in min_dists.iter_mut().zip(candidates.iter()) {
+                *dist = dist.min(candidate.distance(&next));
+            }
+        }
+        chosen
+    }
+
+    /// Returns the complement of this color, its opposite in CIELCH hue, mapped into the sRGB gamut.
+    /// Rotating the hue of a vivid color by 180 degrees while keeping its lightness and chroma often
+    /// asks for a color that can't be displayed, like the complement of a bright yellow. This keeps the
+    /// complementary hue exactly, and looks for the displayable color closest to the ideal complement
+    /// by CIEDE2000 [`distance`](#method.distance): lightnesses within 20 of the original are each
+    /// tried with the largest chroma sRGB allows, up to the original chroma. If the ideal complement
+    /// is already displayable, it's returned as-is.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let yellow = RGBColor::from_hex_code("#FFE000").unwrap();
+    /// let complement = yellow.complement_in_gamut();
+    /// assert!((complement.hue() - (yellow.hue() + 180.) % 360.).abs() <= 1e-3);
+    /// for &x in [complement.r, complement.g, complement.b].iter() {
+    ///     assert!(x >= 0. && x <= 1.);
+    /// }
+    /// ```
+    fn complement_in_gamut(&self) -> RGBColor {
+        let lch: CIELCHColor = self.convert();
+        let ideal = CIELCHColor {
+            l: lch.l,
+            c: lch.c,
+            h: (lch.h + 180.0) % 360.0,
+        };
+        let to_srgb = |l: f64, c: f64| -> Option<RGBColor> {
+            let rgb: RGBColor = CIELCHColor { l, c, h: ideal.h }.convert();
+            if [rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|&x| (0.0..=1.0).contains(&x))
+            {
+                Some(rgb)
+            } else {
+                None
+            }
+        };
+        if let Some(rgb) = to_srgb(ideal.l, ideal.c) {
+            return rgb;
+        }
+        let mut best: Option<(f64, RGBColor)> = None;
+        for offset in -20..=20 {
+            let l = ideal.l + f64::from(offset);
+            if !(0.0..=100.0).contains(&l) {
+                continue;
+            }
+            // binary search for the largest chroma in gamut: the lower end always is
+            let (mut lo, mut hi) = (0.0, ideal.c);
+            for _ in 0..30 {
+                let mid = (lo + hi) / 2.0;
+                if to_srgb(l, mid).is_some() {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            if let Some(rgb) = to_srgb(l, lo) {
+                let dist = ideal.distance(&rgb);
+                match best {
+                    Some((best_dist, _)) if best_dist <= dist => {}
+                    _ => best = Some((dist, rgb)),
+                }
+            }
+        }
+        // middle gray always works, so this is only for safety
+        best.map(|(_, rgb)| rgb).unwrap_or(RGBColor {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        })
+    }
+
+    /// Returns a background that makes this color look as vivid as possible, for product shots and
+    /// other imagery where one color needs to stand out. This isn't the complement: a saturated
+    /// complement competes with the subject for attention. Instead, by simultaneous contrast, a
+    /// color looks more saturated against a muted background tinted slightly toward its complement,
+    /// and stands out more against a background far from it in lightness. This returns a
+    /// near-neutral color with a small amount of chroma at the complementary CIELCH hue, either
+    /// dark (for light subjects) or light (for dark subjects). Neutral colors have no complementary
+    /// hue, so they get a pure gray.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let yellow = RGBColor::from_hex_code("#FFD700").unwrap();
+    /// let background = yellow.popping_background();
+    /// // a dark, nearly neutral background
+    /// assert!(background.lightness() < 30.);
+    /// assert!(background.chroma() < 10.);
+    /// ```
+    fn popping_background(&self) -> RGBColor {
+        let lch: CIELCHColor = self.convert();
+        // a light subject gets a dark background and vice versa
+        let l = if lch.l >= 50.0 { 15.0 } else { 92.0 };
+        let h = (lch.h + 180.0) % 360.0;
+        // just enough tint to push the subject's hue, but never more than a quarter of its chroma
+        let c = (lch.c / 4.0).min(8.0).min(RGBColor::max_chroma(l, h));
+        RGBColor::from_lch_clamped(l, c, h)
+    }
+
     /// Returns a metric of the distance between the given color and another that attempts to
     /// accurately reflect human perception. This is done by using the CIEDE2000 difference formula,
     /// the current international and industry standard. The result, being a distance, will never be
@@ -833,6 +2232,64 @@ pub trait Color: Sized {
             + r_t * (delta_c / s_c) * (delta_h / s_h))
             .sqrt()
     }
+    /// Returns the Euclidean distance between two colors in the CAM16-UCS space, a modern uniform
+    /// color space built on the CAM16 color appearance model. This is a strong alternative to the
+    /// CIEDE2000 [`distance`](#method.distance): it performs about as well on the standard datasets
+    /// of perceived color differences, and is both simpler to reason about and a true metric (it
+    /// obeys the triangle inequality, which CIEDE2000 does not). Its values are on roughly the same
+    /// scale as CIEDE2000, but don't expect them to match exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color1 = RGBColor::from_hex_code("#123456").unwrap();
+    /// let color2 = RGBColor::from_hex_code("#123556").unwrap();
+    /// let color3 = RGBColor::from_hex_code("#333333").unwrap();
+    /// assert!(color1.distance_cam16ucs(&color2) < 1.);
+    /// assert!(color1.distance_cam16ucs(&color3) > 10.);
+    /// ```
+    fn distance_cam16ucs<T: Color>(&self, other: &T) -> f64 {
+        let cam1: CAM16UCSColor = self.convert();
+        let cam2: CAM16UCSColor = other.convert();
+        (cam1.j - cam2.j)
+            .hypot(cam1.a - cam2.a)
+            .hypot(cam1.b - cam2.b)
+    }
+    /// Returns the smallest CIEDE2000 [`distance`](#method.distance) between this color and the given
+    /// colormap, approximated by sampling `resolution` evenly-spaced points along the colormap from 0
+    /// to 1 inclusive. This is useful for checking whether a color plausibly came from a given
+    /// colormap: colors taken from the colormap will be very close to 0, given enough resolution. If
+    /// `resolution` is 1, only the midpoint is sampled.
+    ///
+    /// # Panics
+    /// Panics if `resolution` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let viridis = ListedColorMap::viridis();
+    /// let teal: RGBColor = viridis.transform_single(0.4);
+    /// let red = RGBColor{r: 1., g: 0., b: 0.};
+    /// assert!(teal.distance_to_colormap(&viridis, 256) <= 0.5);
+    /// assert!(red.distance_to_colormap(&viridis, 256) >= 20.);
+    /// ```
+    fn distance_to_colormap(&self, cmap: &impl ColorMap<RGBColor>, resolution: usize) -> f64 {
+        assert!(resolution > 0, "Need at least one sample of the colormap");
+        let points: Vec<f64> = if resolution == 1 {
+            vec![0.5]
+        } else {
+            (0..resolution)
+                .map(|i| i as f64 / (resolution - 1) as f64)
+                .collect()
+        };
+        cmap.transform(points)
+            .iter()
+            .map(|c| self.distance(c))
+            .fold(f64::INFINITY, f64::min)
+    }
     /// Using the metric that two colors with a CIEDE2000 distance of less than 1 are
     /// indistinguishable, determines whether two colors are visually distinguishable from each
     /// other. For more, check out [this guide](../color_distance.html).
@@ -853,6 +2310,278 @@ pub trait Color: Sized {
     fn visually_indistinguishable<T: Color>(&self, other: &T) -> bool {
         self.distance(other) <= 1.0
     }
+
+    /// Determines whether adding this color to the given palette would keep every color in it
+    /// distinguishable: that is, whether this color is at least `jnd` away from every color already
+    /// in the palette, using the CIEDE2000 [`distance`](#method.distance). `jnd` stands for "just
+    /// noticeable difference": 1.0 is the bare minimum for two colors to look different at all, but
+    /// for colors that need to be told apart at a glance (say, in a chart legend) something more
+    /// like 10 or 20 is appropriate. An empty palette always returns `true`.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let palette: Vec<RGBColor> = ["#1f77b4", "#ff7f0e", "#2ca02c"]
+    ///     .iter()
+    ///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+    ///     .collect();
+    /// let red = RGBColor::from_hex_code("#d62728").unwrap();
+    /// let another_blue = RGBColor::from_hex_code("#2077b0").unwrap();
+    /// assert!(red.would_be_distinct(&palette, 10.));
+    /// assert!(!another_blue.would_be_distinct(&palette, 10.));
+    /// ```
+    fn would_be_distinct(&self, palette: &[RGBColor], jnd: f64) -> bool {
+        palette.iter().all(|color| self.distance(color) >= jnd)
+    }
+
+    /// Gets the CIE 1931 xy chromaticity coordinates of a color: its position on the familiar
+    /// horseshoe-shaped chromaticity diagram, which ignores how bright the color is. These are
+    /// computed as `x = X / (X + Y + Z)` and `y = Y / (X + Y + Z)`, treating the color as a light in
+    /// the D65 viewing environment sRGB assumes, so sRGB white is at D65's white point. An
+    /// [`XYZColor`](struct.XYZColor.html) is used exactly as given, in its own illuminant. Black has
+    /// no chromaticity, and gives NaN.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let (x, y) = white.chromaticity_xy();
+    /// assert!((x - 0.3127).abs() <= 1e-4);
+    /// assert!((y - 0.3290).abs() <= 1e-4);
+    /// // gray is the same chromaticity
+    /// let gray = RGBColor::from_hex_code("#777777").unwrap();
+    /// let (gray_x, gray_y) = gray.chromaticity_xy();
+    /// assert!((x - gray_x).abs() <= 1e-10 && (y - gray_y).abs() <= 1e-10);
+    /// ```
+    fn chromaticity_xy(&self) -> (f64, f64) {
+        let xyz = self.to_xyz(Illuminant::D65);
+        let sum = xyz.x + xyz.y + xyz.z;
+        (xyz.x / sum, xyz.y / sum)
+    }
+
+    /// Gets the CIE 1976 u'v' chromaticity coordinates of a color. This is a projective transform of
+    /// the [xy chromaticity diagram](#method.chromaticity_xy) that makes distances between
+    /// chromaticities much closer to how different they look, so it's the better choice for
+    /// measuring how far apart two chromaticities are. Like `chromaticity_xy`, the color is treated as
+    /// a light in a D65 viewing environment, an `XYZColor` is used as given, and black gives NaN.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let (u, v) = white.chromaticity_uv();
+    /// assert!((u - 0.1978).abs() <= 1e-4);
+    /// assert!((v - 0.4683).abs() <= 1e-4);
+    /// ```
+    fn chromaticity_uv(&self) -> (f64, f64) {
+        let xyz = self.to_xyz(Illuminant::D65);
+        let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+        (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
+    }
+
+    /// Gets the CIE 1960 UCS uv chromaticity coordinates of a color. This older diagram is the same
+    /// as the [CIE 1976 u'v' diagram](#method.chromaticity_uv) with the v axis shrunk: `u = u'` and
+    /// `v = 2/3 v'`. It's obsolete for most purposes, but it's still where correlated color
+    /// temperature and [`duv`](#method.duv) are defined. See also
+    /// [`UCS1960Chromaticity`](../colors/ucs1960/struct.UCS1960Chromaticity.html).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let (u, v) = white.ucs_uv_1960();
+    /// let (u_prime, v_prime) = white.chromaticity_uv();
+    /// assert_eq!(u, u_prime);
+    /// assert!((v - v_prime * 2. / 3.).abs() <= 1e-12);
+    /// ```
+    fn ucs_uv_1960(&self) -> (f64, f64) {
+        let (u_prime, v_prime) = self.chromaticity_uv();
+        (u_prime, v_prime * 2.0 / 3.0)
+    }
+
+    /// Estimates the *correlated color temperature* (CCT) of a color in kelvin: the temperature of
+    /// the blackbody radiator whose color is closest to this one. This is the number behind "warm
+    /// white" and "cool white" in lighting and white-balance controls. The color is treated as a
+    /// light in the D65 viewing environment sRGB assumes, and the estimate uses McCamy's cubic
+    /// approximation on the CIE 1931 xy chromaticity coordinates, which is accurate to within a few
+    /// kelvin between roughly 2000 K and 12500 K.
+    ///
+    /// Keep in mind that this is only meaningful for colors that are close to white: CCT only
+    /// describes how far along the blackbody curve a color lies, not how far off of it it is, so a
+    /// vivid green and a vivid magenta can have the same CCT. Use this for near-white colors with a
+    /// small distance from the blackbody curve.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// // CIE D50 is named for its CCT of roughly 5000 K
+    /// let d50 = XYZColor::white_point(Illuminant::D50);
+    /// assert!((d50.cct() - 5000.).abs() <= 10.);
+    /// // incandescent bulbs are warmer: lower temperature
+    /// let incandescent = RGBColor::from_hex_code("#ffb46b").unwrap();
+    /// assert!(incandescent.cct() < 3000.);
+    /// ```
+    fn cct(&self) -> f64 {
+        let (x, y) = self.chromaticity_xy();
+        // McCamy's epicenter is (0.3320, 0.1858)
+        let n = (x - 0.3320) / (0.1858 - y);
+        449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+    }
+
+    /// Computes *Duv*, the signed distance of a color from the blackbody (Planckian) locus in the CIE
+    /// 1960 UCS diagram. Together with [`cct`](#method.cct), this fully describes the color of a
+    /// white light: CCT says how warm or cool it is, and Duv says how greenish (positive, above the
+    /// locus) or pinkish (negative, below the locus) it is. Like `cct`, the color is treated as a
+    /// light in a D65 viewing environment. This uses the polynomial approximation of the locus given
+    /// in ANSI C78.377, which is accurate for CCTs between about 2000 K and 20000 K. Values larger
+    /// than about 0.05 in magnitude mean the color isn't really a white anymore.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// // the CIE daylight illuminants are a little green compared to a blackbody
+    /// let d65 = XYZColor::white_point(Illuminant::D65);
+    /// assert!((d65.duv() - 0.0032).abs() <= 1e-4);
+    /// // a pinkish white is below the locus
+    /// let pink = RGBColor::from_hex_code("#fff0f8").unwrap();
+    /// assert!(pink.duv() < 0.);
+    /// ```
+    fn duv(&self) -> f64 {
+        let (u, v) = self.ucs_uv_1960();
+        let (ref_u, ref_v) = DUV_REFERENCE_UV;
+        // distance and angle from a reference point near the center of the locus's curvature
+        let l_fp = (u - ref_u).hypot(v - ref_v);
+        let a = ((u - ref_u) / l_fp).acos();
+        // the polynomial gives the distance of the locus itself from that point at that angle
+        let k = [
+            -0.471106,
+            1.925865,
+            -2.4243787,
+            1.5317403,
+            -0.5179722,
+            0.0893944,
+            -0.00616793,
+        ];
+        let l_bb = k.iter().rev().fold(0.0, |acc, coef| acc * a + coef);
+        l_fp - l_bb
+    }
+
+    /// Gives a heuristic measure of how *warm* or *cool* a color looks, in the sense artists and
+    /// designers use: reds, oranges, and yellows are warm, and greens, cyans, and blues are cool. The
+    /// result is between -1 and 1, positive for warm colors and negative for cool ones.
+    ///
+    /// This is based on the CIELAB hue angle. Warm hues run from 340 degrees, a crimson between red
+    /// and magenta, counterclockwise to 115 degrees, between yellow and green, and every other hue is
+    /// cool. The measure is strongest in the middle of each range, at 47.5 degrees (a red-orange) for
+    /// warm and 227.5 degrees (a cyan-blue) for cool, and falls off as a cosine to 0 at the
+    /// boundaries. That's scaled by how colorful the color is, reaching full strength at a CIELAB
+    /// chroma of 20, so that grays are neither warm nor cool. Note that this has nothing to do with
+    /// [`cct`](#method.cct), the physical color temperature of a light, which famously runs the
+    /// other way: bluer lights are hotter.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let orange = RGBColor::from_hex_code("#FF8000").unwrap();
+    /// let cyan = RGBColor::from_hex_code("#00C0FF").unwrap();
+    /// assert!(orange.temperature_bias() > 0.9);
+    /// assert!(cyan.temperature_bias() < -0.9);
+    /// ```
+    fn temperature_bias(&self) -> f64 {
+        // the warm range, as a start and a width in degrees: the rest is cool
+        let (warm_start, warm_width) = (340.0, 135.0);
+        let lch: CIELCHColor = self.convert();
+        let strength = (lch.c / 20.0).min(1.0);
+        let offset = (lch.h - warm_start).rem_euclid(360.0);
+        let bias = if offset <= warm_width {
+            // scale the range to half a period of the cosine, peaking in the middle
+            (PI * (offset / warm_width - 0.5)).cos()
+        } else {
+            let cool_width = 360.0 - warm_width;
+            -(PI * ((offset - warm_width) / cool_width - 0.5)).cos()
+        };
+        strength * bias
+    }
+
+    /// Returns `true` if the color looks warm, and `false` if it looks cool or neutral, as given by
+    /// [`temperature_bias`](#method.temperature_bias): reds, oranges, and yellows are warm, and
+    /// anything else isn't.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let tomato = RGBColor::from_hex_code("#FF6347").unwrap();
+    /// let teal = RGBColor::from_hex_code("#008080").unwrap();
+    /// assert!(tomato.is_warm());
+    /// assert!(!teal.is_warm());
+    /// ```
+    fn is_warm(&self) -> bool {
+        self.temperature_bias() > 0.0
+    }
+
+    /// Gets an approximate [Munsell](https://en.wikipedia.org/wiki/Munsell_color_system) notation
+    /// for this color, like `"5R 4/14"`: hue, then value (lightness from 0 to 10), then chroma. Gray
+    /// colors get the neutral notation, like `"N 5/"`. Hues are rounded to the nearest 2.5 step and
+    /// chromas to the nearest even number, as on the standard Munsell charts.
+    ///
+    /// This is an approximation computed from CIELCH, **not** a lookup in the official Munsell
+    /// renotation tables: value is taken as L\* / 10, chroma as C\* / 5, and hue is interpolated
+    /// between the approximate CIELCH hue angles of the ten principal Munsell hues. It's generally
+    /// within a step or so of the real thing, which is good enough for describing a color but not
+    /// for standards work that requires the true renotation.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let brick = RGBColor::from_hex_code("#8B3A2B").unwrap();
+    /// assert_eq!(brick.munsell_notation(), "10R 4/8");
+    /// let gray = RGBColor::from_hex_code("#777777").unwrap();
+    /// assert_eq!(gray.munsell_notation(), "N 5/");
+    /// ```
+    fn munsell_notation(&self) -> String {
+        // the CIELCH hue of each principal Munsell hue (at step 5), going around the circle
+        let families: [(&str, f64); 10] = [
+            ("R", 24.0),
+            ("YR", 58.0),
+            ("Y", 95.0),
+            ("GY", 120.0),
+            ("G", 160.0),
+            ("BG", 195.0),
+            ("B", 235.0),
+            ("PB", 285.0),
+            ("P", 325.0),
+            ("RP", 350.0),
+        ];
+        let lch: CIELCHColor = self.convert();
+        let value = (lch.l / 10.0).round().clamp(0.0, 10.0);
+        let chroma = 2.0 * (lch.c / 10.0).round();
+        if chroma == 0.0 {
+            return format!("N {}/", value);
+        }
+
+        // find the two principal hues this is between and interpolate
+        let n = families.len();
+        let i = (0..n)
+            .find(|&i| {
+                let start = families[i].1;
+                let width = (families[(i + 1) % n].1 - start).rem_euclid(360.0);
+                (lch.h - start).rem_euclid(360.0) < width
+            })
+            .unwrap_or(n - 1);
+        let start = families[i].1;
+        let width = (families[(i + 1) % n].1 - start).rem_euclid(360.0);
+        let frac = (lch.h - start).rem_euclid(360.0) / width;
+        // the Munsell hue circle runs from 0 to 100, with 10 steps per family
+        let munsell_hue = (10.0 * i as f64 + 5.0 + 10.0 * frac) % 100.0;
+        let rounded = ((munsell_hue / 2.5).round() * 2.5) % 100.0;
+        // each family runs from just above 0 to 10, so 10R is followed by 2.5YR
+        let family = ((rounded / 10.0).ceil() as usize + n - 1) % n;
+        let step = rounded - 10.0 * family as f64;
+        let step = if step <= 0.0 { step + 100.0 } else { step };
+        format!("{}{} {}/{}", step, families[family].0, value, chroma)
+    }
 }
 
 impl Color for XYZColor {
@@ -965,38 +2694,660 @@ impl RGBColor {
     pub fn int_rgb_tup(&self) -> (u8, u8, u8) {
         (self.int_r(), self.int_g(), self.int_b())
     }
-    /// Given a string, returns that string wrapped in codes that will color the foreground. Used
-    /// for the trait implementation of write_colored_str, which should be used instead. Requires
-    /// the `terminal` feature.
-    #[cfg(feature = "terminal")]
-    fn base_write_colored_str(&self, text: &str) -> String {
-        format!(
-            "{code}{text}{reset}",
-            code = Fg(Rgb(self.int_r(), self.int_g(), self.int_b())),
-            text = text,
-            reset = Fg(Reset)
-        )
-    }
-    /// Used for the Color `write_color()` method. Requires the `terminal` feature.
-    #[cfg(feature = "terminal")]
-    fn base_write_color(&self) -> String {
-        format!(
-            "{bg}{fg}{text}{reset_fg}{reset_bg}",
-            bg = Bg(Rgb(self.int_r(), self.int_g(), self.int_b())),
-            fg = Fg(Rgb(self.int_r(), self.int_g(), self.int_b())),
-            text = "■",
-            reset_fg = Fg(Reset),
-            reset_bg = Bg(Reset),
-        )
+    /// Gets a key for this color that can be hashed and compared exactly: the 8-bit components, as
+    /// in [`int_rgb_tup`](#method.int_rgb_tup). Two colors have the same key exactly when they have
+    /// the same hex code. To use the colors themselves in a `HashSet` or as `HashMap` keys, see
+    /// [`QuantizedRGB`](struct.QuantizedRGB.html).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use std::collections::HashMap;
+    /// let pixels = ["#FF0000", "#00FF00", "#FF0000"];
+    /// let mut counts = HashMap::new();
+    /// for pixel in pixels.iter() {
+    ///     let color = RGBColor::from_hex_code(pixel).unwrap();
+    ///     *counts.entry(color.to_key()).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(counts[&(255, 0, 0)], 2);
+    /// ```
+    pub fn to_key(&self) -> (u8, u8, u8) {
+        self.int_rgb_tup()
     }
-}
-
-impl PartialEq for RGBColor {
-    fn eq(&self, other: &RGBColor) -> bool {
-        self.r == other.r && self.g == other.g && self.b == other.b
+    /// Packs this color into a `u32` as `0x00RRGGBB`, the layout many framebuffers and windowing
+    /// libraries use: blue in the lowest 8 bits, then green, then red, with the top 8 bits 0. This
+    /// describes the numeric value, not the byte order in memory: on a little-endian machine, the
+    /// bytes are stored blue first. The components are as in [`int_rgb_tup`](#method.int_rgb_tup).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::from_hex_code("#1E90FF").unwrap();
+    /// assert_eq!(color.to_u32_rgb(), 0x1E90FF);
+    /// ```
+    pub fn to_u32_rgb(&self) -> u32 {
+        let (r, g, b) = self.int_rgb_tup();
+        (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b)
     }
-}
-
+    /// Packs this color and the given alpha byte into a `u32` as `0xAARRGGBB`: the same as
+    /// [`to_u32_rgb`](#method.to_u32_rgb), with the alpha value in the top 8 bits. An alpha of 255
+    /// is fully opaque.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::from_hex_code("#1E90FF").unwrap();
+    /// assert_eq!(color.to_u32_argb(0x80), 0x801E90FF);
+    /// ```
+    pub fn to_u32_argb(&self, alpha: u8) -> u32 {
+        (u32::from(alpha) << 24) | self.to_u32_rgb()
+    }
+    /// Unpacks a color from a `u32` laid out as `0x00RRGGBB`, as produced by
+    /// [`to_u32_rgb`](#method.to_u32_rgb). The top 8 bits are ignored, so this also reads the color
+    /// out of an `0xAARRGGBB` value.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::from_u32_rgb(0xFF1E90FF);
+    /// assert_eq!(color.to_string(), "#1E90FF");
+    /// ```
+    pub fn from_u32_rgb(rgb: u32) -> RGBColor {
+        let byte = |shift: u32| ((rgb >> shift) & 0xFF) as u8;
+        RGBColor::from((byte(16), byte(8), byte(0)))
+    }
+    /// Decodes an 8-bit sRGB color, as stored in most image files, straight to linear light, giving
+    /// the red, green, and blue components as `[r, g, b]`. This gives the same result as
+    /// [`srgb_decode`](fn.srgb_decode.html) on each component divided by 255, but because there are
+    /// only 256 possible levels it uses a precomputed table instead, which makes decoding whole
+    /// images much faster.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::srgb_decode;
+    /// let linear = RGBColor::from_u8_linear(255, 128, 0);
+    /// assert_eq!(linear[0], 1.0);
+    /// assert_eq!(linear[1], srgb_decode(128. / 255.));
+    /// assert_eq!(linear[2], 0.0);
+    /// ```
+    pub fn from_u8_linear(r: u8, g: u8, b: u8) -> [f64; 3] {
+        [
+            SRGB_U8_TO_LINEAR[usize::from(r)],
+            SRGB_U8_TO_LINEAR[usize::from(g)],
+            SRGB_U8_TO_LINEAR[usize::from(b)],
+        ]
+    }
+    /// Finds the `n` most common colors in the given pixels, returning each with the number of pixels
+    /// that have it, most common first. To keep nearly-identical colors from splitting the count,
+    /// pixels are grouped by quantizing each component to 4 bits (16 levels), and the color returned
+    /// for each group is the average of the pixels in it. Ties are broken by the order in which the
+    /// groups first appear in `pixels`. If there are fewer than `n` groups, all of them are returned.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// let blue = RGBColor::from_hex_code("#0000FF").unwrap();
+    /// let pixels = vec![red, blue, blue, red, blue];
+    /// let dominant = RGBColor::dominant_colors(&pixels, 1);
+    /// assert_eq!(dominant, vec![(blue, 3)]);
+    /// ```
+    pub fn dominant_colors(pixels: &[RGBColor], n: usize) -> Vec<(RGBColor, usize)> {
+        // maps each quantized color to its index in groups
+        let mut indices: HashMap<(u8, u8, u8), usize> = HashMap::new();
+        // the sum of the components in each group, along with the count
+        let mut groups: Vec<([f64; 3], usize)> = Vec::new();
+        for pixel in pixels {
+            let (r, g, b) = pixel.to_key();
+            let key = (r >> 4, g >> 4, b >> 4);
+            let i = *indices.entry(key).or_insert_with(|| {
+                groups.push(([0.0; 3], 0));
+                groups.len() - 1
+            });
+            groups[i].0[0] += pixel.r;
+            groups[i].0[1] += pixel.g;
+            groups[i].0[2] += pixel.b;
+            groups[i].1 += 1;
+        }
+        // stable sort, so ties stay in order of appearance
+        groups.sort_by_key(|group| Reverse(group.1));
+        groups
+            .into_iter()
+            .take(n)
+            .map(|(sums, count)| {
+                let count_f = count as f64;
+                let color = RGBColor {
+                    r: sums[0] / count_f,
+                    g: sums[1] / count_f,
+                    b: sums[2] / count_f,
+                };
+                (color, count)
+            })
+            .collect()
+    }
+    /// Reduces the given pixels to a palette of at most `palette_size` colors using the classic
+    /// median-cut algorithm, the standard way of building an indexed-color palette for an image.
+    /// Starting with a single box in RGB space holding every pixel, the box with the largest extent
+    /// along any one axis is repeatedly split in two at the median along that axis, until there are
+    /// `palette_size` boxes or no box can be split further. The palette is the average color of each
+    /// box. Because boxes are split so each half has about the same number of pixels, large clusters of
+    /// similar colors get more of the palette than small ones. Fewer colors are returned if there
+    /// aren't enough distinct pixels, and none at all if `pixels` is empty.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let pixels: Vec<RGBColor> = ["#101010", "#202020", "#F0F0F0", "#E0E0E0"]
+    ///     .iter()
+    ///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+    ///     .collect();
+    /// let palette = RGBColor::median_cut(&pixels, 2);
+    /// let mut hexes: Vec<String> = palette.iter().map(|c| c.to_string()).collect();
+    /// hexes.sort();
+    /// assert_eq!(hexes, vec!["#181818", "#E8E8E8"]);
+    /// ```
+    pub fn median_cut(pixels: &[RGBColor], palette_size: usize) -> Vec<RGBColor> {
+        let channel = |color: &RGBColor, axis: usize| match axis {
+            0 => color.r,
+            1 => color.g,
+            _ => color.b,
+        };
+        // the axis with the largest extent in a box, along with that extent
+        let longest_axis = |colors: &[RGBColor]| {
+            (0..3)
+                .map(|axis| {
+                    let values = colors.iter().map(|c| channel(c, axis));
+                    let min = values.clone().fold(f64::INFINITY, f64::min);
+                    let max = values.fold(f64::NEG_INFINITY, f64::max);
+                    (axis, max - min)
+                })
+                .max_by(|a, b| a.1.partial_cmp(&b.1).expect("Colors can't be NaN"))
+                .expect("There are three axes")
+        };
+
+        let mut boxes: Vec<Vec<RGBColor>> = Vec::new();
+        if !pixels.is_empty() && palette_size > 0 {
+            boxes.push(pixels.to_vec());
+        }
+        while boxes.len() < palette_size {
+            // find the box with the largest extent: if that's 0, every box is a single color
+            let (i, (axis, extent)) = match boxes
+                .iter()
+                .map(|colors| longest_axis(colors))
+                .enumerate()
+                .max_by(|a, b| (a.1).1.partial_cmp(&(b.1).1).expect("Colors can't be NaN"))
+            {
+                Some(best) => best,
+                None => break,
+            };
+            if extent <= 0.0 {
+                break;
+            }
+            let mut colors = boxes.swap_remove(i);
+            colors.sort_by(|a, b| {
+                channel(a, axis)
+                    .partial_cmp(&channel(b, axis))
+                    .expect("Colors can't be NaN")
+            });
+            // split at the median, but never between two pixels with the same value on the axis,
+            // so that identical colors always stay together
+            let value = |j: usize| channel(&colors[j], axis);
+            let mut mid = colors.len() / 2;
+            while mid < colors.len() && value(mid - 1) == value(mid) {
+                mid += 1;
+            }
+            if mid == colors.len() {
+                mid = colors.len() / 2;
+                while value(mid - 1) == value(mid) {
+                    mid -= 1;
+                }
+            }
+            let upper = colors.split_off(mid);
+            boxes.push(colors);
+            boxes.push(upper);
+        }
+        boxes
+            .iter()
+            .map(|colors| {
+                let n = colors.len() as f64;
+                RGBColor {
+                    r: colors.iter().map(|c| c.r).sum::<f64>() / n,
+                    g: colors.iter().map(|c| c.g).sum::<f64>() / n,
+                    b: colors.iter().map(|c| c.b).sum::<f64>() / n,
+                }
+            })
+            .collect()
+    }
+    /// Returns the index of the palette color closest to this one, using the CIEDE2000
+    /// [`distance`](trait.Color.html#method.distance). This is the "snap to palette" step in making an
+    /// indexed-color image, as with a palette from [`median_cut`](#method.median_cut). If several
+    /// palette colors are equally close, the first is chosen. This is a simple linear scan over the
+    /// palette, so it's best suited to palettes of at most a few hundred colors.
+    ///
+    /// # Panics
+    /// Panics if `palette` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let palette: Vec<RGBColor> = ["#000000", "#FFFFFF", "#FF0000"]
+    ///     .iter()
+    ///     .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+    ///     .collect();
+    /// let maroon = RGBColor::from_hex_code("#800000").unwrap();
+    /// assert_eq!(maroon.nearest_in_palette(&palette), 2);
+    /// ```
+    pub fn nearest_in_palette(&self, palette: &[RGBColor]) -> usize {
+        assert!(!palette.is_empty(), "Palette is empty");
+        palette
+            .iter()
+            .map(|color| self.distance(color))
+            .enumerate()
+            .fold((0, f64::INFINITY), |best, (i, dist)| {
+                if dist < best.1 {
+                    (i, dist)
+                } else {
+                    best
+                }
+            })
+            .0
+    }
+    /// Generates `n` colors that are as easy as possible to tell apart, both from each other and from
+    /// the given background: the usual need when coloring the series of a categorical chart. Candidate
+    /// colors are sampled across the sRGB gamut on a grid of CIELCH lightness, chroma, and hue, and any
+    /// within a CIEDE2000 [`distance`](trait.Color.html#method.distance) of 20 of the background are
+    /// dropped. Then each color is picked greedily as the candidate furthest from the background and
+    /// all of the colors picked before it, so the first colors are the most distinct and asking for
+    /// more colors only appends to the list. Past a dozen or so colors, no palette can keep everything
+    /// easy to tell apart.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let palette = RGBColor::distinct_palette(5, white);
+    /// assert_eq!(palette.len(), 5);
+    /// for (i, color) in palette.iter().enumerate() {
+    ///     assert!(color.distance(&white) > 20.);
+    ///     for other in palette[i + 1..].iter() {
+    ///         assert!(color.distance(other) > 20.);
+    ///     }
+    /// }
+    /// ```
+    pub fn distinct_palette(n: usize, background: RGBColor) -> Vec<RGBColor> {
+        let mut candidates: Vec<RGBColor> = Vec::new();
+        for h in (0..24).map(|x| f64::from(x) * 15.0) {
+            for l in (2..=9).map(|x| f64::from(x) * 10.0) {
+                for c in (0..=13).map(|x| f64::from(x) * 10.0) {
+                    let rgb: RGBColor = CIELCHColor { l, c, h }.convert();
+                    if [rgb.r, rgb.g, rgb.b]
+                        .iter()
+                        .all(|&x| (0.0..=1.0).contains(&x))
+                    {
+                        candidates.push(rgb);
+                    }
+                }
+            }
+        }
+        let mut min_dists: Vec<f64> = candidates.iter().map(|c| c.distance(&background)).collect();
+        // keep enough contrast with the background, unless that rules out everything
+        if min_dists.iter().any(|&dist| dist >= 20.0) {
+            let (kept, kept_dists) = candidates
+                .into_iter()
+                .zip(min_dists)
+                .filter(|&(_, dist)| dist >= 20.0)
+                .unzip();
+            candidates = kept;
+            min_dists = kept_dists;
+        }
+        let mut chosen: Vec<RGBColor> = Vec::with_capacity(n);
+        while chosen.len() < n.min(candidates.len()) {
+            let (best, _) = min_dists
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).expect("Distances can't be NaN"))
+                .expect("There are candidates");
+            let next = candidates[best];
+            chosen.push(next);
+            for (dist, candidate) in min_dists.iter_mut().zip(candidates.iter()) {
+                *dist = dist.min(candidate.distance(&next));
+            }
+        }
+        chosen
+    }
+    /// Finds the entry of the xterm 256-color palette closest to this color by CIEDE2000 distance,
+    /// returning its index, which is what terminals expect in the `38;5;N` and `48;5;N` escape codes.
+    /// Only indices 16 to 255 are considered: these are the same everywhere, while the first 16
+    /// colors vary with the terminal and its theme.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// // pure red is in the palette's color cube
+    /// assert_eq!(RGBColor::from_hex_code("#FF0000").unwrap().to_xterm_256(), 196);
+    /// // and grays land on the gray ramp
+    /// assert_eq!(RGBColor::from_hex_code("#808080").unwrap().to_xterm_256(), 244);
+    /// ```
+    pub fn to_xterm_256(&self) -> u8 {
+        let (best, _) = XTERM_256_PALETTE
+            .iter()
+            .map(|entry| self.distance(entry))
+            .enumerate()
+            .min_by(|a, b| a.1.partial_cmp(&b.1).expect("Distances can't be NaN"))
+            .expect("The palette isn't empty");
+        (best + 16) as u8
+    }
+    /// Gets the WCAG contrast ratio between this color and another, from 1 for two identical colors
+    /// to 21 for black and white. This is the measure that web accessibility guidelines use: they ask
+    /// for at least 4.5 between text and its background, and at least 3 for large text and for the
+    /// edges of shapes. It's computed from the relative luminance (XYZ Y under D65) of each color,
+    /// as `(lighter + 0.05) / (darker + 0.05)`, so the order of the colors doesn't matter.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// let gray = RGBColor::from_hex_code("#777777").unwrap();
+    /// assert!((black.contrast_ratio(&white) - 21.).abs() <= 1e-3);
+    /// // #777777 just misses the 4.5 needed for body text on white
+    /// assert!(gray.contrast_ratio(&white) >= 4.47 && gray.contrast_ratio(&white) < 4.5);
+    /// ```
+    pub fn contrast_ratio(&self, other: &RGBColor) -> f64 {
+        let y1 = self.to_xyz(Illuminant::D65).y;
+        let y2 = other.to_xyz(Illuminant::D65).y;
+        (y1.max(y2) + 0.05) / (y1.min(y2) + 0.05)
+    }
+    /// Picks a color for outlining a shape filled with this color, so that it stands out from the
+    /// given background. If the fill already has a contrast ratio of at least 3 with the background,
+    /// the WCAG minimum for the edges of shapes, no outline is needed and the fill itself is returned,
+    /// so an outline drawn with it blends in. Otherwise, this returns black or white, whichever has
+    /// more contrast with the fill. Compare [`outline_color`](trait.Color.html#method.outline_color),
+    /// which gives a subtle outline that ignores the background.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let yellow = RGBColor::from_hex_code("#FFE066").unwrap();
+    /// let cream = RGBColor::from_hex_code("#FFF8E0").unwrap();
+    /// let navy = RGBColor::from_hex_code("#001F3F").unwrap();
+    /// // yellow on cream needs a dark outline to show up
+    /// assert_eq!(yellow.outline_against(&cream).to_string(), "#000000");
+    /// // but is already plenty visible on navy
+    /// assert_eq!(yellow.outline_against(&navy), yellow);
+    /// ```
+    pub fn outline_against(&self, background: &RGBColor) -> RGBColor {
+        if self.contrast_ratio(background) >= 3.0 {
+            return *self;
+        }
+        let black = RGBColor {
+            r: 0.,
+            g: 0.,
+            b: 0.,
+        };
+        let white = RGBColor {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+        };
+        if self.contrast_ratio(&black) >= self.contrast_ratio(&white) {
+            black
+        } else {
+            white
+        }
+    }
+    /// Gives a rough preview of how this color might look printed with ordinary CMYK process inks,
+    /// without needing an ICC profile. Screens can show much more vivid cyans, greens, blues, and
+    /// oranges than ink on paper can, so those dull down, while colors a printer handles well come
+    /// back nearly unchanged.
+    ///
+    /// This is only an approximation, and can differ a lot from what a real press and paper
+    /// produce: use a proper color-managed workflow with the printer's profile for anything that
+    /// matters. The color is clamped to the sRGB gamut and split into cyan, magenta, yellow, and black
+    /// with the naive formulas (`K = 1 - max(R, G, B)`, `C = (1 - R - K) / (1 - K)`, and so on).
+    /// Then, each ink is treated as a filter that lets through a fraction of each sRGB channel,
+    /// matching the typical process colors `#00AEEF`, `#EC008C`, `#FFF200`, and `#231F20`, and the
+    /// filters of the inks used are multiplied together over white paper. With perfectly pure inks,
+    /// this would give back the original color exactly.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let green = RGBColor::from_hex_code("#00FF00").unwrap();
+    /// let printed = green.soft_proof_cmyk();
+    /// assert!(printed.chroma() < green.chroma() - 20.);
+    /// // white is just the paper
+    /// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+    /// assert_eq!(white.soft_proof_cmyk(), white);
+    /// ```
+    pub fn soft_proof_cmyk(&self) -> RGBColor {
+        // how much of each sRGB channel, out of 255, each ink lets through
+        const INKS: [[u8; 3]; 4] = [[0, 174, 239], [236, 0, 140], [255, 242, 0], [35, 31, 32]];
+        let rgb = [
+            self.r.clamp(0.0, 1.0),
+            self.g.clamp(0.0, 1.0),
+            self.b.clamp(0.0, 1.0),
+        ];
+        let k = 1.0 - rgb.iter().cloned().fold(0.0, f64::max);
+        let coverage = if k >= 1.0 {
+            [0.0, 0.0, 0.0, 1.0]
+        } else {
+            [
+                (1.0 - rgb[0] - k) / (1.0 - k),
+                (1.0 - rgb[1] - k) / (1.0 - k),
+                (1.0 - rgb[2] - k) / (1.0 - k),
+                k,
+            ]
+        };
+        let mut printed = [1.0; 3];
+        for (ink, amount) in INKS.iter().zip(coverage.iter()) {
+            for (channel, filter) in printed.iter_mut().zip(ink.iter()) {
+                *channel *= 1.0 - amount * (1.0 - f64::from(*filter) / 255.0);
+            }
+        }
+        RGBColor::from(printed)
+    }
+    /// Given a string, returns that string wrapped in codes that will color the foreground. Used
+    /// for the trait implementation of write_colored_str, which should be used instead.
+    fn base_write_colored_str(&self, text: &str) -> String {
+        format!(
+            "\x1b[38;2;{r};{g};{b}m{text}\x1b[39m",
+            r = self.int_r(),
+            g = self.int_g(),
+            b = self.int_b(),
+            text = text,
+        )
+    }
+    /// Used for the Color `write_color()` method.
+    fn base_write_color(&self) -> String {
+        format!(
+            "\x1b[48;2;{r};{g};{b}m\x1b[38;2;{r};{g};{b}m■\x1b[39m\x1b[49m",
+            r = self.int_r(),
+            g = self.int_g(),
+            b = self.int_b(),
+        )
+    }
+}
+
+impl PartialEq for RGBColor {
+    fn eq(&self, other: &RGBColor) -> bool {
+        self.r == other.r && self.g == other.g && self.b == other.b
+    }
+}
+
+/// A thin wrapper around an [`RGBColor`](struct.RGBColor.html) that compares and hashes by its 8-bit
+/// representation, as given by [`to_key`](struct.RGBColor.html#method.to_key). `RGBColor` itself
+/// holds floating-point values and so can't implement `Eq` or `Hash`: use this to deduplicate
+/// palettes, count the unique colors in an image, or otherwise use colors as keys. The wrapped color
+/// keeps its full precision.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::QuantizedRGB;
+/// # use std::collections::HashSet;
+/// let palette = vec![
+///     RGBColor{r: 0.5, g: 0.2, b: 0.1},
+///     RGBColor{r: 0.5001, g: 0.2, b: 0.1},
+///     RGBColor{r: 0.1, g: 0.2, b: 0.5},
+/// ];
+/// let unique: HashSet<QuantizedRGB> = palette.into_iter().map(QuantizedRGB).collect();
+/// assert_eq!(unique.len(), 2);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct QuantizedRGB(pub RGBColor);
+
+impl PartialEq for QuantizedRGB {
+    fn eq(&self, other: &QuantizedRGB) -> bool {
+        self.0.to_key() == other.0.to_key()
+    }
+}
+
+impl Eq for QuantizedRGB {}
+
+impl Hash for QuantizedRGB {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_key().hash(state);
+    }
+}
+
+impl From<RGBColor> for QuantizedRGB {
+    fn from(color: RGBColor) -> QuantizedRGB {
+        QuantizedRGB(color)
+    }
+}
+
+impl From<QuantizedRGB> for RGBColor {
+    fn from(quantized: QuantizedRGB) -> RGBColor {
+        quantized.0
+    }
+}
+
+/// A compact sRGB color that stores its components as `f32`, taking half the memory of an
+/// [`RGBColor`](struct.RGBColor.html). This is meant for storage, like large image buffers or data
+/// uploaded to a GPU, where single precision is plenty: convert to `RGBColor` with `From`/`Into` to
+/// do any color math, and back again to store the result. The conversion to `f32` rounds each
+/// component to about 7 significant digits, far finer than any display can show.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::RGBColorF32;
+/// let pixels: Vec<RGBColorF32> = vec![RGBColor::from_hex_code("#1E90FF").unwrap().into(); 4];
+/// assert_eq!(std::mem::size_of_val(&pixels[..]), 4 * 12);
+/// let lighter: Vec<RGBColorF32> = pixels
+///     .iter()
+///     .map(|&p| RGBColor::from(p).lighten(0.1).into())
+///     .collect();
+/// assert!(RGBColor::from(lighter[0]).lightness() > RGBColor::from(pixels[0]).lightness());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RGBColorF32 {
+    /// The red component. Ranges from 0 to 1 for numbers displayable by sRGB machines.
+    pub r: f32,
+    /// The green component. Ranges from 0 to 1 for numbers displayable by sRGB machines.
+    pub g: f32,
+    /// The blue component. Ranges from 0 to 1 for numbers displayable by sRGB machines.
+    pub b: f32,
+}
+
+impl From<RGBColor> for RGBColorF32 {
+    fn from(color: RGBColor) -> RGBColorF32 {
+        RGBColorF32 {
+            r: color.r as f32,
+            g: color.g as f32,
+            b: color.b as f32,
+        }
+    }
+}
+
+impl From<RGBColorF32> for RGBColor {
+    fn from(color: RGBColorF32) -> RGBColor {
+        RGBColor {
+            r: f64::from(color.r),
+            g: f64::from(color.g),
+            b: f64::from(color.b),
+        }
+    }
+}
+
+impl From<[f32; 3]> for RGBColorF32 {
+    fn from(rgb: [f32; 3]) -> RGBColorF32 {
+        RGBColorF32 {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+    }
+}
+
+impl From<RGBColorF32> for [f32; 3] {
+    fn from(val: RGBColorF32) -> Self {
+        [val.r, val.g, val.b]
+    }
+}
+
+/// The result of converting a color to sRGB for display, along with what was lost on the way, as
+/// returned by [`to_srgb_info`](trait.Color.html#method.to_srgb_info).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SrgbConversion {
+    /// The displayable color, with each channel clipped to lie between 0 and 1.
+    pub rgb: RGBColor,
+    /// Whether the red, green, and blue channels, in that order, had to be clipped.
+    pub clipped: [bool; 3],
+    /// The CIEDE2000 distance between the original color and the clipped one: 0 for colors in the
+    /// sRGB gamut.
+    pub delta_e: f64,
+}
+
+impl SrgbConversion {
+    /// Returns `true` if any channel had to be clipped, and `false` otherwise.
+    pub fn is_clipped(&self) -> bool {
+        self.clipped.iter().any(|&c| c)
+    }
+}
+
+/// Options controlling how [`to_hex`](struct.RGBColor.html#method.to_hex) writes a hex code. The
+/// default matches `to_string`: uppercase, with a leading `#`, six digits, and no alpha.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::HexOptions;
+/// let options = HexOptions {
+///     lowercase: true,
+///     ..HexOptions::default()
+/// };
+/// let color = RGBColor::from_hex_code("#1E90FF").unwrap();
+/// assert_eq!(color.to_hex(options), "#1e90ff");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HexOptions {
+    /// Whether to write the letter digits in lowercase, as CSS and many design tools prefer.
+    pub lowercase: bool,
+    /// Whether to start with `#`.
+    pub hash: bool,
+    /// Whether to use the three-digit shorthand, like `#ABC` for `#AABBCC`, when the color allows
+    /// it. With alpha, this gives four digits, and needs the alpha byte to be doubled as well.
+    pub shorthand: bool,
+    /// An alpha value from 0 to 1 to append as a final byte, as in CSS's `#RRGGBBAA`, or `None` to
+    /// leave it out. Values outside of that range are clamped.
+    pub alpha: Option<f64>,
+}
+
+impl Default for HexOptions {
+    fn default() -> HexOptions {
+        HexOptions {
+            lowercase: false,
+            hash: true,
+            shorthand: false,
+            alpha: None,
+        }
+    }
+}
+
 impl From<(u8, u8, u8)> for RGBColor {
     fn from(rgb: (u8, u8, u8)) -> RGBColor {
         let (r, g, b) = rgb;
@@ -1014,6 +3365,34 @@ impl From<RGBColor> for (u8, u8, u8) {
     }
 }
 
+impl From<[u8; 3]> for RGBColor {
+    fn from(rgb: [u8; 3]) -> RGBColor {
+        RGBColor::from((rgb[0], rgb[1], rgb[2]))
+    }
+}
+
+impl From<RGBColor> for [u8; 3] {
+    fn from(val: RGBColor) -> Self {
+        [val.int_r(), val.int_g(), val.int_b()]
+    }
+}
+
+impl From<[f64; 3]> for RGBColor {
+    fn from(rgb: [f64; 3]) -> RGBColor {
+        RGBColor {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+    }
+}
+
+impl From<RGBColor> for [f64; 3] {
+    fn from(val: RGBColor) -> Self {
+        [val.r, val.g, val.b]
+    }
+}
+
 impl From<Coord> for RGBColor {
     fn from(c: Coord) -> RGBColor {
         RGBColor {
@@ -1054,32 +3433,17 @@ impl Color for RGBColor {
 
         let lin_rgb_vec = *SRGB * vector![xyz_d65.x, xyz_d65.y, xyz_d65.z];
         // now we scale for gamma correction
-        let gamma_correct = |x: &f64| {
-            if x <= &0.0031308 {
-                12.92 * x
-            } else {
-                1.055 * x.powf(1.0 / 2.4) - 0.055
-            }
-        };
-        let float_vec: Vec<f64> = lin_rgb_vec.iter().map(gamma_correct).collect();
         RGBColor {
-            r: float_vec[0],
-            g: float_vec[1],
-            b: float_vec[2],
+            r: srgb_encode(lin_rgb_vec[0]),
+            g: srgb_encode(lin_rgb_vec[1]),
+            b: srgb_encode(lin_rgb_vec[2]),
         }
     }
     fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
-        let uncorrect_gamma = |x: &f64| {
-            if x <= &0.04045 {
-                x / 12.92
-            } else {
-                ((x + 0.055) / 1.055).powf(2.4)
-            }
-        };
         let rgb_vec = vector![
-            uncorrect_gamma(&self.r),
-            uncorrect_gamma(&self.g),
-            uncorrect_gamma(&self.b)
+            srgb_decode(self.r),
+            srgb_decode(self.g),
+            srgb_decode(self.b)
         ];
 
         // invert the matrix multiplication used in from_xyz()
@@ -1142,10 +3506,153 @@ impl Error for RGBParseError {
 }
 
 impl RGBColor {
+    /// Creates a new color from the given components, clamping each to lie between 0 and 1 so the
+    /// result is always displayable. Struct literals, like `RGBColor{r: 1.2, g: 0., b: 0.}`, keep
+    /// the components exactly as given instead, which is useful for intermediate results that may
+    /// temporarily leave the gamut.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::new(1.2, 0.5, -0.1);
+    /// assert_eq!((color.r, color.g, color.b), (1., 0.5, 0.));
+    /// ```
+    pub fn new(r: f64, g: f64, b: f64) -> RGBColor {
+        RGBColor {
+            r: r.clamp(0.0, 1.0),
+            g: g.clamp(0.0, 1.0),
+            b: b.clamp(0.0, 1.0),
+        }
+    }
+    /// Creates a new color from the given components, which must each lie between 0 and 1, inclusive.
+    /// This is for rejecting bad data where it comes in, instead of silently clamping it like
+    /// [`new`](#method.new) does.
+    ///
+    /// # Errors
+    /// Returns `RGBParseError::OutOfRange` if any component is outside of that range or is NaN.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// assert!(RGBColor::try_new(1., 0.5, 0.).is_ok());
+    /// assert_eq!(RGBColor::try_new(1.2, 0.5, 0.), Err(RGBParseError::OutOfRange));
+    /// ```
+    pub fn try_new(r: f64, g: f64, b: f64) -> Result<RGBColor, RGBParseError> {
+        if [r, g, b].iter().all(|x| (0.0..=1.0).contains(x)) {
+            Ok(RGBColor { r, g, b })
+        } else {
+            Err(RGBParseError::OutOfRange)
+        }
+    }
+    /// Creates a new color from percentages, as design specs often give them: 100 is full intensity
+    /// and 0 is none, like in CSS's `rgb(100%, 0%, 50%)`. Each percentage is clamped to lie between 0
+    /// and 100.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let pink = RGBColor::from_percent(100., 0., 50.);
+    /// assert_eq!(pink.to_string(), "#FF0080");
+    /// ```
+    pub fn from_percent(r: f64, g: f64, b: f64) -> RGBColor {
+        RGBColor::new(r / 100.0, g / 100.0, b / 100.0)
+    }
+    /// Creates a new color from 8-bit components from 0 to 255, as in `rgb(255, 0, 128)`. This is the
+    /// same as `RGBColor::from((r, g, b))`, but says what scale it expects.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// assert_eq!(RGBColor::from_u8s(255, 0, 128).to_string(), "#FF0080");
+    /// ```
+    pub fn from_u8s(r: u8, g: u8, b: u8) -> RGBColor {
+        RGBColor::from((r, g, b))
+    }
+    /// Writes this color as a hex code, with the formatting controlled by the given
+    /// [`HexOptions`](struct.HexOptions.html). `to_string` is the same as this with the default
+    /// options. Every option produces a code that
+    /// [`from_hex_code`](#method.from_hex_code) or CSS can read back, as long as there's no alpha
+    /// for the former.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::HexOptions;
+    /// let color = RGBColor::from_hex_code("#AABBCC").unwrap();
+    /// assert_eq!(color.to_hex(HexOptions::default()), color.to_string());
+    /// let css = HexOptions {
+    ///     lowercase: true,
+    ///     shorthand: true,
+    ///     ..HexOptions::default()
+    /// };
+    /// assert_eq!(color.to_hex(css), "#abc");
+    /// let translucent = HexOptions {
+    ///     hash: false,
+    ///     alpha: Some(0.5),
+    ///     ..HexOptions::default()
+    /// };
+    /// assert_eq!(color.to_hex(translucent), "AABBCC80");
+    /// ```
+    pub fn to_hex(&self, opts: HexOptions) -> String {
+        let mut bytes = vec![self.int_r(), self.int_g(), self.int_b()];
+        if let Some(alpha) = opts.alpha {
+            bytes.push((alpha.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+        let mut hex = String::new();
+        if opts.hash {
+            hex.push('#');
+        }
+        // 0xAA is 0xA * 0x11, so a byte can be one digit exactly when it's a multiple of 0x11
+        if opts.shorthand && bytes.iter().all(|&byte| byte % 0x11 == 0) {
+            for byte in bytes {
+                hex.push_str(&format!("{:X}", byte / 0x11));
+            }
+        } else {
+            for byte in bytes {
+                hex.push_str(&format!("{:02X}", byte));
+            }
+        }
+        if opts.lowercase {
+            hex.to_lowercase()
+        } else {
+            hex
+        }
+    }
+    /// Writes this color in CSS functional notation, as in `rgb(255, 0, 128)`, using the same 8-bit
+    /// components as [`int_rgb_tup`](#method.int_rgb_tup). For a color with transparency, see
+    /// [`Alpha::to_css`](../alpha/struct.Alpha.html#method.to_css).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::from_hex_code("#FF0080").unwrap();
+    /// assert_eq!(color.to_css(), "rgb(255, 0, 128)");
+    /// ```
+    pub fn to_css(&self) -> String {
+        format!("rgb({}, {}, {})", self.int_r(), self.int_g(), self.int_b())
+    }
+    /// Writes this color in CSS functional notation with percentages, as in `rgb(100%, 0%, 50%)`.
+    /// Components are clamped to lie between 0% and 100%, and given to at most two decimal places.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor{r: 1., g: 0., b: 0.5};
+    /// assert_eq!(color.to_css_percent(), "rgb(100%, 0%, 50%)");
+    /// ```
+    pub fn to_css_percent(&self) -> String {
+        format!(
+            "rgb({}%, {}%, {}%)",
+            css_percent(self.r),
+            css_percent(self.g),
+            css_percent(self.b)
+        )
+    }
     /// Given a string that represents a hex code, returns the RGB color that the given hex code
     /// represents. Four formats are accepted: `"#rgb"` as a shorthand for `"#rrggbb"`, `#rrggbb` by
-    /// itself, and either of those formats without `#`: `"rgb"` or `"rrggbb"` are acceptable. Returns
-    /// a ColorParseError if the given string does not follow one of these formats.
+    /// itself, and either of those formats without `#`: `"rgb"` or `"rrggbb"` are acceptable.
+    /// Whitespace around the code is ignored. Returns a ColorParseError if the given string does not
+    /// follow one of these formats, including if it's empty.
     /// # Example
     ///
     /// ```
@@ -1166,9 +3673,10 @@ impl RGBColor {
     // otherwise you have really long lines with different reasons for throwing the same error
     #[allow(clippy::if_same_then_else)]
     pub fn from_hex_code(hex: &str) -> Result<RGBColor, RGBParseError> {
-        let mut chars: Vec<char> = hex.chars().collect();
+        // input from text fields often has stray whitespace
+        let mut chars: Vec<char> = hex.trim().chars().collect();
         // check if leading hex, remove if so
-        if chars[0] == '#' {
+        if chars.first() == Some(&'#') {
             chars.remove(0);
         }
         // can only have 3 or 6 characters: error if not so
@@ -1239,7 +3747,198 @@ impl RGBColor {
             Some(x) => Self::from_hex_code(x),
         }
     }
-}
+    /// Gets every X11 color name that [`from_color_name`](#method.from_color_name) understands, in
+    /// alphabetical order and in lowercase, paired with its color. This is useful for building
+    /// swatch pickers, or for finding the named color closest to a given one. Some colors have more
+    /// than one name, like `"gray"` and `"grey"`, and appear once under each.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let named = RGBColor::all_named_colors();
+    /// let teal = RGBColor::from_hex_code("#008080").unwrap();
+    /// let colors: Vec<RGBColor> = named.iter().map(|&(_, color)| color).collect();
+    /// let (name, _) = named[teal.nearest_in_palette(&colors)];
+    /// assert_eq!(name, "teal");
+    /// ```
+    pub fn all_named_colors() -> Vec<(&'static str, RGBColor)> {
+        X11_NAMED_COLORS.clone()
+    }
+    /// Gets the approximate color of a blackbody radiator at the given temperature in kelvin, as it
+    /// would look on a screen: the kind of thing used for warm/cool white sliders or flame
+    /// effects. This uses the Planckian white point given by
+    /// [`Illuminant::blackbody`](../illuminants/enum.Illuminant.html#method.blackbody), treated as a
+    /// light seen in a D65 viewing environment, so no chromatic adaptation removes its tint. The
+    /// result is scaled so that its brightest channel is at full intensity, and then any remaining
+    /// out-of-gamut components are clamped. Temperatures are clamped between 1000 K and 40000 K.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let candle = RGBColor::from_temperature(1900.);
+    /// let sky = RGBColor::from_temperature(12000.);
+    /// // candlelight is a deep orange, and a clear sky is blue
+    /// assert!(candle.r > candle.g && candle.g > candle.b);
+    /// assert!(sky.b > sky.g && sky.g > sky.r);
+    /// ```
+    pub fn from_temperature(kelvin: f64) -> RGBColor {
+        let wp = Illuminant::blackbody(kelvin).white_point();
+        // scale so the brightest linear channel is exactly 1
+        let lin_rgb_vec = *SRGB * vector![wp[0], wp[1], wp[2]];
+        let scale = lin_rgb_vec.max();
+        let rgb = RGBColor::from_xyz(XYZColor {
+            x: wp[0] / scale,
+            y: wp[1] / scale,
+            z: wp[2] / scale,
+            illuminant: Illuminant::D65,
+        });
+        RGBColor {
+            r: rgb.r.clamp(0., 1.),
+            g: rgb.g.clamp(0., 1.),
+            b: rgb.b.clamp(0., 1.),
+        }
+    }
+    /// Shifts the color temperature of this color, as a photo editor's temperature slider does: the
+    /// color is treated as lit by a blackbody at `from_k` kelvin, and relit by one at `to_k` kelvin
+    /// using the Bradford chromatic adaptation in
+    /// [`color_adapt`](struct.XYZColor.html#method.color_adapt). Raising the temperature makes the
+    /// color cooler and bluer, and lowering it makes it warmer and more orange; only the difference
+    /// between the two white points matters, so the same shift applied to a whole image keeps its
+    /// colors consistent with each other. The result isn't clamped, so very large shifts of vivid
+    /// colors may leave the sRGB gamut. Temperatures are clamped as in
+    /// [`Illuminant::blackbody`](../illuminants/enum.Illuminant.html#method.blackbody).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let gray = RGBColor::from_hex_code("#808080").unwrap();
+    /// let cooler = gray.white_balance(5000., 6500.);
+    /// assert!(cooler.b > cooler.r);
+    /// let warmer = gray.white_balance(6500., 5000.);
+    /// assert!(warmer.r > warmer.b);
+    /// ```
+    pub fn white_balance(&self, from_k: f64, to_k: f64) -> RGBColor {
+        let xyz = self.to_xyz(Illuminant::D65);
+        let relit = XYZColor {
+            illuminant: Illuminant::blackbody(from_k),
+            ..xyz
+        }
+        .color_adapt(Illuminant::blackbody(to_k));
+        // the screen is still D65, so keep the tint the adaptation added
+        RGBColor::from_xyz(XYZColor {
+            illuminant: Illuminant::D65,
+            ..relit
+        })
+    }
+    /// Shifts the tint of this color along the green-magenta axis, like the tint slider that goes
+    /// with a photo editor's temperature slider: the other half of
+    /// [`white_balance`](#method.white_balance). Positive amounts push toward magenta and negative
+    /// ones toward green. The amount is in the units of [`duv`](trait.Color.html#method.duv), so
+    /// `adjust_tint(0.01)` lowers the Duv by 0.01: the color moves in the CIE 1960 UCS diagram
+    /// straight across the blackbody locus, which keeps its [`cct`](trait.Color.html#method.cct)
+    /// roughly the same. Its luminance is unchanged. As with `duv`, the color is treated as a light
+    /// in a D65 viewing environment.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let gray = RGBColor::from_hex_code("#808080").unwrap();
+    /// let magenta = gray.adjust_tint(0.01);
+    /// assert!(magenta.g < magenta.r && magenta.g < magenta.b);
+    /// assert!((gray.duv() - magenta.duv() - 0.01).abs() <= 1e-6);
+    /// ```
+    pub fn adjust_tint(&self, amount: f64) -> RGBColor {
+        let (u, v) = self.ucs_uv_1960();
+        if u.is_nan() || v.is_nan() {
+            // black has no tint
+            return *self;
+        }
+        // Duv is measured along the line through the same reference point duv uses, so moving
+        // along that line changes only Duv
+        let (ref_u, ref_v) = DUV_REFERENCE_UV;
+        let (du, dv) = (u - ref_u, v - ref_v);
+        let dist = du.hypot(dv);
+        let scale = (dist - amount) / dist;
+        let (u, v) = (ref_u + du * scale, ref_v + dv * scale);
+        let xyz = self.to_xyz(Illuminant::D65);
+        // back to xy, and then XYZ with the same luminance
+        let d = 2.0 * u - 8.0 * v + 4.0;
+        let (x, y) = (3.0 * u / d, 2.0 * v / d);
+        RGBColor::from_xyz(XYZColor {
+            x: x * xyz.y / y,
+            y: xyz.y,
+            z: (1.0 - x - y) * xyz.y / y,
+            illuminant: Illuminant::D65,
+        })
+    }
+    /// Characterizes this color as a white light, returning its correlated color temperature in kelvin
+    /// and its Duv, as given by [`cct`](trait.Color.html#method.cct) and
+    /// [`duv`](trait.Color.html#method.duv). This is roughly the inverse of
+    /// [`from_temperature`](#method.from_temperature), but the pair describes any near-white color,
+    /// not just blackbodies: the CCT says how warm or cool it is, and the Duv how green or pink.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let warm_white = RGBColor::from_temperature(3000.);
+    /// let (cct, duv) = warm_white.white_point_descriptor();
+    /// assert!((cct - 3000.).abs() <= 50.);
+    /// assert!(duv.abs() <= 0.002);
+    /// ```
+    pub fn white_point_descriptor(&self) -> (f64, f64) {
+        (self.cct(), self.duv())
+    }
+    /// Builds the sRGB color with the given CIELCH lightness, chroma, and hue (in degrees), reducing
+    /// the chroma as little as possible if the color would otherwise be out of gamut. Lightness and
+    /// hue are kept exactly: this gives the most vivid displayable color at that lightness and hue, up
+    /// to the chroma asked for, which makes it easy to author palettes in CIELCH without worrying
+    /// about the gamut. Lightness is clamped between 0 and 100, and negative chromas are treated as 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// // sRGB can't show a blue this vivid at this lightness
+    /// let blue = RGBColor::from_lch_clamped(70., 100., 260.);
+    /// assert!((blue.lightness() - 70.).abs() <= 1e-3);
+    /// assert!((blue.hue() - 260.).abs() <= 1e-3);
+    /// assert!(blue.chroma() < 100.);
+    /// // but a gentler one is fine as-is
+    /// let soft_blue = RGBColor::from_lch_clamped(70., 20., 260.);
+    /// assert!((soft_blue.chroma() - 20.).abs() <= 1e-3);
+    /// ```
+    pub fn from_lch_clamped(l: f64, c: f64, h: f64) -> RGBColor {
+        let lch = |c: f64| CIELCHColor {
+            l: l.clamp(0.0, 100.0),
+            c,
+            h: h.rem_euclid(360.0),
+        };
+        let to_srgb = |c: f64| -> Option<RGBColor> {
+            let rgb: RGBColor = lch(c).convert();
+            if [rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|&x| (-1e-9..=1.0 + 1e-9).contains(&x))
+            {
+                Some(rgb)
+            } else {
+                None
+            }
+        };
+        let c = c.max(0.0);
+        let rgb = match to_srgb(c) {
+            Some(rgb) => rgb,
+            None => {
+                let max_c = RGBColor::max_chroma(l.clamp(0.0, 100.0), h);
+                lch(max_c.min(c)).convert()
+            }
+        };
+        // clean up any rounding error at the edges
+        RGBColor {
+            r: rgb.r.clamp(0.0, 1.0),
+            g: rgb.g.clamp(0.0, 1.0),
+            b: rgb.b.clamp(0.0, 1.0),
+        }
+    }
+}
 
 impl FromStr for RGBColor {
     type Err = RGBParseError;
@@ -1262,6 +3961,7 @@ impl FromStr for RGBColor {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use colors::{AdobeRGBColor, GammaRGBColor};
     use consts::TEST_PRECISION;
 
     #[test]
@@ -1274,7 +3974,6 @@ mod tests {
         assert!(!color1.visually_indistinguishable(&color3));
     }
 
-    #[cfg(feature = "terminal")]
     #[test]
     #[ignore]
     fn can_display_colors() {
@@ -1386,7 +4085,6 @@ mod tests {
         let xyz2 = xyz.color_adapt(Illuminant::D65);
         assert_eq!(xyz, xyz2);
     }
-    #[cfg(feature = "terminal")]
     #[test]
     #[ignore]
     fn fun_dress_color_adaptation_demo() {
@@ -1447,7 +4145,6 @@ mod tests {
         print_col(white);
     }
 
-    #[cfg(feature = "terminal")]
     #[test]
     #[ignore]
     fn fun_color_adaptation_demo() {
@@ -1591,7 +4288,6 @@ mod tests {
             assert_eq!(*hex, RGBColor::from_hex_code(hex).unwrap().to_string());
         }
     }
-    #[cfg(feature = "terminal")]
     #[test]
     #[ignore]
     fn lightness_demo() {
@@ -1735,19 +4431,1550 @@ mod tests {
         }
     }
     #[test]
-    #[ignore]
-    fn color_scheme() {
-        let mut colors: Vec<RGBColor> = vec![];
-        for i in 0..8 {
-            colors.push(
-                CIELCHColor {
-                    l: i as f64 / 7. * 100.,
-                    c: 0.,
-                    h: 0.,
+    fn test_from_reflectance() {
+        // a perfect white reflector should give back the white point of each illuminant
+        let flat = [(360., 1.), (830., 1.)];
+        for illuminant in [
+            Illuminant::D50,
+            Illuminant::D55,
+            Illuminant::D65,
+            Illuminant::D75,
+            Illuminant::Custom([0.9, 1., 0.7]),
+        ]
+        .iter()
+        {
+            let white = XYZColor::from_reflectance(&flat, *illuminant);
+            let wp = illuminant.white_point();
+            assert!((white.x - wp[0]).abs() <= 1e-3);
+            assert!((white.y - wp[1]).abs() <= 1e-6);
+            assert!((white.z - wp[2]).abs() <= 1e-3);
+        }
+        // and a flat gray reflector should scale it down
+        let gray = XYZColor::from_reflectance(&[(500., 0.2)], Illuminant::D65);
+        assert!((gray.y - 0.2).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_munsell_notation() {
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let notation = red.munsell_notation();
+        let (hue, rest) = notation.split_at(notation.find(' ').unwrap());
+        assert!(hue.ends_with('R') && !hue.ends_with("YR") && !hue.ends_with("PR"));
+        // sRGB red is a bright, very saturated color
+        let parts: Vec<&str> = rest.trim().split('/').collect();
+        assert_eq!(parts[0], "5");
+        assert!(parts[1].parse::<u32>().unwrap() >= 14);
+        // check the other families
+        let yellow = RGBColor::from_hex_code("#FFFF00").unwrap();
+        assert!(yellow.munsell_notation().contains("Y "));
+        let blue = RGBColor::from_hex_code("#0000FF").unwrap();
+        assert!(blue.munsell_notation().contains("PB "));
+        assert_eq!(
+            RGBColor::from_hex_code("#FFFFFF")
+                .unwrap()
+                .munsell_notation(),
+            "N 10/"
+        );
+        assert_eq!(
+            RGBColor::from_hex_code("#000000")
+                .unwrap()
+                .munsell_notation(),
+            "N 0/"
+        );
+    }
+    #[test]
+    fn test_posterize() {
+        // a smooth gradient should collapse to two values per channel
+        let gradient: Vec<RGBColor> = (0..=100)
+            .map(|i| {
+                let x = f64::from(i) / 100.;
+                RGBColor {
+                    r: x,
+                    g: 1. - x,
+                    b: x * x,
                 }
-                .convert(),
+            })
+            .collect();
+        for levels in 2..6 {
+            let mut rs: Vec<u8> = gradient
+                .iter()
+                .map(|c| c.posterize(levels).int_r())
+                .collect();
+            let mut gs: Vec<u8> = gradient
+                .iter()
+                .map(|c| c.posterize(levels).int_g())
+                .collect();
+            let mut bs: Vec<u8> = gradient
+                .iter()
+                .map(|c| c.posterize(levels).int_b())
+                .collect();
+            for channel in [&mut rs, &mut gs, &mut bs].iter_mut() {
+                channel.sort_unstable();
+                channel.dedup();
+                assert_eq!(channel.len(), levels);
+            }
+        }
+        // the perceptual version only gives the middles of the lightness bands
+        for color in gradient.iter() {
+            let l = color.posterize_lightness(4).lightness();
+            assert!([12.5, 37.5, 62.5, 87.5]
+                .iter()
+                .any(|band| (l - band).abs() <= 1e-6));
+        }
+    }
+    #[test]
+    fn test_outline_color() {
+        let near_white = RGBColor::from_hex_code("#FAFAFA").unwrap();
+        let near_black = RGBColor::from_hex_code("#050505").unwrap();
+        let white_outline = near_white.outline_color();
+        let black_outline = near_black.outline_color();
+        assert!(white_outline.lightness() < near_white.lightness() - 10.);
+        assert!(black_outline.lightness() > near_black.lightness() + 10.);
+        // the outline should always be distinguishable from the swatch
+        for hex in ["#FF0000", "#00FF00", "#0000FF", "#808080", "#FFFF00"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            assert!(color.distance(&color.outline_color()) >= 5.);
+        }
+    }
+    #[test]
+    fn test_from_reflectance_observers() {
+        // a greenish surface: the observers weight the spectrum differently, so they disagree
+        let samples: Vec<(f64, f64)> = (36..=83)
+            .map(|i| {
+                let wavelength = f64::from(i) * 10.;
+                (wavelength, (-((wavelength - 520.) / 40.).powi(2)).exp())
+            })
+            .collect();
+        let two = XYZColor::from_reflectance(&samples, Illuminant::D65);
+        let two_explicit = XYZColor::from_reflectance_with_observer(
+            &samples,
+            Illuminant::D65,
+            Observer::TwoDegree,
+        );
+        let ten = XYZColor::from_reflectance_with_observer(
+            &samples,
+            Illuminant::D65,
+            Observer::TenDegree,
+        );
+        assert_eq!(two, two_explicit);
+        assert!((two.x - ten.x).abs() >= 0.01 || (two.z - ten.z).abs() >= 0.01);
+        // but both should agree that it's green
+        assert!(two.y > two.x && ten.y > ten.x);
+    }
+    #[test]
+    fn test_perceptual_spread() {
+        let base = RGBColor::from_hex_code("#4080C0").unwrap();
+        let near_identical: Vec<RGBColor> = (0..5)
+            .map(|i| RGBColor {
+                r: base.r + f64::from(i) * 1e-4,
+                ..base
+            })
+            .collect();
+        assert!(perceptual_spread(&near_identical) <= 0.1);
+        let rainbow: Vec<RGBColor> = [
+            "#FF0000", "#FF8000", "#FFFF00", "#00FF00", "#0000FF", "#8000FF",
+        ]
+        .iter()
+        .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+        .collect();
+        assert!(perceptual_spread(&rainbow) >= 30.);
+        assert_eq!(perceptual_spread(&[base]), 0.);
+        assert_eq!(perceptual_spread(&[]), 0.);
+    }
+    #[test]
+    fn test_grayscale_ramp() {
+        let ramp = grayscale_ramp(9);
+        assert_eq!(ramp.len(), 9);
+        assert_eq!(ramp[0].to_string(), "#000000");
+        assert_eq!(ramp[8].to_string(), "#FFFFFF");
+        // compare the spread of step sizes with a ramp that's even in RGB
+        let rgb_ramp: Vec<RGBColor> = (0..9)
+            .map(|i| {
+                let v = f64::from(i) / 8.;
+                RGBColor { r: v, g: v, b: v }
+            })
+            .collect();
+        let step_ratio = |colors: &[RGBColor]| {
+            let steps: Vec<f64> = colors
+                .windows(2)
+                .map(|w| {
+                    let lab1: CIELABColor = w[0].convert();
+                    let lab2: CIELABColor = w[1].convert();
+                    (lab1.l - lab2.l).abs()
+                })
+                .collect();
+            let max = steps.iter().cloned().fold(f64::MIN, f64::max);
+            let min = steps.iter().cloned().fold(f64::MAX, f64::min);
+            max / min
+        };
+        assert!(step_ratio(&ramp) <= 1.01);
+        assert!(step_ratio(&rgb_ramp) >= 1.2);
+        assert_eq!(grayscale_ramp(1)[0].to_string(), "#777777");
+        assert!(grayscale_ramp(0).is_empty());
+    }
+    #[test]
+    fn test_in_rgb_gamut() {
+        use colors::AdobeRGBColor;
+        // the corners of each gamut should be in that gamut
+        for r in 0..2 {
+            for g in 0..2 {
+                for b in 0..2 {
+                    let (r, g, b) = (f64::from(r), f64::from(g), f64::from(b));
+                    assert!(RGBColor { r, g, b }.in_rgb_gamut::<RGBColor>());
+                    assert!(AdobeRGBColor { r, g, b }.in_rgb_gamut::<AdobeRGBColor>());
+                }
+            }
+        }
+        // a saturated green that's within Adobe RGB but not sRGB
+        let green = AdobeRGBColor {
+            r: 0.2,
+            g: 0.9,
+            b: 0.1,
+        };
+        assert!(green.in_rgb_gamut::<AdobeRGBColor>());
+        assert!(!green.in_rgb_gamut::<RGBColor>());
+        // everything in sRGB is within Adobe RGB
+        let srgb_green = RGBColor::from_hex_code("#00FF00").unwrap();
+        assert!(srgb_green.in_rgb_gamut::<AdobeRGBColor>());
+        // a color that's just barely out of gamut is caught
+        assert!(!RGBColor {
+            r: 1.01,
+            g: 0.5,
+            b: 0.5
+        }
+        .in_rgb_gamut::<RGBColor>());
+    }
+    #[test]
+    fn test_quantized_rgb() {
+        use std::collections::HashSet;
+        // these are all different floats, but the same 8-bit color
+        let colors = [
+            RGBColor {
+                r: 0.2,
+                g: 0.4,
+                b: 0.6,
+            },
+            RGBColor {
+                r: 0.2001,
+                g: 0.4,
+                b: 0.6,
+            },
+            RGBColor {
+                r: 0.2,
+                g: 0.3999,
+                b: 0.6001,
+            },
+        ];
+        let set: HashSet<QuantizedRGB> = colors.iter().cloned().map(QuantizedRGB::from).collect();
+        assert_eq!(set.len(), 1);
+        assert!(colors.iter().all(|c| c.to_key() == colors[0].int_rgb_tup()));
+        // a visibly different color is a different key
+        let other = RGBColor {
+            r: 0.21,
+            g: 0.4,
+            b: 0.6,
+        };
+        assert_ne!(QuantizedRGB(other), QuantizedRGB(colors[0]));
+        // the wrapped color keeps its precision
+        let unwrapped: RGBColor = QuantizedRGB(colors[1]).into();
+        assert_eq!(unwrapped, colors[1]);
+    }
+    #[test]
+    fn test_distance_to_colormap() {
+        use colormap::ListedColorMap;
+        let viridis = ListedColorMap::viridis();
+        for &x in [0.0, 0.25, 0.6, 1.0].iter() {
+            let color: RGBColor = viridis.transform_single(x);
+            assert!(color.distance_to_colormap(&viridis, 256) <= 0.5);
+        }
+        // magenta is nowhere near viridis, and neither is gray
+        let magenta = RGBColor::from_hex_code("#FF00FF").unwrap();
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        assert!(magenta.distance_to_colormap(&viridis, 256) >= 20.);
+        assert!(gray.distance_to_colormap(&viridis, 256) >= 10.);
+        // each of the 16 samples is also one of the 256, so coarser sampling can only look further away
+        assert!(
+            gray.distance_to_colormap(&viridis, 16) >= gray.distance_to_colormap(&viridis, 256)
+        );
+    }
+
+    #[test]
+    fn test_dominant_colors() {
+        // a synthetic image: 50% orange, 30% teal with slight noise, 15% white, 5% black
+        let orange = RGBColor::from_hex_code("#FF8000").unwrap();
+        let teal = RGBColor::from_hex_code("#208080").unwrap();
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let mut pixels = Vec::new();
+        for i in 0..100 {
+            let pixel = match i % 20 {
+                0..=9 => orange,
+                10..=15 => RGBColor {
+                    r: teal.r,
+                    g: teal.g + 0.01 * f64::from(i % 2),
+                    b: teal.b,
+                },
+                16..=18 => white,
+                _ => black,
+            };
+            pixels.push(pixel);
+        }
+        let dominant = RGBColor::dominant_colors(&pixels, 3);
+        assert_eq!(dominant.len(), 3);
+        let counts: Vec<usize> = dominant.iter().map(|&(_, count)| count).collect();
+        assert_eq!(counts, vec![50, 30, 15]);
+        assert_eq!(dominant[0].0.to_string(), orange.to_string());
+        // the noisy teal pixels average out to the middle of the noise
+        assert!((dominant[1].0.g - (teal.g + 0.005)).abs() <= 1e-10);
+        assert_eq!(dominant[2].0.to_string(), white.to_string());
+        // asking for more than there are just gives everything
+        assert_eq!(RGBColor::dominant_colors(&pixels, 10).len(), 4);
+        assert!(RGBColor::dominant_colors(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_constrained_scheme() {
+        let hue_in = |h: f64, start: f64, end: f64| {
+            (h - start).rem_euclid(360.) <= (end - start).rem_euclid(360.)
+        };
+        let red = RGBColor::from_hex_code("#C62828").unwrap();
+        // both a normal range and one that wraps through 0
+        for &(start, end) in [(20.0, 60.0), (330.0, 30.0)].iter() {
+            let scheme = red.constrained_scheme(6, (start, end));
+            assert_eq!(scheme.len(), 6);
+            for color in scheme.iter() {
+                assert!(hue_in(color.hue(), start, end));
+            }
+            for (i, c1) in scheme.iter().enumerate() {
+                for c2 in scheme.iter().skip(i + 1) {
+                    assert!(c1.distance(c2) >= 10.);
+                }
+            }
+        }
+        // red's hue is about 33, so it only starts the scheme if that's in range
+        assert_eq!(
+            red.constrained_scheme(3, (20., 60.))[0].to_string(),
+            red.to_string()
+        );
+        assert!(red.constrained_scheme(3, (330., 30.))[0].to_string() != red.to_string());
+        assert!(red.constrained_scheme(0, (20., 60.)).is_empty());
+    }
+
+    #[test]
+    fn test_median_cut() {
+        // two tight clusters of the same size, one around dark blue and one around light orange
+        let blue = RGBColor {
+            r: 0.1,
+            g: 0.15,
+            b: 0.5,
+        };
+        let orange = RGBColor {
+            r: 0.95,
+            g: 0.7,
+            b: 0.3,
+        };
+        let mut pixels = Vec::new();
+        for i in 0..40 {
+            // offsets that average to 0 within each cluster
+            let offset = 0.02 * (f64::from(i % 5) - 2.0);
+            let center = if i < 20 { blue } else { orange };
+            pixels.push(RGBColor {
+                r: center.r + offset,
+                g: center.g - offset,
+                b: center.b + offset / 2.0,
+            });
+        }
+        let mut palette = RGBColor::median_cut(&pixels, 2);
+        assert_eq!(palette.len(), 2);
+        palette.sort_by(|a, b| a.r.partial_cmp(&b.r).unwrap());
+        assert!(palette[0].distance(&blue) <= 0.5);
+        assert!(palette[1].distance(&orange) <= 0.5);
+        // asking for more colors than there are gives each distinct color
+        let few = vec![blue, blue, orange];
+        assert_eq!(RGBColor::median_cut(&few, 8).len(), 2);
+        assert!(RGBColor::median_cut(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_in_palette() {
+        let palette: Vec<RGBColor> = ["#1B9E77", "#D95F02", "#7570B3", "#E7298A", "#66A61E"]
+            .iter()
+            .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+            .collect();
+        // palette members map to themselves
+        for (i, color) in palette.iter().enumerate() {
+            assert_eq!(color.nearest_in_palette(&palette), i);
+        }
+        // slightly off colors map to the closest member
+        let near_purple = RGBColor::from_hex_code("#7872B0").unwrap();
+        assert_eq!(near_purple.nearest_in_palette(&palette), 2);
+        // ties go to the first
+        let doubled = vec![palette[1], palette[1]];
+        assert_eq!(palette[1].nearest_in_palette(&doubled), 0);
+    }
+
+    #[test]
+    fn test_tint_preserving_luminance() {
+        let original: Vec<RGBColor> = ["#202020", "#8B4513", "#3CB371", "#87CEEB", "#F5F5DC"]
+            .iter()
+            .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+            .collect();
+        let mut pixels = original.clone();
+        // negative hues should wrap
+        tint_preserving_luminance(&mut pixels, -40., 20.);
+        for (before, after) in original.iter().zip(pixels.iter()) {
+            assert!((after.hue() - 320.).abs() <= 1e-6);
+            assert!((after.lightness() - before.lightness()).abs() <= 1e-6);
+        }
+        // so the lightness ordering is kept
+        let order = |colors: &[RGBColor]| {
+            let mut indices: Vec<usize> = (0..colors.len()).collect();
+            indices.sort_by(|&i, &j| {
+                colors[i]
+                    .lightness()
+                    .partial_cmp(&colors[j].lightness())
+                    .unwrap()
+            });
+            indices
+        };
+        assert_eq!(order(&original), order(&pixels));
+    }
+
+    #[test]
+    fn test_dither_floyd_steinberg() {
+        // a horizontal gradient from black to white, 32 pixels wide and 16 tall, reduced to just
+        // black and white
+        let (width, height) = (32, 16);
+        let black = RGBColor {
+            r: 0.,
+            g: 0.,
+            b: 0.,
+        };
+        let white = RGBColor {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+        };
+        let mut pixels = Vec::new();
+        for _y in 0..height {
+            for x in 0..width {
+                let v = srgb_encode(x as f64 / (width - 1) as f64);
+                pixels.push(RGBColor { r: v, g: v, b: v });
+            }
+        }
+        dither_floyd_steinberg(&mut pixels, width, &[black, white]);
+        assert!(pixels.iter().all(|&p| p == black || p == white));
+        // the left edge stays black and the right edge white
+        for y in 0..height {
+            assert_eq!(pixels[y * width], black);
+            assert_eq!(pixels[y * width + width - 1], white);
+        }
+        // each band of columns has about as much white as the gradient was bright there, instead
+        // of a hard edge down the middle (only roughly, as some error is lost off the edges)
+        for band in 0..4 {
+            let columns = (band * width / 4)..((band + 1) * width / 4);
+            let expected: f64 = columns
+                .clone()
+                .map(|x| x as f64 / (width - 1) as f64)
+                .sum::<f64>()
+                / columns.len() as f64;
+            let mut num_white = 0;
+            for y in 0..height {
+                for x in columns.clone() {
+                    if pixels[y * width + x] == white {
+                        num_white += 1;
+                    }
+                }
+            }
+            let fraction = f64::from(num_white) / (height * columns.len()) as f64;
+            assert!((fraction - expected).abs() <= 0.08);
+        }
+        // in the middle, black and white are mixed within every row: the dither pattern
+        for y in 0..height {
+            let row = &pixels[y * width + 12..y * width + 20];
+            assert!(row.contains(&black) && row.contains(&white));
+        }
+    }
+
+    #[test]
+    fn test_duotone() {
+        let shadow = RGBColor::from_hex_code("#4A148C").unwrap();
+        let highlight = RGBColor::from_hex_code("#FFEB3B").unwrap();
+        let original: Vec<RGBColor> = ["#0A0A0A", "#3366CC", "#CC3333", "#F0F0F0"]
+            .iter()
+            .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+            .collect();
+        let mut pixels = original.clone();
+        duotone(&mut pixels, shadow, highlight);
+        // the darkest pixel is close to the shadow color, and the lightest to the highlight
+        assert!(pixels[0].distance(&shadow) <= 5.);
+        assert!(pixels[3].distance(&highlight) <= 5.);
+        assert!(pixels[0].distance(&shadow) < pixels[1].distance(&shadow));
+        // everything ends up on the line between the two in CIELAB
+        let shadow_lab: CIELABColor = shadow.convert();
+        let highlight_lab: CIELABColor = highlight.convert();
+        for pixel in pixels.iter() {
+            let lab: CIELABColor = pixel.convert();
+            let t = (lab.l - shadow_lab.l) / (highlight_lab.l - shadow_lab.l);
+            assert!((lab.a - (shadow_lab.a + t * (highlight_lab.a - shadow_lab.a))).abs() <= 1e-6);
+            assert!((lab.b - (shadow_lab.b + t * (highlight_lab.b - shadow_lab.b))).abs() <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_complement_in_gamut() {
+        let in_srgb = |c: &RGBColor| [c.r, c.g, c.b].iter().all(|&x| (0.0..=1.0).contains(&x));
+        // the naive complement of a vivid yellow is a very light, very saturated blue that sRGB can't
+        // show
+        let yellow = RGBColor::from_hex_code("#FFE000").unwrap();
+        let mut naive: CIELCHColor = yellow.convert();
+        naive.h = (naive.h + 180.) % 360.;
+        let naive_rgb: RGBColor = naive.convert();
+        assert!(!in_srgb(&naive_rgb));
+        let complement = yellow.complement_in_gamut();
+        assert!(in_srgb(&complement));
+        assert!((complement.hue() - naive.h).abs() <= 1e-3);
+        // it should still be a reasonably strong blue, not a gray
+        assert!(complement.chroma() >= 30.);
+        // colors with a displayable complement just get it
+        let muted = RGBColor::from_hex_code("#907860").unwrap();
+        let mut ideal: CIELCHColor = muted.convert();
+        ideal.h = (ideal.h + 180.) % 360.;
+        assert!(muted.complement_in_gamut().distance(&ideal) <= 1e-6);
+    }
+
+    #[test]
+    fn test_to_srgb_info() {
+        // in gamut: nothing is lost, even with rounding error at the edges
+        for hex in ["#123456", "#FFFFFF", "#000000", "#FF00FF"].iter() {
+            let rgb = RGBColor::from_hex_code(hex).unwrap();
+            let lab: CIELABColor = rgb.convert();
+            let info = lab.to_srgb_info();
+            assert!(!info.is_clipped());
+            assert_eq!(info.clipped, [false; 3]);
+            assert!(info.delta_e <= 1e-6);
+            assert_eq!(info.rgb.to_string(), rgb.to_string());
+        }
+        // out of gamut: a blue beyond sRGB loses something
+        let blue = CIELABColor {
+            l: 30.,
+            a: 40.,
+            b: -120.,
+        };
+        let info = blue.to_srgb_info();
+        assert!(info.is_clipped());
+        assert!(info.clipped[2] || info.clipped[0]);
+        assert!(info.delta_e > 1.);
+        for &x in [info.rgb.r, info.rgb.g, info.rgb.b].iter() {
+            assert!((0.0..=1.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn test_white_point_descriptor() {
+        // sRGB white is D65, a little above the blackbody curve at about 6500 K
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        let (cct, duv) = white.white_point_descriptor();
+        assert!((cct - 6504.).abs() <= 10.);
+        assert!((duv - 0.0032).abs() <= 2e-4);
+        // grays have the same chromaticity as white
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        let (gray_cct, gray_duv) = gray.white_point_descriptor();
+        assert!((gray_cct - cct).abs() <= 1e-6 && (gray_duv - duv).abs() <= 1e-9);
+        // blackbody colors sit on the curve
+        for &kelvin in [2700.0, 4000.0, 5000.0].iter() {
+            let (cct, duv) = RGBColor::from_temperature(kelvin).white_point_descriptor();
+            assert!((cct - kelvin).abs() <= 0.02 * kelvin);
+            assert!(duv.abs() <= 0.002);
+        }
+    }
+
+    #[test]
+    fn test_warm_cool() {
+        let warm = ["#FF8000", "#E53935", "#FFD54F", "#8D6E63"];
+        let cool = ["#00BCD4", "#1E88E5", "#43A047", "#283593"];
+        for hex in warm.iter() {
+            assert!(RGBColor::from_hex_code(hex).unwrap().is_warm());
+        }
+        for hex in cool.iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            assert!(!color.is_warm());
+            assert!(color.temperature_bias() < 0.);
+        }
+        // grays are neutral
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        assert!(gray.temperature_bias().abs() <= 1e-3);
+        assert!(!gray.is_warm());
+        // and a muted orange is less warm than a vivid one
+        let vivid = RGBColor::from_hex_code("#FF8000").unwrap();
+        let muted = RGBColor::from_hex_code("#A09080").unwrap();
+        assert!(muted.temperature_bias() > 0.);
+        assert!(muted.temperature_bias() < vivid.temperature_bias());
+    }
+
+    #[test]
+    fn test_with_setters() {
+        let original = RGBColor::from_hex_code("#4A7A9C").unwrap();
+        let color = original;
+        let mut mutated = original;
+        mutated.set_hue(-60.);
+        assert_eq!(color.with_hue(-60.), mutated);
+        let mut mutated = original;
+        mutated.set_lightness(70.);
+        assert_eq!(color.with_lightness(70.), mutated);
+        let mut mutated = original;
+        mutated.set_chroma(15.);
+        assert_eq!(color.with_chroma(15.), mutated);
+        let mut mutated = original;
+        mutated.set_saturation(0.5);
+        assert_eq!(color.with_saturation(0.5), mutated);
+        // none of that touched the original
+        assert_eq!(color, original);
+        // and they chain
+        let chained = color.with_hue(300.).with_chroma(40.).with_lightness(55.);
+        assert!((chained.hue() - 300.).abs() <= 1e-6);
+        assert!((chained.chroma() - 40.).abs() <= 1e-6);
+        assert!((chained.lightness() - 55.).abs() <= 1e-6);
+    }
+
+    #[test]
+    fn test_from_lch_clamped() {
+        for l in (0..=10).map(|x| f64::from(x) * 10.) {
+            for h in (0..12).map(|x| f64::from(x) * 30.) {
+                let rgb = RGBColor::from_lch_clamped(l, 150., h);
+                assert!([rgb.r, rgb.g, rgb.b]
+                    .iter()
+                    .all(|&x| (0.0..=1.0).contains(&x)));
+                // sRGB white isn't exactly CIELAB white after chromatic adaptation, so allow for that
+                assert!((rgb.lightness() - l).abs() <= 1e-2);
+                // black and white have no hue to keep
+                if l > 0. && l < 100. {
+                    let hue_diff = (rgb.hue() - h).rem_euclid(360.);
+                    assert!(hue_diff.min(360. - hue_diff) <= 1e-3);
+                    // and it's as vivid as it gets: any more chroma leaves the gamut
+                    let more: RGBColor = CIELCHColor {
+                        l,
+                        c: rgb.chroma() + 0.5,
+                        h,
+                    }
+                    .convert();
+                    assert!([more.r, more.g, more.b]
+                        .iter()
+                        .any(|&x| !(0.0..=1.0).contains(&x)));
+                }
+            }
+        }
+        // in-gamut colors are left alone
+        let teal = RGBColor::from_hex_code("#2A9D8F").unwrap();
+        let lch: CIELCHColor = teal.convert();
+        assert_eq!(
+            RGBColor::from_lch_clamped(lch.l, lch.c, lch.h).to_string(),
+            "#2A9D8F"
+        );
+    }
+
+    #[test]
+    fn test_popping_background() {
+        for hex in ["#FFD700", "#1E90FF", "#800020", "#2E8B57", "#FF69B4"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let lch: CIELCHColor = color.convert();
+            let background = color.popping_background();
+            let bg_lch: CIELCHColor = background.convert();
+            assert!(bg_lch.c <= 10.);
+            assert!(bg_lch.c >= 1.);
+            assert!((lch.l - bg_lch.l).abs() >= 35.);
+            let hue_diff = (bg_lch.h - lch.h).rem_euclid(360.);
+            assert!((hue_diff - 180.).abs() <= 15.);
+        }
+        // neutrals get neutral backgrounds
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        assert!(gray.popping_background().chroma() <= 0.1);
+    }
+
+    #[test]
+    fn test_distinct_palette() {
+        for hex in ["#FFFFFF", "#000000", "#404040"].iter() {
+            let background = RGBColor::from_hex_code(hex).unwrap();
+            for n in 1..=6 {
+                let palette = RGBColor::distinct_palette(n, background);
+                assert_eq!(palette.len(), n);
+                for (i, color) in palette.iter().enumerate() {
+                    assert!(color.distance(&background) >= 20.);
+                    for other in palette[i + 1..].iter() {
+                        assert!(color.distance(other) >= 25.);
+                    }
+                }
+            }
+        }
+        // larger palettes extend smaller ones
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        let small = RGBColor::distinct_palette(3, white);
+        let large = RGBColor::distinct_palette(8, white);
+        assert_eq!(small[..], large[..3]);
+        assert!(RGBColor::distinct_palette(0, white).is_empty());
+    }
+
+    #[test]
+    fn test_remap_to_palette_smooth() {
+        use std::collections::HashSet;
+        let palette: Vec<RGBColor> = ["#000080", "#FFFFFF", "#FFA500"]
+            .iter()
+            .map(|hex| RGBColor::from_hex_code(hex).unwrap())
+            .collect();
+        // a smooth gradient from dark blue through light gray to orange
+        let start = RGBColor::from_hex_code("#101070").unwrap();
+        let middle = RGBColor::from_hex_code("#F4F4F4").unwrap();
+        let end = RGBColor::from_hex_code("#F0A010").unwrap();
+        let mut pixels: Vec<RGBColor> = (0..=100)
+            .map(|i| {
+                let t = f64::from(i) / 50.0;
+                if t <= 1.0 {
+                    start.gradient(&middle)(t)
+                } else {
+                    middle.gradient(&end)(t - 1.0)
+                }
+            })
+            .collect();
+        let max_jump = |pixels: &[RGBColor]| {
+            pixels
+                .windows(2)
+                .map(|pair| pair[0].distance(&pair[1]))
+                .fold(0.0, f64::max)
+        };
+        let snapped: Vec<RGBColor> = pixels
+            .iter()
+            .map(|c| palette[c.nearest_in_palette(&palette)])
+            .collect();
+        remap_to_palette_smooth(&mut pixels, &palette);
+        // no big jumps between neighboring pixels, unlike hard quantization
+        assert!(max_jump(&pixels) <= 8.);
+        assert!(max_jump(&snapped) >= 30.);
+        // and many distinct colors, not three flat regions
+        let distinct: HashSet<QuantizedRGB> = pixels.iter().map(|&c| QuantizedRGB(c)).collect();
+        assert!(distinct.len() >= 50);
+        // the palette colors map to themselves
+        let mut same = palette.clone();
+        remap_to_palette_smooth(&mut same, &palette);
+        for (a, b) in same.iter().zip(palette.iter()) {
+            assert_eq!(a.to_string(), b.to_string());
+        }
+        // a single-color palette maps everything to it
+        let mut single = pixels.clone();
+        remap_to_palette_smooth(&mut single, &palette[..1]);
+        assert!(single.iter().all(|c| c.to_string() == "#000080"));
+    }
+
+    #[test]
+    fn test_to_hex() {
+        let color = RGBColor::from_hex_code("#aabbcc").unwrap();
+        let lower_short = HexOptions {
+            lowercase: true,
+            shorthand: true,
+            ..HexOptions::default()
+        };
+        assert_eq!(color.to_hex(lower_short), "#abc");
+        assert_eq!(
+            color.to_hex(HexOptions {
+                lowercase: true,
+                ..HexOptions::default()
+            }),
+            "#aabbcc"
+        );
+        // shorthand only applies when every byte allows it
+        let other = RGBColor::from_hex_code("#AABBCD").unwrap();
+        assert_eq!(other.to_hex(lower_short), "#aabbcd");
+        let with_alpha = |alpha| HexOptions {
+            alpha: Some(alpha),
+            ..lower_short
+        };
+        assert_eq!(color.to_hex(with_alpha(1.)), "#abcf");
+        assert_eq!(color.to_hex(with_alpha(0.5)), "#aabbcc80");
+        assert_eq!(color.to_hex(with_alpha(-1.)), "#abc0");
+        let no_hash = HexOptions {
+            hash: false,
+            ..HexOptions::default()
+        };
+        assert_eq!(color.to_hex(no_hash), "AABBCC");
+        for hex in ["#000000", "#FFFFFF", "#1E90FF", "#F0A010"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            assert_eq!(color.to_hex(HexOptions::default()), color.to_string());
+            let short = color.to_hex(lower_short);
+            assert_eq!(RGBColor::from_hex_code(&short).unwrap().to_string(), *hex);
+        }
+    }
+
+    #[test]
+    fn test_to_css() {
+        let color = RGBColor::from_hex_code("#FF0080").unwrap();
+        assert_eq!(color.to_css(), "rgb(255, 0, 128)");
+        assert_eq!(color.to_css_percent(), "rgb(100%, 0%, 50.2%)");
+        // out-of-gamut components are clamped in both modes
+        let out = RGBColor {
+            r: 1.2,
+            g: -0.1,
+            b: 0.25,
+        };
+        assert_eq!(out.to_css(), "rgb(255, 0, 64)");
+        assert_eq!(out.to_css_percent(), "rgb(100%, 0%, 25%)");
+        assert_eq!(css_number(-0.001), "0");
+        assert_eq!(css_number(12.345678), "12.35");
+    }
+
+    #[test]
+    fn test_hex_code_whitespace_and_empty() {
+        assert_eq!(
+            RGBColor::from_hex_code(""),
+            Err(RGBParseError::InvalidHexSyntax)
+        );
+        assert_eq!(
+            RGBColor::from_hex_code("   "),
+            Err(RGBParseError::InvalidHexSyntax)
+        );
+        assert_eq!(
+            RGBColor::from_hex_code("#"),
+            Err(RGBParseError::InvalidHexSyntax)
+        );
+        assert_eq!(
+            RGBColor::from_hex_code(" #abc ").unwrap().to_string(),
+            "#AABBCC"
+        );
+        assert_eq!(
+            RGBColor::from_hex_code("\tff8000\n").unwrap().to_string(),
+            "#FF8000"
+        );
+        // whitespace inside the code is still an error
+        assert_eq!(
+            RGBColor::from_hex_code("#ff 8000"),
+            Err(RGBParseError::InvalidHexSyntax)
+        );
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        for &(r, g, b) in [(0, 0, 0), (255, 255, 255), (12, 200, 97)].iter() {
+            let from_tuple = RGBColor::from((r, g, b));
+            let from_array = RGBColor::from([r, g, b]);
+            assert_eq!(from_tuple, from_array);
+            let array: [u8; 3] = from_array.into();
+            assert_eq!(array, [r, g, b]);
+        }
+        let color = RGBColor::from([0.25, 0.5, 1.5]);
+        assert_eq!((color.r, color.g, color.b), (0.25, 0.5, 1.5));
+        let array: [f64; 3] = color.into();
+        assert_eq!(array, [0.25, 0.5, 1.5]);
+    }
+
+    #[test]
+    fn test_u32_packing() {
+        let color = RGBColor::from((0x12, 0x34, 0x56));
+        assert_eq!(color.to_u32_rgb(), 0x00123456);
+        assert_eq!(color.to_u32_argb(0xFF), 0xFF123456);
+        assert_eq!(color.to_u32_argb(0), 0x00123456);
+        assert_eq!(RGBColor::from_u32_rgb(0xAB123456), color);
+        for r in (0..=255).step_by(51) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(85) {
+                    let color = RGBColor::from((r, g, b));
+                    let unpacked = RGBColor::from_u32_rgb(color.to_u32_argb(0x7F));
+                    assert_eq!(unpacked.int_rgb_tup(), (r, g, b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lighten_darken() {
+        for hex in ["#00808F", "#B03060", "#556B2F", "#808080"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let lighter = color.lighten(0.15);
+            let darker = color.darken(0.15);
+            assert!((lighter.lightness() - color.lightness() - 15.).abs() <= 1e-6);
+            assert!((color.lightness() - darker.lightness() - 15.).abs() <= 1e-6);
+            if color.chroma() > 1. {
+                assert!((lighter.hue() - color.hue()).abs() <= 1.);
+                assert!((darker.hue() - color.hue()).abs() <= 1.);
+            }
+        }
+        // lightness is clamped at the ends
+        let color = RGBColor::from_hex_code("#C0C0C0").unwrap();
+        assert!((color.lighten(1.).lightness() - 100.).abs() <= 1e-6);
+        assert!(color.darken(1.).lightness().abs() <= 1e-6);
+        assert!((color.lighten(-0.1).lightness() - color.darken(0.1).lightness()).abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_saturate_desaturate() {
+        for hex in ["#B07080", "#406080", "#8F8F40"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let more = color.saturate(0.25);
+            let less = color.desaturate(0.25);
+            assert!(more.chroma() > color.chroma());
+            assert!(less.chroma() < color.chroma());
+            assert!((more.chroma() - 1.25 * color.chroma()).abs() <= 1e-6);
+            assert!((less.chroma() - 0.75 * color.chroma()).abs() <= 1e-6);
+            assert!((more.hue() - color.hue()).abs() <= 1e-6);
+            assert!((more.lightness() - color.lightness()).abs() <= 1e-6);
+            let gray = color.desaturate(1.0);
+            assert!(gray.chroma() <= 1e-6);
+            assert_eq!(gray.to_string(), color.grayscale().to_string());
+            // chroma can't go negative
+            assert!(color.desaturate(2.0).chroma() <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_metameric_mismatch() {
+        use nalgebra::{Matrix3, Vector3};
+        let spectrum = |f: &dyn Fn(f64) -> f64| -> Vec<(f64, f64)> {
+            (36..=83)
+                .map(|i| {
+                    let wavelength = f64::from(i) * 10.;
+                    (wavelength, f(wavelength))
+                })
+                .collect()
+        };
+        let xyz = |f: &dyn Fn(f64) -> f64| {
+            let color = XYZColor::from_reflectance(&spectrum(f), Illuminant::D65);
+            Vector3::new(color.x, color.y, color.z)
+        };
+        // build a metameric black for D65: a wiggle, with broad bumps in the blue, green, and red
+        // added to cancel out its color, so adding it to a spectrum doesn't change the color
+        let wiggle = |w: f64| ((w - 380.) / 40.).sin();
+        let bump = |center: f64| move |w: f64| (-((w - center) / 40.).powi(2)).exp();
+        let bumps = [bump(450.), bump(540.), bump(610.)];
+        let basis = Matrix3::from_columns(&[xyz(&bumps[0]), xyz(&bumps[1]), xyz(&bumps[2])]);
+        let coefs = basis.lu().solve(&-xyz(&wiggle)).unwrap();
+        let black = |w: f64| {
+            wiggle(w) + coefs[0] * bumps[0](w) + coefs[1] * bumps[1](w) + coefs[2] * bumps[2](w)
+        };
+
+        let gray = spectrum(&|_| 0.5);
+        let metamer = spectrum(&|w| 0.5 + 0.4 * black(w));
+        assert!(metamer.iter().all(|&(_, r)| (0. ..=1.).contains(&r)));
+        // they match under D65, and so also "under" D65 again
+        let same = XYZColor::metameric_mismatch(&gray, &metamer, Illuminant::D65, Illuminant::D65);
+        assert!(same.unwrap() <= 1e-6);
+        // but not under D50
+        let mismatch =
+            XYZColor::metameric_mismatch(&gray, &metamer, Illuminant::D65, Illuminant::D50);
+        assert!(mismatch.unwrap() > 1.);
+        // and if they don't match in the first place, there's no answer
+        let darker = spectrum(&|_| 0.3);
+        assert_eq!(
+            XYZColor::metameric_mismatch(&gray, &darker, Illuminant::D65, Illuminant::D50),
+            None
+        );
+    }
+
+    #[test]
+    fn test_white_balance() {
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        let cooler = gray.white_balance(4000., 7000.);
+        assert!(cooler.b > gray.b && cooler.r < gray.r);
+        assert!(cooler.temperature_bias() < 0.);
+        let warmer = gray.white_balance(7000., 4000.);
+        assert!(warmer.r > gray.r && warmer.b < gray.b);
+        assert!(warmer.temperature_bias() > 0.);
+        // no shift changes nothing, and shifting back undoes a shift
+        assert!(gray.white_balance(5000., 5000.).distance(&gray) <= 1e-10);
+        let color = RGBColor::from_hex_code("#6A8F3C").unwrap();
+        let back = color
+            .white_balance(5000., 6000.)
+            .white_balance(6000., 5000.);
+        assert!(back.distance(&color) <= 1e-6);
+    }
+
+    #[test]
+    fn test_adjust_tint() {
+        for hex in ["#808080", "#FFF0E0", "#D0E0FF"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let magenta = color.adjust_tint(0.01);
+            let green = color.adjust_tint(-0.01);
+            assert!((color.duv() - magenta.duv() - 0.01).abs() <= 1e-6);
+            assert!((green.duv() - color.duv() - 0.01).abs() <= 1e-6);
+            // magenta has less green than the original, and green more
+            assert!(magenta.g - (magenta.r + magenta.b) / 2. < color.g - (color.r + color.b) / 2.);
+            assert!(green.g - (green.r + green.b) / 2. > color.g - (color.r + color.b) / 2.);
+            // the color temperature and luminance barely move
+            assert!((magenta.cct() - color.cct()).abs() / color.cct() <= 0.05);
+            let y = |c: RGBColor| c.to_xyz(Illuminant::D65).y;
+            assert!((y(magenta) - y(color)).abs() <= 1e-10);
+        }
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        assert_eq!(black.adjust_tint(0.01), black);
+    }
+
+    #[test]
+    fn test_xyz_mix_in() {
+        let blue = RGBColor::from_hex_code("#0000FF")
+            .unwrap()
+            .to_xyz(Illuminant::D65);
+        let yellow = RGBColor::from_hex_code("#FFFF00")
+            .unwrap()
+            .to_xyz(Illuminant::D50);
+        let lab_mid = blue.mix_in::<CIELABColor>(yellow, 0.5);
+        let xyz_mid = blue.mix(yellow);
+        assert_eq!(lab_mid.illuminant, Illuminant::D65);
+        assert!(lab_mid.distance(&xyz_mid) > 5.);
+        // in CIELAB, the midpoint is exactly halfway
+        let lab_blue: CIELABColor = blue.convert();
+        let lab_yellow: CIELABColor = yellow.convert();
+        let lab: CIELABColor = lab_mid.convert();
+        assert!((lab.l - (lab_blue.l + lab_yellow.l) / 2.).abs() <= 1e-6);
+        // the ends are the colors themselves
+        assert!(blue.mix_in::<CIELABColor>(yellow, 0.).distance(&blue) <= 1e-10);
+        assert!(blue.mix_in::<CIELABColor>(yellow, 1.).distance(&yellow) <= 1e-10);
+    }
+
+    #[test]
+    fn test_srgb_transfer_slices() {
+        let values: Vec<f64> = (0..=100).map(|i| f64::from(i) / 100.).collect();
+        let mut decoded = values.clone();
+        srgb_decode_slice(&mut decoded);
+        for (&x, &lin) in values.iter().zip(decoded.iter()) {
+            assert_eq!(lin, srgb_decode(x));
+        }
+        // the two pieces of the curve meet at the breakpoint
+        let linear_piece = 0.04045 / 12.92;
+        let power_piece = ((0.04045f64 + 0.055) / 1.055).powf(2.4);
+        assert!((linear_piece - power_piece).abs() <= 1e-6);
+        assert_eq!(srgb_decode(0.04045), linear_piece);
+        assert!((srgb_decode(0.04045 + 1e-12) - power_piece).abs() <= 1e-6);
+        let linear_piece = 12.92 * 0.0031308;
+        let power_piece = 1.055 * 0.0031308f64.powf(1.0 / 2.4) - 0.055;
+        assert!((linear_piece - power_piece).abs() <= 1e-6);
+        assert_eq!(srgb_encode(0.0031308), linear_piece);
+        srgb_encode_slice(&mut decoded);
+        for (&x, &y) in values.iter().zip(decoded.iter()) {
+            assert!((x - y).abs() <= 1e-12);
+        }
+        // and they agree with RGBColor's conversion
+        let color = RGBColor {
+            r: 0.2,
+            g: 0.5,
+            b: 0.9,
+        };
+        let lin = *SRGB * {
+            let xyz = color.to_xyz(Illuminant::D65);
+            vector![xyz.x, xyz.y, xyz.z]
+        };
+        assert!((lin[1] - srgb_decode(0.5)).abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_rgb_f32_conversion() {
+        assert_eq!(
+            std::mem::size_of::<RGBColorF32>() * 2,
+            std::mem::size_of::<RGBColor>()
+        );
+        let color = RGBColor {
+            r: 0.123456789,
+            g: 0.5,
+            b: 1.0,
+        };
+        let compact: RGBColorF32 = color.into();
+        let back: RGBColor = compact.into();
+        assert!((back.r - color.r).abs() <= 1e-7);
+        assert_eq!(back.g, 0.5);
+        assert_eq!(back.b, 1.0);
+        assert!(back.distance(&color) <= 1e-4);
+        // every 8-bit color survives the trip unchanged
+        for i in 0..=255u8 {
+            let c = RGBColor::from((i, 255 - i, i / 2));
+            assert_eq!(
+                RGBColor::from(RGBColorF32::from(c)).to_string(),
+                c.to_string()
             );
         }
+        let arr: [f32; 3] = RGBColorF32::from([0.25, 0.5, 0.75]).into();
+        assert_eq!(arr, [0.25, 0.5, 0.75]);
+        // serializes like the other color types
+        let compact = RGBColorF32::from([0.25, 0.5, 0.75]);
+        let json = serde_json::to_string(&compact).unwrap();
+        assert_eq!(json, r#"{"r":0.25,"g":0.5,"b":0.75}"#);
+        assert_eq!(serde_json::from_str::<RGBColorF32>(&json).unwrap(), compact);
+    }
+
+    #[test]
+    fn test_srgb_u8_table() {
+        for &level in [0u8, 1, 10, 11, 12, 64, 128, 200, 254, 255].iter() {
+            let x = f64::from(level) / 255.0;
+            let closed_form = if x <= 0.04045 {
+                x / 12.92
+            } else {
+                ((x + 0.055) / 1.055).powf(2.4)
+            };
+            let linear = RGBColor::from_u8_linear(level, level, level);
+            assert!((linear[0] - closed_form).abs() <= 1e-15);
+            assert_eq!(linear[0], linear[2]);
+        }
+        // and it agrees with the full conversion
+        let color = RGBColor::from((30, 144, 255));
+        let linear = RGBColor::from_u8_linear(30, 144, 255);
+        let xyz = color.to_xyz(Illuminant::D65);
+        let expected = *SRGB * vector![xyz.x, xyz.y, xyz.z];
+        for i in 0..3 {
+            assert!((linear[i] - expected[i]).abs() <= 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_xterm_256() {
+        assert_eq!(RGBColor::from((255, 0, 0)).to_xterm_256(), 196);
+        assert_eq!(RGBColor::from((0, 0, 0)).to_xterm_256(), 16);
+        assert_eq!(RGBColor::from((255, 255, 255)).to_xterm_256(), 231);
+        assert_eq!(RGBColor::from((0, 135, 255)).to_xterm_256(), 33);
+        assert_eq!(RGBColor::from((118, 118, 118)).to_xterm_256(), 243);
+        // colors close to an entry pick it
+        assert_eq!(RGBColor::from((250, 5, 3)).to_xterm_256(), 196);
+        // and every entry maps to itself
+        for (i, entry) in XTERM_256_PALETTE.iter().enumerate() {
+            let index = entry.to_xterm_256() as usize;
+            assert!(index == i + 16 || entry.distance(&XTERM_256_PALETTE[index - 16]) == 0.);
+        }
+    }
+
+    #[test]
+    fn test_terminal_escapes() {
+        let color = RGBColor::from((255, 0, 128));
+        assert_eq!(
+            color.write_colored_str("hi"),
+            "\x1b[38;2;255;0;128mhi\x1b[39m"
+        );
+        assert_eq!(
+            color.write_color(),
+            "\x1b[48;2;255;0;128m\x1b[38;2;255;0;128m■\x1b[39m\x1b[49m"
+        );
+        assert_eq!(
+            RGBColor::from((255, 0, 0)).write_color_256(),
+            "\x1b[48;5;196m\x1b[38;5;196m■\x1b[39m\x1b[49m"
+        );
+    }
+
+    #[test]
+    fn test_write_color_if() {
+        let color = CIELABColor {
+            l: 50.,
+            a: 40.,
+            b: -20.,
+        };
+        let plain = color.write_color_if(false);
+        let text = color.write_colored_str_if("text", false);
+        assert!(!plain.contains('\x1b') && !text.contains('\x1b'));
+        assert_eq!(plain, color.convert::<RGBColor>().to_string());
+        assert_eq!(text, "text");
+        assert_eq!(color.write_color_if(true), color.write_color());
+        assert_eq!(
+            color.write_colored_str_if("text", true),
+            color.write_colored_str("text")
+        );
+    }
+
+    #[test]
+    fn test_rgb_constructors() {
+        let clamped = RGBColor::new(1.5, 0.25, -2.);
+        assert_eq!(
+            clamped,
+            RGBColor {
+                r: 1.,
+                g: 0.25,
+                b: 0.
+            }
+        );
+        assert_eq!(
+            RGBColor::new(0.1, 0.2, 0.3),
+            RGBColor::try_new(0.1, 0.2, 0.3).unwrap()
+        );
+        assert!(RGBColor::try_new(0., 1., 0.).is_ok());
+        for &(r, g, b) in [(1.01, 0., 0.), (0., -0.01, 0.), (0., 0., f64::NAN)].iter() {
+            assert_eq!(RGBColor::try_new(r, g, b), Err(RGBParseError::OutOfRange));
+        }
+    }
+
+    #[test]
+    fn test_outline_against() {
+        let hex = |code: &str| RGBColor::from_hex_code(code).unwrap();
+        assert!((hex("#FFFFFF").contrast_ratio(&hex("#000000")) - 21.).abs() <= 1e-3);
+        assert_eq!(hex("#3366CC").contrast_ratio(&hex("#3366CC")), 1.);
+        assert_eq!(
+            hex("#3366CC").contrast_ratio(&hex("#EEEEEE")),
+            hex("#EEEEEE").contrast_ratio(&hex("#3366CC"))
+        );
+        // light fill on light background: outlined in black
+        assert_eq!(
+            hex("#F0F0F0").outline_against(&hex("#FFFFFF")),
+            hex("#000000")
+        );
+        // dark fill on dark background: outlined in white
+        assert_eq!(
+            hex("#202040").outline_against(&hex("#101010")),
+            hex("#FFFFFF")
+        );
+        // dark fill on light background: already stands out
+        assert_eq!(
+            hex("#202040").outline_against(&hex("#FFFFFF")),
+            hex("#202040")
+        );
+    }
+
+    #[test]
+    fn test_snap_neutral() {
+        // a gray that picked up a chroma of about 1e-6
+        let almost_gray = CIELCHColor {
+            l: 60.,
+            c: 1e-6,
+            h: 120.,
+        };
+        assert!(almost_gray.is_neutral(1e-4));
+        let snapped = almost_gray.snap_neutral(1e-4);
+        assert_eq!(snapped.c, 0.);
+        assert!((snapped.l - 60.).abs() <= 1e-8);
+        let rgb: RGBColor = snapped.convert();
+        assert!((rgb.r - rgb.g).abs() <= 1e-4 && (rgb.g - rgb.b).abs() <= 1e-4);
+        assert!(!CIELCHColor {
+            l: 60.,
+            c: 1e-6,
+            h: 120.,
+        }
+        .is_neutral(1e-7));
+        let orange = RGBColor::from_hex_code("#FF8800").unwrap();
+        assert!(!orange.is_neutral(1.));
+        assert_eq!(orange.snap_neutral(1.), orange);
+    }
+
+    #[test]
+    fn test_recolor_and_tint() {
+        let muted = RGBColor::from_hex_code("#8A9A7B").unwrap();
+        for &hue in [0., 45., 200., 330., -30.].iter() {
+            let recolored = muted.recolor_to_hue(hue);
+            let diff = (recolored.hue() - hue).rem_euclid(360.);
+            assert!(diff.min(360. - diff) <= 1e-6);
+            assert!((recolored.lightness() - muted.lightness()).abs() <= 1e-6);
+            assert!(recolored.in_rgb_gamut::<RGBColor>());
+        }
+        // grays stay gray
+        let gray = RGBColor::from_hex_code("#777777").unwrap();
+        assert!(gray.recolor_to_hue(120.).chroma() <= 0.05);
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let blue = RGBColor::from_hex_code("#0000FF").unwrap();
+        assert!(red.tint_toward(&blue, 0.).distance(&red) <= 1e-10);
+        assert!(red.tint_toward(&blue, 1.).distance(&blue) <= 1e-10);
+        let halfway: CIELABColor = red.tint_toward(&blue, 0.5).convert();
+        let (red_lab, blue_lab): (CIELABColor, CIELABColor) = (red.convert(), blue.convert());
+        assert!((halfway.l - (red_lab.l + blue_lab.l) / 2.).abs() <= 1e-8);
+    }
+
+    #[test]
+    fn test_soft_proof_cmyk() {
+        let hex = |code: &str| RGBColor::from_hex_code(code).unwrap();
+        // vivid screen greens, blues, and cyans can't be printed
+        for &code in ["#00FF00", "#0000FF"].iter() {
+            let color = hex(code);
+            let printed = color.soft_proof_cmyk();
+            assert!(printed.chroma() < color.chroma() - 20.);
+            assert!(printed.in_rgb_gamut::<RGBColor>());
+        }
+        let cyan = hex("#00FFFF");
+        assert_eq!(cyan.soft_proof_cmyk().to_string(), "#00AEEF");
+        assert!(cyan.soft_proof_cmyk().distance(&cyan) > 20.);
+        // paper white and the inks themselves come back unchanged
+        assert_eq!(hex("#FFFFFF").soft_proof_cmyk(), hex("#FFFFFF"));
+        assert_eq!(hex("#000000").soft_proof_cmyk().to_string(), "#231F20");
+        // a muted color is close to printable already
+        let tan = hex("#C8A882");
+        assert!(tan.soft_proof_cmyk().distance(&tan) < 5.);
+    }
+
+    #[test]
+    fn test_xyz_coord_round_trip() {
+        let xyz = XYZColor {
+            x: 0.25,
+            y: 0.5,
+            z: 0.75,
+            illuminant: Illuminant::Custom([0.9, 1., 1.1]),
+        };
+        let c = xyz.to_coord();
+        assert_eq!(
+            c,
+            Coord {
+                x: 0.25,
+                y: 0.5,
+                z: 0.75
+            }
+        );
+        assert_eq!(XYZColor::from_coord(c, xyz.illuminant), xyz);
+        let d65 = XYZColor::from_coord(c, Illuminant::D65);
+        assert_eq!(d65.illuminant, Illuminant::D65);
+        assert_eq!(d65.to_coord(), c);
+    }
+
+    #[test]
+    fn test_convert_with_illuminant() {
+        let color = RGBColor::from_hex_code("#3A7F2C").unwrap();
+        // plain convert gives XYZ under D50, which is a different point
+        let d50: XYZColor = color.convert();
+        let d65: XYZColor = color.convert_with_illuminant(Illuminant::D65);
+        assert!((d50.z - d65.z).abs() >= 0.01);
+        assert!(d50.distance(&d65) <= TEST_PRECISION);
+        assert_eq!(d65, color.to_xyz(Illuminant::D65));
+        // between two D65 spaces, the results agree
+        let adobe_d50: AdobeRGBColor = color.convert();
+        let adobe_d65: AdobeRGBColor = color.convert_with_illuminant(Illuminant::D65);
+        assert!(adobe_d50.distance(&adobe_d65) <= TEST_PRECISION);
+    }
+
+    #[test]
+    fn test_convert_intermediate_illuminant() {
+        // sRGB and this gamma RGB are both D65, so going through D50 adds a pointless adaptation:
+        // measure how much error that builds up compared to staying in D65
+        let (mut via_d50, mut via_d65) = (0.0f64, 0.0f64);
+        for i in 0..200 {
+            let color = RGBColor {
+                r: (f64::from(i) * 0.137).fract(),
+                g: (f64::from(i) * 0.311).fract(),
+                b: (f64::from(i) * 0.771).fract(),
+            };
+            let (mut a, mut b) = (color, color);
+            for _ in 0..100 {
+                let a_gamma: GammaRGBColor = a.convert();
+                a = a_gamma.convert();
+                let b_gamma: GammaRGBColor = b.convert_with_illuminant(Illuminant::D65);
+                b = b_gamma.convert_with_illuminant(Illuminant::D65);
+            }
+            let error = |c: RGBColor| {
+                (c.r - color.r)
+                    .abs()
+                    .max((c.g - color.g).abs())
+                    .max((c.b - color.b).abs())
+            };
+            via_d50 = via_d50.max(error(a));
+            via_d65 = via_d65.max(error(b));
+        }
+        // both are far below anything visible
+        assert!(via_d50 <= 1e-12);
+        assert!(via_d65 <= 1e-12);
+    }
+
+    #[test]
+    fn test_from_percent_and_u8s() {
+        let pink = RGBColor::from_percent(100., 0., 50.);
+        assert_eq!(pink.to_string(), "#FF0080");
+        assert_eq!(
+            pink,
+            RGBColor {
+                r: 1.,
+                g: 0.,
+                b: 0.5
+            }
+        );
+        assert_eq!(
+            RGBColor::from_percent(150., -10., 25.),
+            RGBColor::new(1., 0., 0.25)
+        );
+        assert_eq!(
+            RGBColor::from_u8s(255, 0, 128),
+            RGBColor::from_hex_code("#FF0080").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_chromaticity() {
+        // the white points of each illuminant are at their standard chromaticities
+        let d65 = XYZColor::white_point(Illuminant::D65);
+        let (x, y) = d65.chromaticity_xy();
+        assert!((x - 0.3127).abs() <= 1e-4);
+        assert!((y - 0.3290).abs() <= 1e-4);
+        let (u, v) = d65.chromaticity_uv();
+        assert!((u - 0.1978).abs() <= 1e-4);
+        assert!((v - 0.4683).abs() <= 1e-4);
+        // u'v' is a projective transform of xy
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let (x, y) = red.chromaticity_xy();
+        let (u, v) = red.chromaticity_uv();
+        let denom = -2.0 * x + 12.0 * y + 3.0;
+        assert!((u - 4.0 * x / denom).abs() <= 1e-10);
+        assert!((v - 9.0 * y / denom).abs() <= 1e-10);
+        // sRGB's red primary
+        assert!((x - 0.64).abs() <= 1e-3);
+        assert!((y - 0.33).abs() <= 1e-3);
+    }
+
+    #[test]
+    fn test_all_named_colors() {
+        let named = RGBColor::all_named_colors();
+        assert_eq!(named.len(), 148);
+        let red = named.iter().find(|&&(name, _)| name == "red").unwrap().1;
+        assert_eq!(red.to_string(), "#FF0000");
+        // every entry agrees with from_color_name
+        for &(name, color) in named.iter() {
+            assert_eq!(RGBColor::from_color_name(name).unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn test_harmony_score() {
+        let lch_palette = |l: f64, c: f64, hues: &[f64]| -> Vec<RGBColor> {
+            hues.iter()
+                .map(|&h| CIELCHColor { l, c, h }.convert())
+                .collect()
+        };
+        // a clean triadic palette beats arbitrary hues
+        let triadic = lch_palette(55., 40., &[30., 150., 270.]);
+        let random = lch_palette(55., 40., &[10., 75., 205., 260.]);
+        let triadic_score = harmony_score(&triadic);
+        let random_score = harmony_score(&random);
+        assert!(triadic_score > random_score + 0.2);
+        assert!((0.0..=1.0).contains(&triadic_score) && (0.0..=1.0).contains(&random_score));
+        // analogous colors don't clash at all
+        assert!((harmony_score(&lch_palette(55., 40., &[200., 215., 230.])) - 1.).abs() <= 1e-10);
+        // complementary colors harmonize, but vibrate at the same lightness
+        let even = harmony_score(&lch_palette(55., 60., &[40., 220.]));
+        let mut varied = lch_palette(30., 60., &[40.]);
+        varied.extend(lch_palette(80., 60., &[220.]));
+        assert!(harmony_score(&varied) > even);
+        assert!((harmony_score(&varied) - 1.).abs() <= 1e-6);
+        // grays go with anything
+        let mut with_grays = triadic.clone();
+        with_grays.extend(grayscale_ramp(3));
+        assert!((harmony_score(&with_grays) - triadic_score).abs() <= 1e-10);
+        assert_eq!(harmony_score(&grayscale_ramp(5)), 1.);
+    }
+
+    #[test]
+    fn test_from_temperature() {
+        // a 6500 K blackbody is slightly pinker than D65, but should be hard to tell from white
+        let white = RGBColor::from_temperature(6500.);
+        assert!(
+            white.distance(&RGBColor {
+                r: 1.,
+                g: 1.,
+                b: 1.
+            }) <= 5.
+        );
+        // 2000 K should be a pretty clear orange
+        let orange = RGBColor::from_temperature(2000.);
+        assert_eq!(orange.int_r(), 255);
+        assert!(orange.hue() > 40. && orange.hue() < 80.);
+        assert!(orange.chroma() > 40.);
+        // out-of-range temperatures are clamped
+        assert_eq!(
+            RGBColor::from_temperature(500.).to_string(),
+            RGBColor::from_temperature(1000.).to_string()
+        );
+    }
+    #[test]
+    fn test_cct() {
+        let d65 = XYZColor::white_point(Illuminant::D65);
+        assert!((d65.cct() - 6504.).abs() <= 5.);
+        // sRGB white is D65 white
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        assert!((white.cct() - d65.cct()).abs() <= 5.);
+        // round trip through from_temperature for some common whites
+        for temp in [2700., 4000., 5500., 8000.].iter() {
+            let rgb = RGBColor::from_temperature(*temp);
+            assert!((rgb.cct() - temp).abs() / temp <= 0.02);
+        }
+    }
+    #[test]
+    fn test_duv() {
+        // published (CCT, Duv) values for the standard daylight illuminants
+        let d50 = XYZColor::white_point(Illuminant::D50);
+        let d65 = XYZColor::white_point(Illuminant::D65);
+        assert!((d50.cct() - 5003.).abs() <= 5.);
+        assert!((d50.duv() - 0.0033).abs() <= 2e-4);
+        assert!((d65.cct() - 6504.).abs() <= 5.);
+        assert!((d65.duv() - 0.0032).abs() <= 2e-4);
+        // blackbodies are on the locus by definition
+        for temp in [2000., 2856., 4000., 10000.].iter() {
+            let wp = Illuminant::blackbody(*temp).white_point();
+            let xyz = XYZColor {
+                x: wp[0],
+                y: wp[1],
+                z: wp[2],
+                illuminant: Illuminant::D65,
+            };
+            assert!(xyz.duv().abs() <= 3e-4);
+        }
+        // greenish whites are above, pinkish below
+        let green = RGBColor::from_hex_code("#f0fff0").unwrap();
+        let pink = RGBColor::from_hex_code("#fff0ff").unwrap();
+        assert!(green.duv() > 0.005);
+        assert!(pink.duv() < -0.004);
+    }
+    #[test]
+    fn test_analogous_scheme() {
+        let seed = RGBColor::from_hex_code("#D00A12").unwrap();
+        let seed_lch: CIELCHColor = seed.convert();
+        for count in 1..7 {
+            let scheme = seed.analogous_scheme(count, 15.);
+            assert_eq!(scheme.len(), count);
+            // hue differences, accounting for wrapping around 0 (the seed is red)
+            let offsets: Vec<f64> = scheme
+                .iter()
+                .map(|c| (c.hue() - seed_lch.h + 180.).rem_euclid(360.) - 180.)
+                .collect();
+            for i in 0..count {
+                // evenly spaced
+                if i > 0 {
+                    assert!((offsets[i] - offsets[i - 1] - 15.).abs() <= 1e-6);
+                }
+                // symmetric around the seed
+                assert!((offsets[i] + offsets[count - 1 - i]).abs() <= 1e-6);
+                // same lightness and chroma
+                assert!((scheme[i].lightness() - seed_lch.l).abs() <= 1e-6);
+                assert!((scheme[i].chroma() - seed_lch.c).abs() <= 1e-6);
+            }
+        }
+        assert!(seed.analogous_scheme(0, 15.).is_empty());
+    }
+    #[test]
+    fn test_would_be_distinct() {
+        let palette = vec![
+            RGBColor::from_hex_code("#000000").unwrap(),
+            RGBColor::from_hex_code("#FF0000").unwrap(),
+            RGBColor::from_hex_code("#0000FF").unwrap(),
+        ];
+        let near_red = RGBColor::from_hex_code("#FE0101").unwrap();
+        let green = RGBColor::from_hex_code("#00FF00").unwrap();
+        assert!(!near_red.would_be_distinct(&palette, 1.));
+        assert!(green.would_be_distinct(&palette, 20.));
+        // works with any color type
+        let lab: CIELABColor = near_red.convert();
+        assert!(!lab.would_be_distinct(&palette, 1.));
+        assert!(lab.would_be_distinct(&[], 1000.));
+    }
+    #[test]
+    fn test_hue_oclock() {
+        // red is at 12 and its opposite at 6
+        assert_eq!(hue_to_oclock(0.), 12.);
+        assert_eq!(hue_to_oclock(360.), 12.);
+        assert_eq!(hue_to_oclock(180.), 6.);
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let red_oclock = hue_to_oclock(red.hue());
+        assert!(!(2. ..=11.).contains(&red_oclock));
+        // the two functions should be inverses
+        for i in 0..36 {
+            let hue = f64::from(i) * 10.;
+            assert!((oclock_to_hue(hue_to_oclock(hue)) - hue).abs() <= 1e-10);
+        }
+    }
+    #[test]
+    fn test_to_svg_rect() {
+        let color = CIELABColor {
+            l: 50.,
+            a: 20.,
+            b: -40.,
+        };
+        let hex = color.convert::<RGBColor>().to_string();
+        let svg = color.to_svg_rect(120, 30);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert!(svg.contains(&format!("fill=\"{}\"", hex)));
+        assert!(svg.contains("width=\"120\""));
+        assert!(svg.contains("height=\"30\""));
+    }
+    #[test]
+    #[ignore]
+    fn color_scheme() {
+        let mut colors: Vec<RGBColor> = grayscale_ramp(8);
         for j in 0..8 {
             colors.push(
                 CIELCHColor {
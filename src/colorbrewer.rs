@@ -0,0 +1,427 @@
+//! This module provides the [ColorBrewer](http://colorbrewer2.org) palettes by Cynthia Brewer, the
+//! standard choice for maps and charts. Each palette was designed by hand for a specific number of
+//! classes, or data categories, so palettes are looked up by both name and class count. There are
+//! three kinds, described by [`SchemeType`](enum.SchemeType.html):
+//!
+//! - *Qualitative* palettes, like `Set1` and `Dark2`, for unordered categories. These are best used
+//!   as a list of colors with [`palette`](fn.palette.html).
+//! - *Sequential* palettes, like `Blues` and `YlOrRd`, for ordered data from low to high.
+//! - *Diverging* palettes, like `RdBu`, for data with a meaningful midpoint.
+//!
+//! Sequential and diverging palettes can also be used as colormaps with
+//! [`colormap`](fn.colormap.html). Names are matched ignoring case. The palette data is licensed
+//! under the Apache License 2.0, copyright Cynthia Brewer, Mark Harrower, and The Pennsylvania State
+//! University.
+
+use color::RGBColor;
+use colormap::ListedColorMap;
+
+/// The three kinds of ColorBrewer palettes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SchemeType {
+    /// Palettes for categories with no order, where every color should be equally prominent.
+    Qualitative,
+    /// Palettes for ordered data, running from light for low values to dark for high values.
+    Sequential,
+    /// Palettes for ordered data with a critical midpoint, which is light, with dark colors of two
+    /// different hues at either end.
+    Diverging,
+}
+
+// Qualitative palettes: the colors for fewer classes are the first colors of the full palette.
+const ACCENT: [&str; 8] = [
+    "#7fc97f", "#beaed4", "#fdc086", "#ffff99", "#386cb0", "#f0027f", "#bf5b17", "#666666",
+];
+const DARK2: [&str; 8] = [
+    "#1b9e77", "#d95f02", "#7570b3", "#e7298a", "#66a61e", "#e6ab02", "#a6761d", "#666666",
+];
+const PAIRED: [&str; 12] = [
+    "#a6cee3", "#1f78b4", "#b2df8a", "#33a02c", "#fb9a99", "#e31a1c", "#fdbf6f", "#ff7f00",
+    "#cab2d6", "#6a3d9a", "#ffff99", "#b15928",
+];
+const PASTEL1: [&str; 9] = [
+    "#fbb4ae", "#b3cde3", "#ccebc5", "#decbe4", "#fed9a6", "#ffffcc", "#e5d8bd", "#fddaec",
+    "#f2f2f2",
+];
+const PASTEL2: [&str; 8] = [
+    "#b3e2cd", "#fdcdac", "#cbd5e8", "#f4cae4", "#e6f5c9", "#fff2ae", "#f1e2cc", "#cccccc",
+];
+const SET1: [&str; 9] = [
+    "#e41a1c", "#377eb8", "#4daf4a", "#984ea3", "#ff7f00", "#ffff33", "#a65628", "#f781bf",
+    "#999999",
+];
+const SET2: [&str; 8] = [
+    "#66c2a5", "#fc8d62", "#8da0cb", "#e78ac3", "#a6d854", "#ffd92f", "#e5c494", "#b3b3b3",
+];
+const SET3: [&str; 12] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#d9d9d9", "#bc80bd", "#ccebc5", "#ffed6f",
+];
+
+// Sequential and diverging palettes: one list for each class count, starting from 3.
+const BLUES: [&[&str]; 7] = [
+    &["#deebf7", "#9ecae1", "#3182bd"],
+    &["#eff3ff", "#bdd7e7", "#6baed6", "#2171b5"],
+    &["#eff3ff", "#bdd7e7", "#6baed6", "#3182bd", "#08519c"],
+    &[
+        "#eff3ff", "#c6dbef", "#9ecae1", "#6baed6", "#3182bd", "#08519c",
+    ],
+    &[
+        "#eff3ff", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#084594",
+    ],
+    &[
+        "#f7fbff", "#deebf7", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#084594",
+    ],
+    &[
+        "#f7fbff", "#deebf7", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#08519c",
+        "#08306b",
+    ],
+];
+const GREENS: [&[&str]; 7] = [
+    &["#e5f5e0", "#a1d99b", "#31a354"],
+    &["#edf8e9", "#bae4b3", "#74c476", "#238b45"],
+    &["#edf8e9", "#bae4b3", "#74c476", "#31a354", "#006d2c"],
+    &[
+        "#edf8e9", "#c7e9c0", "#a1d99b", "#74c476", "#31a354", "#006d2c",
+    ],
+    &[
+        "#edf8e9", "#c7e9c0", "#a1d99b", "#74c476", "#41ab5d", "#238b45", "#005a32",
+    ],
+    &[
+        "#f7fcf5", "#e5f5e0", "#c7e9c0", "#a1d99b", "#74c476", "#41ab5d", "#238b45", "#005a32",
+    ],
+    &[
+        "#f7fcf5", "#e5f5e0", "#c7e9c0", "#a1d99b", "#74c476", "#41ab5d", "#238b45", "#006d2c",
+        "#00441b",
+    ],
+];
+const GREYS: [&[&str]; 7] = [
+    &["#f0f0f0", "#bdbdbd", "#636363"],
+    &["#f7f7f7", "#cccccc", "#969696", "#525252"],
+    &["#f7f7f7", "#cccccc", "#969696", "#636363", "#252525"],
+    &[
+        "#f7f7f7", "#d9d9d9", "#bdbdbd", "#969696", "#636363", "#252525",
+    ],
+    &[
+        "#f7f7f7", "#d9d9d9", "#bdbdbd", "#969696", "#737373", "#525252", "#252525",
+    ],
+    &[
+        "#ffffff", "#f0f0f0", "#d9d9d9", "#bdbdbd", "#969696", "#737373", "#525252", "#252525",
+    ],
+    &[
+        "#ffffff", "#f0f0f0", "#d9d9d9", "#bdbdbd", "#969696", "#737373", "#525252", "#252525",
+        "#000000",
+    ],
+];
+const ORANGES: [&[&str]; 7] = [
+    &["#fee6ce", "#fdae6b", "#e6550d"],
+    &["#feedde", "#fdbe85", "#fd8d3c", "#d94701"],
+    &["#feedde", "#fdbe85", "#fd8d3c", "#e6550d", "#a63603"],
+    &[
+        "#feedde", "#fdd0a2", "#fdae6b", "#fd8d3c", "#e6550d", "#a63603",
+    ],
+    &[
+        "#feedde", "#fdd0a2", "#fdae6b", "#fd8d3c", "#f16913", "#d94801", "#8c2d04",
+    ],
+    &[
+        "#fff5eb", "#fee6ce", "#fdd0a2", "#fdae6b", "#fd8d3c", "#f16913", "#d94801", "#8c2d04",
+    ],
+    &[
+        "#fff5eb", "#fee6ce", "#fdd0a2", "#fdae6b", "#fd8d3c", "#f16913", "#d94801", "#a63603",
+        "#7f2704",
+    ],
+];
+const PURPLES: [&[&str]; 7] = [
+    &["#efedf5", "#bcbddc", "#756bb1"],
+    &["#f2f0f7", "#cbc9e2", "#9e9ac8", "#6a51a3"],
+    &["#f2f0f7", "#cbc9e2", "#9e9ac8", "#756bb1", "#54278f"],
+    &[
+        "#f2f0f7", "#dadaeb", "#bcbddc", "#9e9ac8", "#756bb1", "#54278f",
+    ],
+    &[
+        "#f2f0f7", "#dadaeb", "#bcbddc", "#9e9ac8", "#807dba", "#6a51a3", "#4a1486",
+    ],
+    &[
+        "#fcfbfd", "#efedf5", "#dadaeb", "#bcbddc", "#9e9ac8", "#807dba", "#6a51a3", "#4a1486",
+    ],
+    &[
+        "#fcfbfd", "#efedf5", "#dadaeb", "#bcbddc", "#9e9ac8", "#807dba", "#6a51a3", "#54278f",
+        "#3f007d",
+    ],
+];
+const REDS: [&[&str]; 7] = [
+    &["#fee0d2", "#fc9272", "#de2d26"],
+    &["#fee5d9", "#fcae91", "#fb6a4a", "#cb181d"],
+    &["#fee5d9", "#fcae91", "#fb6a4a", "#de2d26", "#a50f15"],
+    &[
+        "#fee5d9", "#fcbba1", "#fc9272", "#fb6a4a", "#de2d26", "#a50f15",
+    ],
+    &[
+        "#fee5d9", "#fcbba1", "#fc9272", "#fb6a4a", "#ef3b2c", "#cb181d", "#99000d",
+    ],
+    &[
+        "#fff5f0", "#fee0d2", "#fcbba1", "#fc9272", "#fb6a4a", "#ef3b2c", "#cb181d", "#99000d",
+    ],
+    &[
+        "#fff5f0", "#fee0d2", "#fcbba1", "#fc9272", "#fb6a4a", "#ef3b2c", "#cb181d", "#a50f15",
+        "#67000d",
+    ],
+];
+const YLGNBU: [&[&str]; 7] = [
+    &["#edf8b1", "#7fcdbb", "#2c7fb8"],
+    &["#ffffcc", "#a1dab4", "#41b6c4", "#225ea8"],
+    &["#ffffcc", "#a1dab4", "#41b6c4", "#2c7fb8", "#253494"],
+    &[
+        "#ffffcc", "#c7e9b4", "#7fcdbb", "#41b6c4", "#2c7fb8", "#253494",
+    ],
+    &[
+        "#ffffcc", "#c7e9b4", "#7fcdbb", "#41b6c4", "#1d91c0", "#225ea8", "#0c2c84",
+    ],
+    &[
+        "#ffffd9", "#edf8b1", "#c7e9b4", "#7fcdbb", "#41b6c4", "#1d91c0", "#225ea8", "#0c2c84",
+    ],
+    &[
+        "#ffffd9", "#edf8b1", "#c7e9b4", "#7fcdbb", "#41b6c4", "#1d91c0", "#225ea8", "#253494",
+        "#081d58",
+    ],
+];
+const YLORRD: [&[&str]; 7] = [
+    &["#ffeda0", "#feb24c", "#f03b20"],
+    &["#ffffb2", "#fecc5c", "#fd8d3c", "#e31a1c"],
+    &["#ffffb2", "#fecc5c", "#fd8d3c", "#f03b20", "#bd0026"],
+    &[
+        "#ffffb2", "#fed976", "#feb24c", "#fd8d3c", "#f03b20", "#bd0026",
+    ],
+    &[
+        "#ffffb2", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#b10026",
+    ],
+    &[
+        "#ffffcc", "#ffeda0", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#b10026",
+    ],
+    &[
+        "#ffffcc", "#ffeda0", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#bd0026",
+        "#800026",
+    ],
+];
+const RDBU: [&[&str]; 9] = [
+    &["#ef8a62", "#f7f7f7", "#67a9cf"],
+    &["#ca0020", "#f4a582", "#92c5de", "#0571b0"],
+    &["#ca0020", "#f4a582", "#f7f7f7", "#92c5de", "#0571b0"],
+    &[
+        "#b2182b", "#ef8a62", "#fddbc7", "#d1e5f0", "#67a9cf", "#2166ac",
+    ],
+    &[
+        "#b2182b", "#ef8a62", "#fddbc7", "#f7f7f7", "#d1e5f0", "#67a9cf", "#2166ac",
+    ],
+    &[
+        "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#d1e5f0", "#92c5de", "#4393c3", "#2166ac",
+    ],
+    &[
+        "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#f7f7f7", "#d1e5f0", "#92c5de", "#4393c3",
+        "#2166ac",
+    ],
+    &[
+        "#67001f", "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#d1e5f0", "#92c5de", "#4393c3",
+        "#2166ac", "#053061",
+    ],
+    &[
+        "#67001f", "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#f7f7f7", "#d1e5f0", "#92c5de",
+        "#4393c3", "#2166ac", "#053061",
+    ],
+];
+const RDYLBU: [&[&str]; 9] = [
+    &["#fc8d59", "#ffffbf", "#91bfdb"],
+    &["#d7191c", "#fdae61", "#abd9e9", "#2c7bb6"],
+    &["#d7191c", "#fdae61", "#ffffbf", "#abd9e9", "#2c7bb6"],
+    &[
+        "#d73027", "#fc8d59", "#fee090", "#e0f3f8", "#91bfdb", "#4575b4",
+    ],
+    &[
+        "#d73027", "#fc8d59", "#fee090", "#ffffbf", "#e0f3f8", "#91bfdb", "#4575b4",
+    ],
+    &[
+        "#d73027", "#f46d43", "#fdae61", "#fee090", "#e0f3f8", "#abd9e9", "#74add1", "#4575b4",
+    ],
+    &[
+        "#d73027", "#f46d43", "#fdae61", "#fee090", "#ffffbf", "#e0f3f8", "#abd9e9", "#74add1",
+        "#4575b4",
+    ],
+    &[
+        "#a50026", "#d73027", "#f46d43", "#fdae61", "#fee090", "#e0f3f8", "#abd9e9", "#74add1",
+        "#4575b4", "#313695",
+    ],
+    &[
+        "#a50026", "#d73027", "#f46d43", "#fdae61", "#fee090", "#ffffbf", "#e0f3f8", "#abd9e9",
+        "#74add1", "#4575b4", "#313695",
+    ],
+];
+
+/// The data for a single palette.
+enum Scheme {
+    /// A qualitative palette's colors, for the largest number of classes.
+    Qualitative(&'static [&'static str]),
+    /// A sequential or diverging palette's colors for each number of classes, starting with 3.
+    Classed(SchemeType, &'static [&'static [&'static str]]),
+}
+
+fn scheme(name: &str) -> Option<Scheme> {
+    let scheme = match name.to_lowercase().as_str() {
+        "accent" => Scheme::Qualitative(&ACCENT),
+        "dark2" => Scheme::Qualitative(&DARK2),
+        "paired" => Scheme::Qualitative(&PAIRED),
+        "pastel1" => Scheme::Qualitative(&PASTEL1),
+        "pastel2" => Scheme::Qualitative(&PASTEL2),
+        "set1" => Scheme::Qualitative(&SET1),
+        "set2" => Scheme::Qualitative(&SET2),
+        "set3" => Scheme::Qualitative(&SET3),
+        "blues" => Scheme::Classed(SchemeType::Sequential, &BLUES),
+        "greens" => Scheme::Classed(SchemeType::Sequential, &GREENS),
+        "greys" => Scheme::Classed(SchemeType::Sequential, &GREYS),
+        "oranges" => Scheme::Classed(SchemeType::Sequential, &ORANGES),
+        "purples" => Scheme::Classed(SchemeType::Sequential, &PURPLES),
+        "reds" => Scheme::Classed(SchemeType::Sequential, &REDS),
+        "ylgnbu" => Scheme::Classed(SchemeType::Sequential, &YLGNBU),
+        "ylorrd" => Scheme::Classed(SchemeType::Sequential, &YLORRD),
+        "rdbu" => Scheme::Classed(SchemeType::Diverging, &RDBU),
+        "rdylbu" => Scheme::Classed(SchemeType::Diverging, &RDYLBU),
+        _ => return None,
+    };
+    Some(scheme)
+}
+
+/// Gets the names of every available palette, in the capitalization ColorBrewer uses.
+pub fn scheme_names() -> Vec<&'static str> {
+    vec![
+        "Accent", "Dark2", "Paired", "Pastel1", "Pastel2", "Set1", "Set2", "Set3", "Blues",
+        "Greens", "Greys", "Oranges", "Purples", "Reds", "YlGnBu", "YlOrRd", "RdBu", "RdYlBu",
+    ]
+}
+
+/// Gets the kind of the palette with the given name, or `None` if there's no such palette.
+/// # Example
+///
+/// ```
+/// # use scarlet::colorbrewer::{scheme_type, SchemeType};
+/// assert_eq!(scheme_type("Set1"), Some(SchemeType::Qualitative));
+/// assert_eq!(scheme_type("rdbu"), Some(SchemeType::Diverging));
+/// assert_eq!(scheme_type("Rainbow"), None);
+/// ```
+pub fn scheme_type(name: &str) -> Option<SchemeType> {
+    scheme(name).map(|scheme| match scheme {
+        Scheme::Qualitative(_) => SchemeType::Qualitative,
+        Scheme::Classed(scheme_type, _) => scheme_type,
+    })
+}
+
+/// Gets the colors of the named palette for the given number of classes, or `None` if there's no
+/// palette with that name or it isn't defined for that many classes. Every palette has at least 3
+/// classes; sequential palettes go up to 9, diverging palettes up to 11, and qualitative palettes
+/// vary from 8 to 12.
+/// # Example
+///
+/// ```
+/// # use scarlet::colorbrewer::palette;
+/// let blues = palette("Blues", 3).unwrap();
+/// let hexes: Vec<String> = blues.iter().map(|c| c.to_string()).collect();
+/// assert_eq!(hexes, vec!["#DEEBF7", "#9ECAE1", "#3182BD"]);
+/// assert!(palette("Blues", 10).is_none());
+/// ```
+pub fn palette(name: &str, classes: usize) -> Option<Vec<RGBColor>> {
+    if classes < 3 {
+        return None;
+    }
+    let hexes: &[&str] = match scheme(name)? {
+        Scheme::Qualitative(colors) => colors.get(..classes)?,
+        Scheme::Classed(_, lists) => lists.get(classes - 3)?,
+    };
+    Some(
+        hexes
+            .iter()
+            .map(|hex| RGBColor::from_hex_code(hex).expect("Palette data is valid"))
+            .collect(),
+    )
+}
+
+/// Gets the named sequential or diverging palette for the given number of classes as a colormap,
+/// interpolating between its colors. Returns `None` if there's no such palette, or if it's
+/// qualitative: interpolating between unrelated categories doesn't make sense.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colorbrewer::colormap;
+/// # use scarlet::colormap::ColorMap;
+/// let cmap = colormap("RdBu", 11).unwrap();
+/// let middle: RGBColor = cmap.transform_single(0.5);
+/// assert_eq!(middle.to_string(), "#F7F7F7");
+/// assert!(colormap("Set1", 5).is_none());
+/// ```
+pub fn colormap(name: &str, classes: usize) -> Option<ListedColorMap> {
+    match scheme(name)? {
+        Scheme::Qualitative(_) => None,
+        Scheme::Classed(..) => {
+            let colors = palette(name, classes)?;
+            Some(ListedColorMap::new(
+                colors.into_iter().map(|c| [c.r, c.g, c.b]),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::Color;
+
+    #[test]
+    fn test_published_values() {
+        let hexes = |name, classes| -> Vec<String> {
+            palette(name, classes)
+                .unwrap()
+                .iter()
+                .map(|c| c.to_string().to_lowercase())
+                .collect()
+        };
+        assert_eq!(hexes("Blues", 3), vec!["#deebf7", "#9ecae1", "#3182bd"]);
+        assert_eq!(
+            hexes("RdYlBu", 5),
+            vec!["#d7191c", "#fdae61", "#ffffbf", "#abd9e9", "#2c7bb6"]
+        );
+        assert_eq!(hexes("set1", 3), vec!["#e41a1c", "#377eb8", "#4daf4a"]);
+        assert_eq!(hexes("Dark2", 8)[7], "#666666");
+    }
+
+    #[test]
+    fn test_class_counts() {
+        for name in scheme_names() {
+            let (min, max) = match scheme_type(name).unwrap() {
+                SchemeType::Sequential => (3, 9),
+                SchemeType::Diverging => (3, 11),
+                SchemeType::Qualitative => (3, 8),
+            };
+            for classes in min..=max {
+                assert_eq!(palette(name, classes).unwrap().len(), classes);
+            }
+            assert!(palette(name, 2).is_none());
+            assert!(palette(name, 13).is_none());
+        }
+        assert!(palette("Viridis", 5).is_none());
+    }
+
+    #[test]
+    fn test_sequential_order() {
+        // every sequential palette runs from light to dark
+        for name in scheme_names() {
+            if scheme_type(name) != Some(SchemeType::Sequential) {
+                continue;
+            }
+            for classes in 3..=9 {
+                let colors = palette(name, classes).unwrap();
+                for pair in colors.windows(2) {
+                    assert!(pair[0].lightness() > pair[1].lightness());
+                }
+            }
+        }
+    }
+}
@@ -5,10 +5,55 @@
 
 use color::{Color, RGBColor};
 use colorpoint::ColorPoint;
+use colors::CIELCHColor;
 use coord::Coord;
+use csv;
 use matplotlib_cmaps;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
 use std::iter::Iterator;
 
+/// An error that results from an invalid attempt to load a colormap from external data.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum CmapError {
+    /// This indicates that the underlying data could not be read as CSV at all, for example because
+    /// of an I/O error or invalid UTF-8.
+    InvalidCsv,
+    /// This indicates that a row did not have exactly three columns, one for each of R, G, and B.
+    WrongNumberOfColumns,
+    /// This indicates that a value in a row could not be parsed as a number, or was negative or
+    /// larger than 255.
+    InvalidNumber,
+    /// This indicates that there were no colors in the given data.
+    Empty,
+}
+
+impl fmt::Display for CmapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Colormap loading error")
+    }
+}
+
+impl Error for CmapError {
+    fn description(&self) -> &str {
+        match *self {
+            CmapError::InvalidCsv => "Invalid CSV data",
+            CmapError::WrongNumberOfColumns => "Row does not have exactly three columns",
+            CmapError::InvalidNumber => "Invalid or out-of-range RGB value",
+            CmapError::Empty => "No colors given",
+        }
+    }
+}
+
+impl From<csv::Error> for CmapError {
+    fn from(_err: csv::Error) -> CmapError {
+        CmapError::InvalidCsv
+    }
+}
+
 /// A trait that models a colormap, a continuous mapping of the numbers between 0 and 1 to
 /// colors. Any color output format is supported, but it must be consistent.
 pub trait ColorMap<T: Color + Sized> {
@@ -24,6 +69,118 @@ pub trait ColorMap<T: Color + Sized> {
             .map(|x| self.transform_single(x))
             .collect()
     }
+    /// Lazily maps each number from the given iterator to a `Color`, as it's needed, instead of
+    /// collecting them all first like [`transform`](#method.transform). This means data can be
+    /// colormapped as it streams in, even if it never ends. Because the colors are produced whenever
+    /// the caller asks for them, this should only be used when `transform_single` gives the same
+    /// output for the same input no matter when it's called, as every colormap in Scarlet does.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let cmap = ListedColorMap::viridis();
+    /// // an endless sawtooth signal: only the colors that are used get computed
+    /// let signal = (0..).map(|i| f64::from(i % 10) / 9.);
+    /// let colors: Vec<RGBColor> = cmap.iter_transform(signal).skip(9).take(2).collect();
+    /// assert_eq!(colors[0].to_string(), "#FDE725");
+    /// assert_eq!(colors[1].to_string(), "#440154");
+    /// ```
+    fn iter_transform<I: Iterator<Item = f64>>(&self, it: I) -> impl Iterator<Item = T> {
+        it.map(move |x| self.transform_single(x))
+    }
+    /// Maps a given collection of numbers between 0 and 1 to `Color`s like
+    /// [`transform`](#method.transform), but spreads the work across threads using Rayon, returning
+    /// the colors in the same order as the inputs. This is meant for large datasets, like every pixel
+    /// of an image, and only helps with more than one core: on a single core it runs about as fast
+    /// as `transform`. The `colormap_par` benchmark compares the two. Requires the `rayon`
+    /// feature.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// # #[cfg(feature = "rayon")]
+    /// # {
+    /// let cmap = ListedColorMap::viridis();
+    /// let data: Vec<f64> = (0..10_000).map(|i| f64::from(i) / 9999.).collect();
+    /// let colors: Vec<RGBColor> = cmap.transform_par(data.clone());
+    /// assert_eq!(colors, cmap.transform(data));
+    /// # }
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn transform_par<U: IntoParallelIterator<Item = f64>>(&self, inputs: U) -> Vec<T>
+    where
+        Self: Sync,
+        T: Send,
+    {
+        inputs
+            .into_par_iter()
+            .map(|x| self.transform_single(x))
+            .collect()
+    }
+    /// Samples `n` evenly-spaced colors across the whole colormap, from 0 to 1 inclusive: the usual
+    /// way to turn a continuous colormap into a discrete palette. A single sample is taken from the
+    /// middle of the map, at 0.5, and no samples give an empty `Vec`.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let black = RGBColor{r: 0., g: 0., b: 0.};
+    /// let white = RGBColor{r: 1., g: 1., b: 1.};
+    /// let palette = GradientColorMap::new_linear(black, white).sample(3);
+    /// let hexes: Vec<String> = palette.iter().map(|c| c.to_string()).collect();
+    /// assert_eq!(hexes, vec!["#000000", "#808080", "#FFFFFF"]);
+    /// ```
+    fn sample(&self, n: usize) -> Vec<T> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![self.transform_single(0.5)],
+            _ => self.transform((0..n).map(|i| i as f64 / (n - 1) as f64)),
+        }
+    }
+    /// Samples this colormap at `samples` evenly-spaced points from 0 to 1, including both ends, and
+    /// returns the smallest and largest CIELAB [`lightness`](../color/trait.Color.html#method.lightness)
+    /// among them as `(min, max)`. For scientific data, changes in lightness are read as changes in
+    /// the data, so a colormap meant to show only hue should have a small range. At least two
+    /// points, the ends, are always sampled.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// // viridis climbs steadily from dark to light
+    /// let (min, max): (f64, f64) = ColorMap::<RGBColor>::luminance_range(&ListedColorMap::viridis(), 100);
+    /// assert!(min < 20. && max > 90.);
+    /// ```
+    fn luminance_range(&self, samples: usize) -> (f64, f64) {
+        self.sample(samples.max(2))
+            .iter()
+            .map(|color| color.lightness())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), l| {
+                (min.min(l), max.max(l))
+            })
+    }
+    /// Returns `true` if this colormap is isoluminant: if its
+    /// [`luminance_range`](#method.luminance_range), sampled at `samples` points, spans less than
+    /// one unit of CIELAB lightness, about the smallest difference that can be seen. Isoluminant
+    /// colormaps encode data purely in hue and chroma, so they don't suggest structure that isn't
+    /// in the data through changes in lightness.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// # use scarlet::colors::CIELABColor;
+    /// let red = CIELABColor{l: 60., a: 60., b: 30.};
+    /// let blue = CIELABColor{l: 60., a: 10., b: -50.};
+    /// assert!(GradientColorMap::new_linear(red, blue).is_isoluminant(50));
+    /// ```
+    fn is_isoluminant(&self, samples: usize) -> bool {
+        let (min, max) = self.luminance_range(samples);
+        max - min < 1.0
+    }
 }
 
 /// A struct that describes different transformations of the numbers between 0 and 1 to themselves,
@@ -185,6 +342,56 @@ impl ListedColorMap {
             vals: vals.collect(),
         }
     }
+    /// Initializes a ListedColorMap from CSV data with one color per row, given as three columns R,
+    /// G, and B and no header row. This is the format many published scientific colormaps are
+    /// distributed in. Values can either be floats from 0 to 1 or numbers from 0 to 255: if any value
+    /// in the data is larger than 1, the whole table is assumed to be on the 0-255 scale.
+    /// # Errors
+    /// Returns a [`CmapError`] if the data isn't valid CSV, if any row doesn't have exactly three
+    /// numbers between 0 and 255, or if there are no rows at all.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let data = "0, 0, 0\n128, 0, 255\n255, 255, 255\n";
+    /// let cmap = ListedColorMap::from_csv_reader(data.as_bytes()).unwrap();
+    /// let mid: RGBColor = cmap.transform_single(0.5);
+    /// assert_eq!(mid.to_string(), "#8000FF");
+    /// ```
+    pub fn from_csv_reader<R: Read>(reader: R) -> Result<ListedColorMap, CmapError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let mut vals = vec![];
+        for result in csv_reader.records() {
+            let record = result?;
+            if record.len() != 3 {
+                return Err(CmapError::WrongNumberOfColumns);
+            }
+            let mut row = [0.; 3];
+            for (i, field) in record.iter().enumerate() {
+                row[i] = match field.parse::<f64>() {
+                    Ok(val) if (0.0..=255.0).contains(&val) => val,
+                    _ => return Err(CmapError::InvalidNumber),
+                };
+            }
+            vals.push(row);
+        }
+        if vals.is_empty() {
+            return Err(CmapError::Empty);
+        }
+        // auto-detect the 0-255 scale: no valid 0-1 value can be larger than 1
+        if vals.iter().any(|row| row.iter().any(|&val| val > 1.0)) {
+            for row in vals.iter_mut() {
+                for val in row.iter_mut() {
+                    *val /= 255.0;
+                }
+            }
+        }
+        Ok(ListedColorMap { vals })
+    }
     /// Initializes a viridis colormap, a pleasing blue-green-yellow colormap that is perceptually
     /// uniform with respect to luminance, found in Python's `matplotlib` as the default
     /// colormap.
@@ -271,6 +478,96 @@ impl ListedColorMap {
     }
 }
 
+impl ListedColorMap {
+    /// Reverses this colormap in place, so that what 0 mapped to is now what 1 maps to and vice
+    /// versa.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let mut cmap = ListedColorMap::viridis();
+    /// let end: RGBColor = cmap.transform_single(1.);
+    /// cmap.reverse();
+    /// let start: RGBColor = cmap.transform_single(0.);
+    /// assert_eq!(start, end);
+    /// ```
+    pub fn reverse(&mut self) {
+        self.vals.reverse();
+    }
+    /// Returns a reversed copy of this colormap, leaving this one unchanged. See
+    /// [`reverse`](#method.reverse).
+    pub fn reversed(&self) -> ListedColorMap {
+        let mut reversed = self.clone();
+        reversed.reverse();
+        reversed
+    }
+}
+
+/// A cyclic colormap that sweeps once around the hue circle at a fixed CIELCH lightness and chroma,
+/// for data that wraps around, like angles, phases, or times of day. Since it's cyclic, inputs are
+/// taken modulo 1 instead of being clamped: 0 and 1 give exactly the same color, and 1.25 gives the
+/// same color as 0.25. This is the perceptual analog of the familiar HSV rainbow: every color has
+/// the same lightness, so no part of the cycle looks more important than the rest, and equal steps
+/// of input give equal steps of hue.
+///
+/// Keep in mind that not every lightness and chroma can be shown at every hue: at a lightness of 65,
+/// chroma up to about 39 stays in the sRGB gamut all the way around. The default is that lightness
+/// with a chroma of 35, which leaves some headroom below that limit.
+/// Brighter colors need less chroma, since blue can't be very light, and darker colors need less
+/// too, since yellow can't be very dark.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, HueWheelColorMap};
+/// let wheel = HueWheelColorMap::default();
+/// let start: RGBColor = wheel.transform_single(0.);
+/// let end: RGBColor = wheel.transform_single(1.);
+/// assert_eq!(start, end);
+/// assert!(ColorMap::<RGBColor>::is_isoluminant(&wheel, 50));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HueWheelColorMap {
+    /// The CIELCH lightness of every color in the map, from 0 to 100.
+    pub lightness: f64,
+    /// The CIELCH chroma of every color in the map.
+    pub chroma: f64,
+    /// The hue, in degrees, that 0 maps to. Increasing input increases the hue from here.
+    pub start_hue: f64,
+}
+
+impl HueWheelColorMap {
+    /// Constructs a new [`HueWheelColorMap`] with the given CIELCH lightness and chroma, starting at a
+    /// hue of 0 degrees.
+    pub fn new(lightness: f64, chroma: f64) -> HueWheelColorMap {
+        HueWheelColorMap {
+            lightness,
+            chroma,
+            start_hue: 0.,
+        }
+    }
+}
+
+impl Default for HueWheelColorMap {
+    /// A hue wheel with a lightness of 65 and a chroma of 35, which is within the sRGB gamut at every
+    /// hue.
+    fn default() -> HueWheelColorMap {
+        HueWheelColorMap::new(65., 35.)
+    }
+}
+
+impl<T: Color> ColorMap<T> for HueWheelColorMap {
+    fn transform_single(&self, x: f64) -> T {
+        CIELCHColor {
+            l: self.lightness,
+            c: self.chroma,
+            h: (self.start_hue + 360. * x.rem_euclid(1.)).rem_euclid(360.),
+        }
+        .convert()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -292,6 +589,34 @@ mod tests {
         }
     }
     #[test]
+    fn test_sample() {
+        let viridis = ListedColorMap::viridis();
+        let palette: Vec<RGBColor> = viridis.sample(256);
+        assert_eq!(palette.len(), 256);
+        let start: RGBColor = viridis.transform_single(0.);
+        let end: RGBColor = viridis.transform_single(1.);
+        assert_eq!(palette[0], start);
+        assert_eq!(palette[255], end);
+        let middle: RGBColor = viridis.transform_single(0.5);
+        assert_eq!(ColorMap::<RGBColor>::sample(&viridis, 1), vec![middle]);
+        assert!(ColorMap::<RGBColor>::sample(&viridis, 0).is_empty());
+    }
+    #[test]
+    fn test_luminance_range() {
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let green = RGBColor::from_hex_code("#00FF00").unwrap();
+        let cmap = GradientColorMap::new_linear(red, green);
+        let (min, max) = cmap.luminance_range(100);
+        // pure green is much lighter than pure red, and the olive in the middle darker still
+        assert!(min < red.lightness());
+        assert!((max - green.lightness()).abs() <= 1e-6);
+        assert!(max - min > 30.);
+        assert!(!cmap.is_isoluminant(100));
+        // a constant colormap is trivially isoluminant, even with too few samples
+        let flat = GradientColorMap::new_linear(red, red);
+        assert!(flat.is_isoluminant(0));
+    }
+    #[test]
     fn test_cbrt_gradient() {
         let red = RGBColor::from_hex_code("#CC0000").unwrap();
         let blue = RGBColor::from_hex_code("#0000CC").unwrap();
@@ -321,6 +646,119 @@ mod tests {
             assert_eq!(col.to_string(), strs[i]);
         }
     }
+    #[test]
+    fn test_csv_colormap() {
+        // 0-1 scale
+        let data = "0.0,0.0,1.0\n0.2,0.4,0.6\n1.0,0.0,0.0\n";
+        let cmap = ListedColorMap::from_csv_reader(data.as_bytes()).unwrap();
+        let cols: Vec<RGBColor> = cmap.transform(vec![0., 0.5, 1.]);
+        assert_eq!(cols[0].to_string(), "#0000FF");
+        assert_eq!(cols[1].to_string(), "#336699");
+        assert_eq!(cols[2].to_string(), "#FF0000");
+        // 0-255 scale, with whitespace
+        let data = "0, 0, 255\n 51 ,102,  153\n255,0,0";
+        let cmap2 = ListedColorMap::from_csv_reader(data.as_bytes()).unwrap();
+        assert_eq!(cmap.vals, cmap2.vals);
+        // errors
+        let wrong_cols = "0,0\n1,1\n";
+        let bad_num = "0,0,0\n1,foo,1\n";
+        assert_eq!(
+            ListedColorMap::from_csv_reader(wrong_cols.as_bytes()).unwrap_err(),
+            CmapError::WrongNumberOfColumns
+        );
+        assert_eq!(
+            ListedColorMap::from_csv_reader(bad_num.as_bytes()).unwrap_err(),
+            CmapError::InvalidNumber
+        );
+        assert_eq!(
+            ListedColorMap::from_csv_reader("".as_bytes()).unwrap_err(),
+            CmapError::Empty
+        );
+    }
+    #[test]
+    fn test_reverse() {
+        let viridis = ListedColorMap::viridis();
+        let reversed = viridis.reversed();
+        for &(x, y) in [(0., 1.), (1., 0.), (0.3, 0.7), (0.5, 0.5)].iter() {
+            let a: RGBColor = viridis.transform_single(x);
+            let b: RGBColor = reversed.transform_single(y);
+            assert!(a.distance(&b) <= 1e-10);
+        }
+        let mut twice = reversed.clone();
+        twice.reverse();
+        assert_eq!(twice.vals, viridis.vals);
+    }
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_transform_par() {
+        let cmap = ListedColorMap::magma();
+        let data: Vec<f64> = (0..100_000)
+            .map(|i| (f64::from(i) * 0.618).fract())
+            .collect();
+        let serial: Vec<RGBColor> = cmap.transform(data.clone());
+        let parallel: Vec<RGBColor> = cmap.transform_par(data);
+        assert_eq!(serial, parallel);
+        let gradient = GradientColorMap::new_linear(
+            RGBColor::from_hex_code("#FF0000").unwrap(),
+            RGBColor::from_hex_code("#0000FF").unwrap(),
+        );
+        let data: Vec<f64> = (0..=1000).map(|i| f64::from(i) / 1000.).collect();
+        let serial: Vec<RGBColor> = gradient.transform(data.clone());
+        let parallel: Vec<RGBColor> = gradient.transform_par(data);
+        assert_eq!(serial, parallel);
+    }
+    #[test]
+    fn test_iter_transform() {
+        let cmap = ListedColorMap::plasma();
+        let data: Vec<f64> = (0..500)
+            .map(|i| (f64::from(i) * 0.37).sin().abs())
+            .collect();
+        let eager: Vec<RGBColor> = cmap.transform(data.clone());
+        let lazy: Vec<RGBColor> = cmap.iter_transform(data.into_iter()).collect();
+        assert_eq!(eager, lazy);
+        let gradient = GradientColorMap::new_linear(
+            RGBColor::from_hex_code("#000000").unwrap(),
+            RGBColor::from_hex_code("#FFFFFF").unwrap(),
+        );
+        let mut stream = gradient.iter_transform((0..).map(|i| f64::from(i) / 10.));
+        assert_eq!(stream.nth(5).unwrap().to_string(), "#808080");
+    }
+
+    #[test]
+    fn test_hue_wheel() {
+        let wheel = HueWheelColorMap::default();
+        // the ends meet
+        let start: RGBColor = wheel.transform_single(0.);
+        let end: RGBColor = wheel.transform_single(1.);
+        assert_eq!(start, end);
+        let wrapped: RGBColor = wheel.transform_single(1.25);
+        let quarter: RGBColor = wheel.transform_single(0.25);
+        assert_eq!(wrapped, quarter);
+        // the hue sweeps evenly, and lightness and chroma stay put
+        for i in 0..20 {
+            let x = i as f64 / 20.;
+            let lch: CIELCHColor = wheel.transform_single(x);
+            let expected = 360. * x;
+            let diff = (lch.h - expected).rem_euclid(360.);
+            assert!(diff.min(360. - diff) <= 1e-6);
+            assert!((lch.l - 65.).abs() <= 1e-6);
+            assert!((lch.c - 35.).abs() <= 1e-6);
+            // and every color is displayable
+            let rgb: RGBColor = wheel.transform_single(x);
+            assert!([rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|&c| (-1e-6..=1. + 1e-6).contains(&c)));
+        }
+        // start_hue shifts the whole wheel
+        let shifted = HueWheelColorMap {
+            start_hue: 90.,
+            ..HueWheelColorMap::new(65., 35.)
+        };
+        let a: RGBColor = shifted.transform_single(0.);
+        let b: RGBColor = wheel.transform_single(0.25);
+        assert!(a.distance(&b) <= 1e-6);
+    }
+
     #[test]
     fn test_mpl_colormaps() {
         let viridis = ListedColorMap::viridis();
@@ -5,17 +5,41 @@
 //! [`Color`](color/trait.Color.html) types.
 
 use super::geo::prelude::*;
-use super::geo::{Closest, LineString, Point};
-use color::{Color, XYZColor};
-use colors::cieluvcolor::CIELUVColor;
+use super::geo::{Closest, LineString, Point, Polygon};
+use color::{srgb_decode, srgb_encode, Color, RGBColor, XYZColor};
+use colors::{CAM16UCSColor, CIELABColor, CIELUVColor, IPTColor};
 use coord::Coord;
-use visual_gamut::read_cie_spectral_data;
+use illuminants::Illuminant;
+use observer::CIE_1931_2_DEGREE;
+
+lazy_static! {
+    // The region of chromaticities visible to the human eye: the spectral locus, closed by the line
+    // of purples. This is computed once, because is_imaginary can easily be called for every pixel
+    // of an image.
+    static ref SPECTRAL_LOCUS: Polygon<f64> = {
+        let locus: Vec<(f64, f64)> = CIE_1931_2_DEGREE
+            .iter()
+            .map(|row| {
+                XYZColor {
+                    x: row[1],
+                    y: row[2],
+                    z: row[3],
+                    illuminant: Illuminant::D50,
+                }
+                .chromaticity_uv()
+            })
+            .collect();
+        Polygon::new(LineString::from(locus), vec![])
+    };
+}
 
 /// Some errors that might pop up when dealing with colors as coordinates.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ColorCalcError {
     /// Returned when the number of weights given and the number of colors being averaged differ.
     MismatchedWeights,
+    /// Returned when the weights given sum to zero, so they can't be normalized.
+    ZeroWeightSum,
 }
 
 /// A trait that indicates that the current Color can be embedded in 3D space. This also requires
@@ -52,98 +76,132 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
         Self::from(c1.midpoint(&c2))
     }
 
-    /// Returns the weighted average of a given set of colors. Weights will be normalized so that they
-    /// sum to 1. Each component of the final value will be calculated by summing the components of
-    /// each of the input colors multiplied by their given weight.
+    /// Returns the weighted average of a given set of colors. The colors averaged are `self` followed
+    /// by everything in `others`, so `weights` should have one more element than `others`: the first
+    /// weight is for `self`, the second for `others[0]`, and so on. Weights will be normalized so that
+    /// they sum to 1. Each component of the final value will be calculated by summing the components
+    /// of each of the input colors multiplied by their given weight.
     /// # Errors
     /// Returns `ColorCalcError::MismatchedWeights` if the number of colors (`self` and anything in
-    /// `others`) and the number of weights mismatch.
+    /// `others`) and the number of weights mismatch, and `ColorCalcError::ZeroWeightSum` if the
+    /// weights sum to 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// # use scarlet::colorpoint::ColorCalcError;
+    /// let gray = CIELABColor{l: 50., a: 0., b: 0.};
+    /// let black = CIELABColor{l: 0., a: 0., b: 0.};
+    /// let white = CIELABColor{l: 100., a: 0., b: 0.};
+    /// // one weight each for gray, black, and white
+    /// let avg = gray.weighted_average(vec![black, white], vec![2., 1., 1.]).unwrap();
+    /// assert!((avg.l - 50.).abs() <= 1e-10);
+    /// assert_eq!(
+    ///     gray.weighted_average(vec![black, white], vec![1., 1.]).unwrap_err(),
+    ///     ColorCalcError::MismatchedWeights
+    /// );
+    /// ```
     fn weighted_average(
         self,
         others: Vec<Self>,
         weights: Vec<f64>,
     ) -> Result<Self, ColorCalcError> {
         if others.len() + 1 != weights.len() {
-            Err(ColorCalcError::MismatchedWeights)
-        } else {
-            let c1: Coord = self.into();
-            let norm: f64 = weights.iter().sum();
-            let mut coord = c1 * weights[0] / norm;
-            for i in 1..weights.len() {
-                coord = coord + others[i - 1].into() * weights[i] / norm;
-            }
-            Ok(Self::from(coord))
+            return Err(ColorCalcError::MismatchedWeights);
         }
+        let norm: f64 = weights.iter().sum();
+        if norm == 0.0 {
+            return Err(ColorCalcError::ZeroWeightSum);
+        }
+        let c1: Coord = self.into();
+        let mut coord = c1 * weights[0] / norm;
+        for i in 1..weights.len() {
+            coord = coord + others[i - 1].into() * weights[i] / norm;
+        }
+        Ok(Self::from(coord))
     }
     /// Returns the arithmetic mean of a given set of colors. Equivalent to `weighted_average` in the
     /// case where each weight is the same.
+    #[deprecated(note = "returns a raw Coord: use average_color, which returns Self")]
     fn average(self, others: Vec<Self>) -> Coord {
         let c1: Coord = self.into();
         let other_cs: Vec<Coord> = others.iter().map(|x| (*x).into()).collect();
         c1.average(&other_cs)
     }
+    /// Returns the arithmetic mean of this color and everything in `others`, as a color of the same
+    /// type. Each component is the mean of that component across all of the colors, so this is
+    /// `weighted_average` with equal weights, but it can't fail.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor{r: 1., g: 0., b: 0.};
+    /// let green = RGBColor{r: 0., g: 1., b: 0.};
+    /// let gray = RGBColor{r: 0.5, g: 0.5, b: 0.5};
+    /// let avg = red.average_color(&[green, gray]);
+    /// assert!((avg.r - 0.5).abs() <= 1e-10);
+    /// assert!((avg.b - 0.5 / 3.).abs() <= 1e-10);
+    /// ```
+    fn average_color(self, others: &[Self]) -> Self {
+        let c1: Coord = self.into();
+        let other_cs: Vec<Coord> = others.iter().map(|x| (*x).into()).collect();
+        Self::from(c1.average(&other_cs))
+    }
 
-    /// Returns `true` if the color is outside the range of human vision. Uses the CIE 1931 standard
-    /// observer spectral data.
+    /// Returns `true` if the color is outside the range of human vision: that is, if its
+    /// chromaticity lies outside the region bounded by the spectral locus of the CIE 1931 standard
+    /// observer and the line of purples, or if it has negative luminance. Black is considered real.
+    /// The spectral locus is the same regardless of illuminant, so this uses the color's XYZ
+    /// coordinates under D50, Scarlet's standard internal illuminant.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// // every sRGB color is real
+    /// let teal = RGBColor::from_hex_code("#008080").unwrap();
+    /// assert!(!teal.is_imaginary());
+    /// // but you can't see a color greener than monochromatic green light
+    /// let too_green = CIELABColor{l: 50., a: -250., b: 0.};
+    /// assert!(too_green.is_imaginary());
+    /// ```
     fn is_imaginary(&self) -> bool {
-        let (_wavelengths, xyz_data) = read_cie_spectral_data();
-        // convert to chromaticity coordinates
-        // use the explicit formulae instead of CIELUVColor to reduce rounding errors
-        // we only care about those coordinates
-        let uv_func = |xyz: XYZColor| {
-            let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
-            (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
-        };
-        let self_uv: (f64, f64) = uv_func(self.convert());
-        let uv_data: Vec<(f64, f64)> = xyz_data.into_iter().map(uv_func).collect();
-        let self_point = Point::new(self_uv.0, self_uv.1);
-
-        // this is an annoying algorithm, so I'm using a crate instead
-        let line: LineString<f64> = uv_data.into();
-        line.contains(&self_point)
+        let xyz = self.to_xyz(Illuminant::D50);
+        if xyz.x + 15.0 * xyz.y + 3.0 * xyz.z == 0.0 {
+            // black has no chromaticity, but it's definitely real
+            return false;
+        }
+        let (u, v) = xyz.chromaticity_uv();
+        xyz.y < 0.0 || !SPECTRAL_LOCUS.intersects(&Point::new(u, v))
     }
 
-    /// Returns the closest color that can be seen by the human eye. If the color is not imaginary,
-    /// returns itself.
+    /// Returns the closest color that can be seen by the human eye, keeping the same luminance and
+    /// moving the chromaticity to the nearest point on the edge of the visible region in the CIE 1976
+    /// u'v' chromaticity diagram. If the color is not imaginary, returns itself.
     fn closest_real_color(&self) -> Self {
         // if real color, return itself
         if !self.is_imaginary() {
-            *self
-        } else {
-            let (_wavelengths, xyz_data) = read_cie_spectral_data();
-            // convert to chromaticity coordinates
-            // use the explicit formulae instead of CIELUVColor to reduce rounding errors
-            // we only care about those coordinates
-            let uv_func = |xyz: XYZColor| {
-                let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
-                (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
-            };
-            // we need to keep luminance data to convert back, so we use CIELUV explicitly
-            let mut self_luv: CIELUVColor = self.convert();
-            let self_uv = (self_luv.u, self_luv.v);
-            let uv_data: Vec<(f64, f64)> = xyz_data.into_iter().map(uv_func).collect();
-            let self_point = Point::new(self_uv.0, self_uv.1);
-
-            // this is also an annoying algorithm: just use the crate
-            let line: LineString<f64> = uv_data.into();
-            let closest_point = line.closest_point(&self_point);
-            // convert back into original type
-            match closest_point {
-                Closest::Intersection(p) => {
-                    self_luv.u = p.x();
-                    self_luv.v = p.y();
-                }
-                Closest::SinglePoint(p) => {
-                    self_luv.u = p.x();
-                    self_luv.v = p.y();
-                }
-                Closest::Indeterminate => {
-                    // should never happen
-                    panic!("Indeterminate closest point! Please report this error");
-                }
-            }
-            self_luv.convert()
+            return *self;
         }
+        let xyz = self.to_xyz(Illuminant::D50);
+        let (u, v) = xyz.chromaticity_uv();
+        let closest = match SPECTRAL_LOCUS.exterior().closest_point(&Point::new(u, v)) {
+            Closest::Intersection(p) | Closest::SinglePoint(p) => p,
+            Closest::Indeterminate => {
+                // should never happen
+                panic!("Indeterminate closest point! Please report this error");
+            }
+        };
+        // go back to XYZ with the same luminance
+        let (u, v) = (closest.x(), closest.y());
+        let y = xyz.y.max(0.0);
+        Self::from_xyz(XYZColor {
+            x: y * 9.0 * u / (4.0 * v),
+            y,
+            z: y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v),
+            illuminant: Illuminant::D50,
+        })
     }
 
     /// Returns a Vector of colors that starts with this color, ends with the given other color, and
@@ -158,10 +216,41 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
         grad_scale
     }
 
-    /// Returns a pointer to a function that maps floating-point values from 0 to 1 to colors, such
-    /// that 0 returns `self`, 1 returns `other`, and anything in between returns a mix (calculated
-    /// linearly). Although it is possible to extrapolate outside of the range [0, 1], this is not
-    /// a guarantee and may change without warning. For more fine-grained control of gradients, see
+    /// Like [`gradient_scale`](#method.gradient_scale), but interpolates in the color space `S`
+    /// instead of this one, converting each step back afterwards. Interpolating between two RGB
+    /// colors in RGB gives muddy, uneven midtones: this lets you build the scale in a perceptually
+    /// uniform space like CIELAB while keeping RGB endpoints and output. The first and last colors
+    /// are always exactly `self` and `other`.
+    ///
+    /// # Example
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+    /// let green = RGBColor::from_hex_code("#00FF00").unwrap();
+    /// let scale = red.gradient_scale_in::<CIELABColor>(&green, 3);
+    /// let hexes: Vec<String> = scale.iter().map(|c| c.to_string()).collect();
+    /// assert_eq!(hexes.len(), 5);
+    /// assert_eq!(hexes[0], "#FF0000");
+    /// assert_eq!(hexes[4], "#00FF00");
+    /// ```
+    fn gradient_scale_in<S: ColorPoint>(&self, other: &Self, n: usize) -> Vec<Self> {
+        let start: S = self.convert();
+        let end: S = other.convert();
+        let mut grad_scale: Vec<Self> = start
+            .gradient_scale(&end, n)
+            .iter()
+            .map(|c| c.convert())
+            .collect();
+        grad_scale[0] = *self;
+        grad_scale[n + 1] = *other;
+        grad_scale
+    }
+
+    /// Returns a function that maps floating-point values from 0 to 1 to colors, such that 0
+    /// returns `self`, 1 returns `other`, and anything in between returns a mix (calculated
+    /// linearly). Although it is possible to extrapolate outside of the range [0, 1], this is not a
+    /// guarantee and may change without warning. For more fine-grained control of gradients, see
     /// the [`GradientColorMap`](../colormap/struct.GradientColorMap.html) struct.
     ///
     /// # Examples
@@ -175,17 +264,18 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
     /// let color_at_end = grad(1.).to_string(); // #774BDC
     /// let color_at_third = grad(2./6.).to_string(); // #33479C
     /// ```
-    fn gradient(&self, other: &Self) -> Box<dyn Fn(f64) -> Self> {
+    fn gradient(&self, other: &Self) -> impl Fn(f64) -> Self + use<Self> {
         let c1: Coord = (*self).into();
         let c2: Coord = (*other).into();
-        Box::new(move |x| Self::from(c2.weighted_midpoint(&c1, x)))
+        move |x| Self::from(c2.weighted_midpoint(&c1, x))
     }
 
-    /// Returns a pointer to a function that maps floating-point values from 0 to 1 to colors, such
-    /// that 0 returns `self`, 1 returns `other`, and anything in between returns a mix (calculated
-    /// by the cube root of the given value). Although it is possible to extrapolate outside of the
-    /// range [0, 1], this is not a guarantee and may change without warning. For more fine-grained
-    /// control of gradients, see the [`GradientColorMap`](../colormap/struct.GradientColorMap.html) struct.
+    /// Returns a function that maps floating-point values from 0 to 1 to colors, such that 0
+    /// returns `self`, 1 returns `other`, and anything in between returns a mix (calculated by the
+    /// cube root of the given value). Although it is possible to extrapolate outside of the range
+    /// [0, 1], this is not a guarantee and may change without warning. For more fine-grained
+    /// control of gradients, see the [`GradientColorMap`](../colormap/struct.GradientColorMap.html)
+    /// struct.
     ///
     /// # Examples
     /// ```rust
@@ -198,16 +288,16 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
     /// let color_at_end = grad(1.).to_string(); // #774BDC
     /// let color_at_third = grad(2./6.).to_string(); // #5849BF
     /// ```
-    fn cbrt_gradient(&self, other: &Self) -> Box<dyn Fn(f64) -> Self> {
+    fn cbrt_gradient(&self, other: &Self) -> impl Fn(f64) -> Self + use<Self> {
         let c1: Coord = (*self).into();
         let c2: Coord = (*other).into();
-        Box::new(move |x| Self::from(c2.weighted_midpoint(&c1, x.cbrt())))
+        move |x| Self::from(c2.weighted_midpoint(&c1, x.cbrt()))
     }
 
-    /// Returns a pointer to a function that maps floating-point values from 0 to 1 to colors with
-    /// padding `lower_pad` and `upper_pad` such that an input of 0 returns the gradient at
-    /// `lower_pad`, an input of 1 returns the gradient at `upper_pad`, and values in-between are
-    /// mapped linearly inside that range. For more fine-grained control over gradients, see the
+    /// Returns a function that maps floating-point values from 0 to 1 to colors with padding
+    /// `lower_pad` and `upper_pad` such that an input of 0 returns the gradient at `lower_pad`, an
+    /// input of 1 returns the gradient at `upper_pad`, and values in-between are mapped linearly
+    /// inside that range. For more fine-grained control over gradients, see the
     /// [`GradientColorMap`](../colormap/struct.GradientColorMap.html) struct.
     ///
     /// # Examples
@@ -229,11 +319,11 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
         other: &Self,
         lower_pad: f64,
         upper_pad: f64,
-    ) -> Box<dyn Fn(f64) -> Self> {
+    ) -> impl Fn(f64) -> Self + use<Self> {
         let c1: Coord = (*self).into();
         let c2: Coord = (*other).into();
         let length = upper_pad - lower_pad;
-        Box::new(move |x| Self::from(c2.weighted_midpoint(&c1, length * x + lower_pad)))
+        move |x| Self::from(c2.weighted_midpoint(&c1, length * x + lower_pad))
     }
 }
 
@@ -241,12 +331,231 @@ impl<T: Color + Into<Coord> + From<Coord> + Copy + Clone> ColorPoint for T {
     // nothing to do
 }
 
+/// A trait for colors that can be mixed with other colors of the same type, like paints on a
+/// palette. Every [`ColorPoint`](trait.ColorPoint.html) mixes along the straight line between the
+/// two colors in its own space, and [`XYZColor`](../color/struct.XYZColor.html) mixes like light,
+/// taking care of differing illuminants.
+pub trait Mix: Sized {
+    /// Mixes `weight` of `other` into this color: a weight of 0 gives this color, a weight of 1
+    /// gives `other`, and 0.3 gives a mix of 70% of this color and 30% of `other`. Weights outside
+    /// of that range extrapolate past either end, which may not make sense for every space.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let black = CIELABColor{l: 0., a: 0., b: 0.};
+    /// let white = CIELABColor{l: 100., a: 0., b: 0.};
+    /// assert!((black.mix_weighted(white, 0.3).l - 30.).abs() <= 1e-10);
+    /// ```
+    fn mix_weighted(self, other: Self, weight: f64) -> Self;
+
+    /// Mixes this color with `other` in equal amounts: the same as
+    /// [`mix_weighted`](#tymethod.mix_weighted) with a weight of 0.5.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor{r: 1., g: 0., b: 0.};
+    /// let blue = RGBColor{r: 0., g: 0., b: 1.};
+    /// assert_eq!(red.mix(blue).to_string(), "#800080");
+    /// ```
+    fn mix(self, other: Self) -> Self {
+        self.mix_weighted(other, 0.5)
+    }
+}
+
+impl<T: ColorPoint> Mix for T {
+    fn mix_weighted(self, other: T, weight: f64) -> T {
+        // weighted_midpoint weights its first point, so swap the order
+        other.weighted_midpoint(self, weight)
+    }
+}
+
+impl Mix for XYZColor {
+    /// Mixes like light, so the result is the same no matter which illuminant the colors are given
+    /// in. `other` is adapted to this color's illuminant before mixing, and the result is in this
+    /// color's illuminant.
+    fn mix_weighted(self, other: XYZColor, weight: f64) -> XYZColor {
+        let other_c = other.color_adapt(self.illuminant);
+        XYZColor {
+            x: self.x + weight * (other_c.x - self.x),
+            y: self.y + weight * (other_c.y - self.y),
+            z: self.z + weight * (other_c.z - self.z),
+            illuminant: self.illuminant,
+        }
+    }
+}
+
+/// Interpolates between two hues in degrees along the shorter way around the color wheel: a weight
+/// of 0 gives `from`, a weight of 1 gives `to`, and the result is always between 0 and 360. Treating
+/// hues as plain numbers goes the long way whenever the two are on opposite sides of 0: halfway
+/// between 350 and 10 degrees is 0, not 180. Hues exactly opposite each other have two equally
+/// short arcs, and this takes the one with increasing hue.
+/// # Example
+///
+/// ```
+/// # use scarlet::colorpoint::lerp_hue;
+/// assert!(lerp_hue(350., 10., 0.5).abs() <= 1e-10);
+/// assert!((lerp_hue(10., 350., 0.25) - 5.).abs() <= 1e-10);
+/// assert!((lerp_hue(90., 180., 0.5) - 135.).abs() <= 1e-10);
+/// ```
+pub fn lerp_hue(from: f64, to: f64, weight: f64) -> f64 {
+    let mut diff = (to - from).rem_euclid(360.0);
+    if diff > 180.0 {
+        diff -= 360.0;
+    }
+    let hue = (from + weight * diff).rem_euclid(360.0);
+    // rem_euclid can round tiny negative values up to exactly 360
+    if hue == 360.0 {
+        0.0
+    } else {
+        hue
+    }
+}
+
+/// A trait for mixing colors in cylindrical spaces, like [`HSLColor`](../colors/hslcolor/struct.HSLColor.html)
+/// or [`CIELCHColor`](../colors/cielchcolor/struct.CIELCHColor.html), taking into account that hue
+/// is an angle. [`Mix`](trait.Mix.html) treats hue like any other coordinate, so mixing a hue of 350
+/// degrees with one of 10 degrees gives 180 degrees: the two reds mix to cyan. This interpolates
+/// hue along the shorter arc with [`lerp_hue`](fn.lerp_hue.html) and the other coordinates in a
+/// straight line, which is what gradients across the red end of the wheel need. Keep in mind that
+/// the hue of a gray is meaningless, so mixing with a gray still sweeps through hues on the way.
+pub trait HueMix: Sized {
+    /// Mixes `weight` of `other` into this color like
+    /// [`Mix::mix_weighted`](trait.Mix.html#tymethod.mix_weighted), but with hue going the short way
+    /// around the color wheel.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::HSLColor;
+    /// let crimson = HSLColor{h: 350., s: 1., l: 0.5};
+    /// let vermilion = HSLColor{h: 10., s: 1., l: 0.5};
+    /// // a plain mix goes the long way around, through cyan
+    /// assert_eq!(crimson.mix(vermilion).h, 180.);
+    /// // but this stays red
+    /// assert!(crimson.mix_hue_shortest(vermilion, 0.5).h.abs() <= 1e-10);
+    /// ```
+    fn mix_hue_shortest(self, other: Self, weight: f64) -> Self;
+}
+
+/// The smallest reflectance used in [`PigmentMix`](trait.PigmentMix.html): a perfectly black
+/// pigment would have infinite absorption and swamp any mix it's in.
+const MIN_REFLECTANCE: f64 = 1e-3;
+
+/// A trait for mixing colors like paints instead of like light. Mixing yellow and blue light, or
+/// averaging them in a color space, gives a gray or a dull olive; mixing yellow and blue paint gives
+/// green, because each pigment absorbs some of the light the other reflects. This approximates that with the
+/// single-constant Kubelka–Munk model: each sRGB channel, in linear light, is treated as the
+/// reflectance `R` of a pigment at one wavelength, which has the absorption-to-scattering ratio
+/// `K/S = (1 - R)² / 2R`. Mixing averages `K/S`, and the mix reflects `1 + K/S - √((K/S)² + 2K/S)`.
+///
+/// Real pigments have reflectance spectra, not three numbers, so this is only an approximation: it
+/// gets the character of paint mixing right, darkening and shifting hue the way artists expect,
+/// but not precise colors. In particular, it works best with paint-like colors, as a channel at 0
+/// absorbs everything in the mix: pure `#FFFF00` and `#0000FF` have no channel in common, and mix
+/// to nearly black.
+pub trait PigmentMix: Color {
+    /// Mixes equal amounts of this color and `other` as if they were paints, returning the result
+    /// in this color's type.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let yellow = RGBColor::from_hex_code("#FFE000").unwrap();
+    /// let blue = RGBColor::from_hex_code("#2050C0").unwrap();
+    /// let green = yellow.pigment_mix(blue);
+    /// assert!(green.hue() > 120. && green.hue() < 180.);
+    /// ```
+    fn pigment_mix(self, other: Self) -> Self {
+        let rgb1: RGBColor = self.convert();
+        let rgb2: RGBColor = other.convert();
+        let k_over_s = |x: f64| {
+            let r = srgb_decode(x).clamp(MIN_REFLECTANCE, 1.0);
+            (1.0 - r).powi(2) / (2.0 * r)
+        };
+        let mix = |x1: f64, x2: f64| {
+            let ks = (k_over_s(x1) + k_over_s(x2)) / 2.0;
+            srgb_encode(1.0 + ks - (ks * ks + 2.0 * ks).sqrt())
+        };
+        RGBColor {
+            r: mix(rgb1.r, rgb2.r),
+            g: mix(rgb1.g, rgb2.g),
+            b: mix(rgb1.b, rgb2.b),
+        }
+        .convert()
+    }
+}
+
+impl<T: Color> PigmentMix for T {}
+
+/// The color spaces that [`mix_any`](fn.mix_any.html) can mix colors in. Mixing is a straight line
+/// between two colors, so which space it happens in changes the colors in between: see each space's
+/// documentation for what that looks like.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MixSpace {
+    /// CIE 1931 XYZ, which mixes like light: this is the physically accurate mix of two lights.
+    XYZ,
+    /// Gamma-encoded sRGB, which is what most software does.
+    RGB,
+    /// CIELAB, for mixes that look perceptually even.
+    CIELAB,
+    /// CIELUV, another perceptually uniform space, similar to CIELAB.
+    CIELUV,
+    /// IPT, which keeps hue especially constant along the way.
+    IPT,
+    /// CAM16-UCS, a modern perceptually uniform space.
+    CAM16UCS,
+}
+
+/// Mixes two colors of any types in the given space: both are converted to that space, the point
+/// `t` of the way from `a` to `b` is found, and the result is returned in XYZ under D50. A `t` of 0
+/// gives `a`, and 1 gives `b`. This is useful when the colors being mixed have different types, or
+/// when the space to mix in should be chosen at runtime. To mix two colors of the same type in their
+/// own space, see [`weighted_midpoint`](trait.ColorPoint.html#method.weighted_midpoint).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colorpoint::{mix_any, MixSpace};
+/// # use scarlet::colors::CIELABColor;
+/// let red = RGBColor::from_hex_code("#FF0000").unwrap();
+/// let gray = CIELABColor{l: 50., a: 0., b: 0.};
+/// let mix = mix_any(&red, &gray, MixSpace::CIELAB, 0.5);
+/// let lab: CIELABColor = mix.convert();
+/// let red_lab: CIELABColor = red.convert();
+/// assert!((lab.l - (red_lab.l + 50.) / 2.).abs() <= 1e-6);
+/// ```
+pub fn mix_any<A: Color, B: Color>(a: &A, b: &B, space: MixSpace, t: f64) -> XYZColor {
+    fn mix_in<S: ColorPoint, A: Color, B: Color>(a: &A, b: &B, t: f64) -> XYZColor {
+        let a_coord: Coord = a.convert::<S>().into();
+        let b_coord: Coord = b.convert::<S>().into();
+        S::from(b_coord.weighted_midpoint(&a_coord, t)).to_xyz(Illuminant::D50)
+    }
+    match space {
+        MixSpace::XYZ => {
+            let a_xyz = a.to_xyz(Illuminant::D50);
+            let b_xyz = b.to_xyz(Illuminant::D50);
+            XYZColor {
+                x: a_xyz.x + t * (b_xyz.x - a_xyz.x),
+                y: a_xyz.y + t * (b_xyz.y - a_xyz.y),
+                z: a_xyz.z + t * (b_xyz.z - a_xyz.z),
+                illuminant: Illuminant::D50,
+            }
+        }
+        MixSpace::RGB => mix_in::<RGBColor, A, B>(a, b, t),
+        MixSpace::CIELAB => mix_in::<CIELABColor, A, B>(a, b, t),
+        MixSpace::CIELUV => mix_in::<CIELUVColor, A, B>(a, b, t),
+        MixSpace::IPT => mix_in::<IPTColor, A, B>(a, b, t),
+        MixSpace::CAM16UCS => mix_in::<CAM16UCSColor, A, B>(a, b, t),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
-    use color::RGBColor;
-    use colors::cielabcolor::CIELABColor;
 
     #[test]
     fn test_cielab_distance() {
@@ -263,6 +572,130 @@ mod tests {
         };
         assert!((lab1.euclidean_distance(lab2) - 132.70150715).abs() <= 1e-7);
     }
+    #[test]
+    fn test_mix_weighted() {
+        let red = CIELABColor {
+            l: 50.,
+            a: 60.,
+            b: 40.,
+        };
+        let blue = CIELABColor {
+            l: 30.,
+            a: 20.,
+            b: -60.,
+        };
+        let mix = red.mix_weighted(blue, 0.25);
+        assert!((mix.l - 45.).abs() <= 1e-10);
+        assert!((mix.a - 50.).abs() <= 1e-10);
+        assert!((mix.b - 15.).abs() <= 1e-10);
+        assert!(red.mix_weighted(blue, 0.).distance(&red) <= 1e-10);
+        assert!(red.mix_weighted(blue, 1.).distance(&blue) <= 1e-10);
+        assert!(red.mix(blue).distance(&red.midpoint(blue)) <= 1e-10);
+    }
+    #[test]
+    fn test_xyz_mix_weighted() {
+        let a = XYZColor {
+            x: 0.2,
+            y: 0.3,
+            z: 0.4,
+            illuminant: Illuminant::D50,
+        };
+        let b = XYZColor {
+            x: 0.6,
+            y: 0.5,
+            z: 0.1,
+            illuminant: Illuminant::D50,
+        };
+        let mix = a.mix_weighted(b, 0.25);
+        assert!((mix.x - 0.3).abs() <= 1e-10);
+        assert!((mix.y - 0.35).abs() <= 1e-10);
+        assert!((mix.z - 0.325).abs() <= 1e-10);
+        // a different illuminant for the second color gives the same mix, in the first's illuminant
+        let b_d65 = b.color_adapt(Illuminant::D65);
+        let mix2 = a.mix_weighted(b_d65, 0.25);
+        assert_eq!(mix2.illuminant, Illuminant::D50);
+        assert!(mix.approx_equal(&mix2));
+        assert!(a.mix(b).approx_equal(&a.mix_weighted(b, 0.5)));
+    }
+    #[test]
+    fn test_average_color() {
+        let colors = [
+            RGBColor {
+                r: 0.9,
+                g: 0.1,
+                b: 0.3,
+            },
+            RGBColor {
+                r: 0.2,
+                g: 0.7,
+                b: 0.4,
+            },
+            RGBColor {
+                r: 0.4,
+                g: 0.4,
+                b: 0.8,
+            },
+        ];
+        let avg = colors[0].average_color(&colors[1..]);
+        assert!((avg.r - 0.5).abs() <= 1e-10);
+        assert!((avg.g - 0.4).abs() <= 1e-10);
+        assert!((avg.b - 0.5).abs() <= 1e-10);
+        // averaging a single color gives it back
+        assert_eq!(colors[0].average_color(&[]), colors[0]);
+    }
+    #[test]
+    fn test_pigment_mix() {
+        let yellow = RGBColor::from_hex_code("#FFE000").unwrap();
+        let blue = RGBColor::from_hex_code("#2050C0").unwrap();
+        let paint = yellow.pigment_mix(blue);
+        // paint gives green, with real chroma
+        assert!(paint.hue() > 120. && paint.hue() < 180.);
+        assert!(paint.chroma() > 20.);
+        // averaging in sRGB gives a duller olive instead
+        let averaged = yellow.mix(blue);
+        assert!(averaged.hue() < 120.);
+        assert!(averaged.chroma() < paint.chroma());
+        // mixing a paint with itself changes nothing, and white doesn't darken anything
+        assert!(blue.pigment_mix(blue).distance(&blue) <= 1e-6);
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        assert!(white.pigment_mix(white).distance(&white) <= 1e-6);
+        // works in any space
+        let yellow_lab: CIELABColor = yellow.convert();
+        let blue_lab: CIELABColor = blue.convert();
+        assert!(yellow_lab.pigment_mix(blue_lab).distance(&paint) <= 1e-6);
+    }
+    #[test]
+    fn test_weighted_average() {
+        let red = CIELABColor {
+            l: 50.,
+            a: 60.,
+            b: 40.,
+        };
+        let blue = CIELABColor {
+            l: 30.,
+            a: 20.,
+            b: -60.,
+        };
+        let avg = red.weighted_average(vec![blue], vec![3., 1.]).unwrap();
+        assert!((avg.l - 45.).abs() <= 1e-10);
+        assert!((avg.a - 50.).abs() <= 1e-10);
+        assert!((avg.b - 15.).abs() <= 1e-10);
+        // one weight per color, including self
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![1.]).unwrap_err(),
+            ColorCalcError::MismatchedWeights
+        );
+        // weights that can't be normalized are an error, not NaN
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![0., 0.]).unwrap_err(),
+            ColorCalcError::ZeroWeightSum
+        );
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![1., -1.]).unwrap_err(),
+            ColorCalcError::ZeroWeightSum
+        );
+    }
+
     #[test]
     fn test_grad_scale() {
         let start = RGBColor::from_hex_code("#11457c").unwrap();
@@ -277,6 +710,66 @@ mod tests {
             vec!["#11457C", "#22468C", "#33479C", "#4448AC", "#5549BC", "#664ACC", "#774BDC",]
         );
     }
+    #[test]
+    fn test_grad_scale_in() {
+        // the middle of a red to green scale in RGB is a dark, muddy olive; in CIELAB, it keeps
+        // about the lightness of the endpoints
+        let red = RGBColor::from_hex_code("#FF0000").unwrap();
+        let green = RGBColor::from_hex_code("#00FF00").unwrap();
+        let rgb_mid = red.gradient_scale(&green, 1)[1];
+        let lab_scale = red.gradient_scale_in::<CIELABColor>(&green, 1);
+        let lab_mid = lab_scale[1];
+        assert_eq!(rgb_mid.to_string(), "#808000");
+        let avg_lightness = (red.lightness() + green.lightness()) / 2.;
+        assert!((lab_mid.lightness() - avg_lightness).abs() <= 1e-6);
+        assert!(rgb_mid.lightness() < avg_lightness - 10.);
+        // and the steps are perceptually even
+        let lab_scale = red.gradient_scale_in::<CIELABColor>(&green, 4);
+        assert_eq!(lab_scale.len(), 6);
+        assert_eq!(lab_scale[0], red);
+        assert_eq!(lab_scale[5], green);
+        let labs: Vec<CIELABColor> = lab_scale.iter().map(|c| c.convert()).collect();
+        let step = labs[0].euclidean_distance(labs[1]);
+        for pair in labs.windows(2) {
+            assert!((pair[0].euclidean_distance(pair[1]) - step).abs() <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mix_any() {
+        let rgb = RGBColor::from_hex_code("#E07020").unwrap();
+        let lab = CIELABColor {
+            l: 40.,
+            a: -20.,
+            b: -35.,
+        };
+        // mixing in CIELAB is the same as converting both to CIELAB first and mixing there
+        let rgb_lab: CIELABColor = rgb.convert();
+        for &t in [0.0, 0.3, 0.5, 1.0].iter() {
+            let mixed: CIELABColor = mix_any(&rgb, &lab, MixSpace::CIELAB, t).convert();
+            let expected = lab.weighted_midpoint(rgb_lab, t);
+            assert!(mixed.euclidean_distance(expected) <= 1e-6);
+        }
+        // the endpoints are the inputs, in every space
+        for &space in [
+            MixSpace::XYZ,
+            MixSpace::RGB,
+            MixSpace::CIELAB,
+            MixSpace::CIELUV,
+            MixSpace::IPT,
+            MixSpace::CAM16UCS,
+        ]
+        .iter()
+        {
+            assert!(mix_any(&rgb, &lab, space, 0.).distance(&rgb) <= 1e-4);
+            assert!(mix_any(&rgb, &lab, space, 1.).distance(&lab) <= 1e-4);
+        }
+        // but the spaces do give different midpoints
+        let mid_rgb = mix_any(&rgb, &lab, MixSpace::RGB, 0.5);
+        let mid_lab = mix_any(&rgb, &lab, MixSpace::CIELAB, 0.5);
+        assert!(mid_rgb.distance(&mid_lab) >= 1.);
+    }
+
     #[test]
     fn test_grad_func() {
         let start = RGBColor::from_hex_code("#11457c").unwrap();
@@ -311,4 +804,93 @@ mod tests {
         assert_eq!(grad(0.75).to_string(), middle_pad_grad(1.).to_string());
         assert_eq!(grad(0.25).to_string(), middle_pad_grad(0.).to_string());
     }
+
+    #[test]
+    fn test_grad_func_outlives_borrows() {
+        // gradients don't borrow their endpoints, so they can be returned and stored
+        fn boxed_gradient(start: RGBColor, end: RGBColor) -> Box<dyn Fn(f64) -> RGBColor> {
+            Box::new(start.gradient(&end))
+        }
+        fn padded(start: RGBColor, end: RGBColor) -> impl Fn(f64) -> RGBColor {
+            start.padded_gradient(&end, 0.25, 0.75)
+        }
+        let start = RGBColor::from_hex_code("#11457c").unwrap();
+        let end = RGBColor::from_hex_code("#774bdc").unwrap();
+        let gradients: Vec<Box<dyn Fn(f64) -> RGBColor>> = vec![
+            boxed_gradient(start, end),
+            Box::new(start.cbrt_gradient(&end)),
+            Box::new(padded(start, end)),
+        ];
+        assert_eq!(gradients[0](2. / 6.).to_string(), "#33479C");
+        assert_eq!(gradients[1](2. / 6.).to_string(), "#5849BF");
+        assert_eq!(
+            gradients[2](0.).to_string(),
+            start.gradient(&end)(0.25).to_string()
+        );
+    }
+
+    #[test]
+    fn test_is_imaginary_srgb() {
+        // every sRGB color is real, including the corners of the gamut
+        for r in 0..=8 {
+            for g in 0..=8 {
+                for b in 0..=8 {
+                    let color = RGBColor {
+                        r: f64::from(r) / 8.,
+                        g: f64::from(g) / 8.,
+                        b: f64::from(b) / 8.,
+                    };
+                    assert!(!color.is_imaginary(), "{}", color.to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_imaginary_spectral_boundary() {
+        // take monochromatic light at a few wavelengths, and nudge its chromaticity slightly toward
+        // or away from white: this should put it just inside or just outside the visible region
+        let white = XYZColor::white_point(Illuminant::D50);
+        let (white_u, white_v) = white.chromaticity_uv();
+        for row in CIE_1931_2_DEGREE.iter().step_by(10).skip(2) {
+            let (u, v) = XYZColor {
+                x: row[1],
+                y: row[2],
+                z: row[3],
+                illuminant: Illuminant::D50,
+            }
+            .chromaticity_uv();
+            for &(scale, imaginary) in [(0.97, false), (1.03, true)].iter() {
+                let u_s = white_u + (u - white_u) * scale;
+                let v_s = white_v + (v - white_v) * scale;
+                let color = XYZColor {
+                    x: 0.3 * 9.0 * u_s / (4.0 * v_s),
+                    y: 0.3,
+                    z: 0.3 * (12.0 - 3.0 * u_s - 20.0 * v_s) / (4.0 * v_s),
+                    illuminant: Illuminant::D50,
+                };
+                let lab: CIELABColor = color.convert();
+                assert_eq!(lab.is_imaginary(), imaginary, "{} nm", row[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_real_color() {
+        let too_green = CIELUVColor {
+            l: 50.,
+            u: -300.,
+            v: 50.,
+        };
+        assert!(too_green.is_imaginary());
+        let real = too_green.closest_real_color();
+        // same luminance, but now right on the edge of the visible region
+        assert!((real.l - too_green.l).abs() <= 1e-8);
+        let (u, v) = real.to_xyz(Illuminant::D50).chromaticity_uv();
+        let boundary = SPECTRAL_LOCUS.exterior();
+        assert!(boundary.euclidean_distance(&Point::new(u, v)) <= 1e-8);
+        // real colors are unchanged
+        let teal = RGBColor::from_hex_code("#008080").unwrap();
+        assert_eq!(teal.closest_real_color().to_string(), "#008080");
+    }
 }
@@ -9,6 +9,10 @@ use consts::ADOBE_RGB_TRANSFORM_LU as ADOBE_RGB_LU;
 use coord::Coord;
 use illuminants::Illuminant;
 
+/// The exponent of the Adobe RGB (1998) transfer function. The specification gives it exactly as
+/// 2 51/256, not the rounded 2.2 it's often quoted as.
+const ADOBE_RGB_GAMMA: f64 = 563.0 / 256.0;
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 /// A color in the Adobe RGB color space. This is a rarer color space, but one that is still pretty
 /// common, especially in color-managed design work. It can represent more colors than sRGB, which is
@@ -55,19 +59,11 @@ impl Color for AdobeRGBColor {
         // &* needed because lazy_static uses a different type which implements Deref
         let rgb = *ADOBE_RGB * vector![xyz_c.x, xyz_c.y, xyz_c.z];
 
-        // clamp
-        let clamp = |x: f64| {
-            if x > 1.0 {
-                1.0
-            } else if x < 0.0 {
-                0.0
-            } else {
-                x
-            }
-        };
+        // clamp: this also keeps tiny negative values from rounding error out of powf
+        let clamp = |x: f64| x.clamp(0.0, 1.0);
 
         // now we apply gamma transformation
-        let gamma = |x: f64| x.powf(256.0 / 563.0);
+        let gamma = |x: f64| x.powf(ADOBE_RGB_GAMMA.recip());
 
         AdobeRGBColor {
             r: gamma(clamp(rgb[0])),
@@ -78,7 +74,7 @@ impl Color for AdobeRGBColor {
     /// Converts from Adobe RGB to an XYZ color in a given illuminant (via chromatic adaptation).
     fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
         // undo gamma transformation
-        let ungamma = |x: f64| x.powf(563.0 / 256.0);
+        let ungamma = |x: f64| x.powf(ADOBE_RGB_GAMMA);
 
         // more efficient/accurate than using inverses
         let xyz_vec = ADOBE_RGB_LU
@@ -140,6 +136,29 @@ mod tests {
         assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
     }
     #[test]
+    fn test_adobe_rgb_round_trip_sweep() {
+        for i in 0..=10 {
+            for j in 0..=10 {
+                for k in 0..=10 {
+                    let argb = AdobeRGBColor {
+                        r: f64::from(i) / 10.,
+                        g: f64::from(j) / 10.,
+                        b: f64::from(k) / 10.,
+                    };
+                    for illuminant in [Illuminant::D65, Illuminant::D50].iter() {
+                        let argb2 = AdobeRGBColor::from_xyz(argb.to_xyz(*illuminant));
+                        assert!(argb.distance(&argb2) <= TEST_PRECISION);
+                        // the power curve is infinitely steep at 0, so components very close to 0
+                        // magnify floating-point error, but never visibly
+                        assert!((argb.r - argb2.r).abs() <= 1e-6);
+                        assert!((argb.g - argb2.g).abs() <= 1e-6);
+                        assert!((argb.b - argb2.b).abs() <= 1e-6);
+                    }
+                }
+            }
+        }
+    }
+    #[test]
     fn test_adobe_rgb_clamping() {
         let argb = AdobeRGBColor {
             r: 1.1,
@@ -0,0 +1,319 @@
+//! This module implements the CAM16-UCS color space, a uniform color space derived from the CAM16
+//! color appearance model. CAM16 is the successor to CIECAM02: instead of just describing a color,
+//! it models how that color *appears* given a set of viewing conditions, like how bright the
+//! surroundings are and how adapted the viewer is to the light. CAM16-UCS then squeezes the
+//! lightness and colorfulness of CAM16 so that Euclidean distance closely tracks perceived
+//! difference, making it a strong modern alternative to CIELAB (it's the space under Google's HCT,
+//! for example).
+//!
+//! Scarlet uses the standard sRGB viewing conditions: a D65 white point, an adapting luminance of
+//! 64 lux / (5π) ≈ 4 cd/m², a background with 20% luminance, and an average surround. For
+//! reference, see [Li et al.,
+//! 2017](https://onlinelibrary.wiley.com/doi/full/10.1002/col.22131).
+
+use color::{Color, XYZColor};
+use consts::CAM16_TRANSFORM as CAM16;
+use consts::CAM16_TRANSFORM_LU as CAM16_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The parameters of CAM16 that only depend on the viewing conditions, not the color being viewed.
+/// The names match those in the paper.
+struct ViewingConditions {
+    // the degree of adaptation, scaled per cone response
+    d_rgb: [f64; 3],
+    // the luminance-level adaptation factor
+    f_l: f64,
+    // background induction factor
+    n: f64,
+    z: f64,
+    n_bb: f64,
+    // chromatic induction factor and impact of surround, for an average surround
+    n_c: f64,
+    c: f64,
+    // the achromatic response to white
+    a_w: f64,
+}
+
+/// The post-adaptation nonlinear response compression, applied to each adapted cone response.
+fn compress(x: f64, f_l: f64) -> f64 {
+    let t = (f_l * x.abs() / 100.0).powf(0.42);
+    400.0 * x.signum() * t / (t + 27.13) + 0.1
+}
+
+/// The inverse of `compress`.
+fn decompress(x: f64, f_l: f64) -> f64 {
+    let x_s = x - 0.1;
+    x_s.signum() * 100.0 / f_l * (27.13 * x_s.abs() / (400.0 - x_s.abs())).powf(1.0 / 0.42)
+}
+
+impl ViewingConditions {
+    /// The sRGB viewing conditions: see the module-level documentation.
+    fn srgb() -> ViewingConditions {
+        let wp = Illuminant::D65.white_point();
+        // XYZ scaled so Y = 100
+        let rgb_w = *CAM16 * vector![wp[0] * 100.0, wp[1] * 100.0, wp[2] * 100.0];
+        let l_a = 64.0 / std::f64::consts::PI / 5.0;
+        let y_b: f64 = 20.0;
+        // average surround
+        let f = 1.0;
+        let c = 0.69;
+        let n_c = 1.0;
+
+        let d = (f * (1.0 - (1.0 / 3.6) * ((-l_a - 42.0) / 92.0).exp())).clamp(0.0, 1.0);
+        let d_rgb = [
+            d * 100.0 / rgb_w[0] + 1.0 - d,
+            d * 100.0 / rgb_w[1] + 1.0 - d,
+            d * 100.0 / rgb_w[2] + 1.0 - d,
+        ];
+        let k = 1.0 / (5.0 * l_a + 1.0);
+        let f_l =
+            0.2 * k.powi(4) * (5.0 * l_a) + 0.1 * (1.0 - k.powi(4)).powi(2) * (5.0 * l_a).cbrt();
+        let n = y_b / 100.0;
+        let z = 1.48 + n.sqrt();
+        let n_bb = 0.725 * n.powf(-0.2);
+
+        let rgb_aw: Vec<f64> = (0..3).map(|i| compress(d_rgb[i] * rgb_w[i], f_l)).collect();
+        let a_w = (2.0 * rgb_aw[0] + rgb_aw[1] + 0.05 * rgb_aw[2] - 0.305) * n_bb;
+        ViewingConditions {
+            d_rgb,
+            f_l,
+            n,
+            z,
+            n_bb,
+            n_c,
+            c,
+            a_w,
+        }
+    }
+}
+
+lazy_static! {
+    // The viewing conditions only depend on constants, so they're computed once instead of on every
+    // conversion.
+    static ref SRGB_VIEWING_CONDITIONS: ViewingConditions = ViewingConditions::srgb();
+}
+
+/// A color in the CAM16-UCS color space, with the coordinates J', a', and b' of the paper. `j` is a
+/// lightness correlate, and `a` and `b` are Cartesian coordinates of the colorfulness and hue, much
+/// like in CIELAB. This is the space to use when you want Euclidean distance to reflect how
+/// different two colors look: see
+/// [`Color::distance_cam16ucs`](../../color/trait.Color.html#method.distance_cam16ucs).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::CAM16UCSColor;
+/// let white: CAM16UCSColor = RGBColor{r: 1., g: 1., b: 1.}.convert();
+/// // white has the maximum lightness and (essentially) no colorfulness
+/// assert!((white.j - 100.).abs() <= 1e-2);
+/// assert!(white.a.hypot(white.b) <= 3.);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct CAM16UCSColor {
+    /// The lightness correlate J', derived from the CAM16 lightness J. Ranges from 0 for black to
+    /// 100 for white.
+    pub j: f64,
+    /// The first Cartesian coordinate of colorfulness and hue, a'. Positive values are reddish,
+    /// negative values greenish.
+    pub a: f64,
+    /// The second Cartesian coordinate of colorfulness and hue, b'. Positive values are yellowish,
+    /// negative values bluish.
+    pub b: f64,
+}
+
+impl Color for CAM16UCSColor {
+    /// Converts from XYZ to CAM16-UCS. Any illuminant other than D65, the white point of the
+    /// viewing conditions Scarlet uses, is chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> CAM16UCSColor {
+        let vc = &*SRGB_VIEWING_CONDITIONS;
+        let xyz_c = xyz.color_adapt(Illuminant::D65);
+        let rgb = *CAM16 * vector![xyz_c.x * 100.0, xyz_c.y * 100.0, xyz_c.z * 100.0];
+        let rgb_a: Vec<f64> = (0..3)
+            .map(|i| compress(vc.d_rgb[i] * rgb[i], vc.f_l))
+            .collect();
+
+        // opponent color dimensions
+        let a = rgb_a[0] - 12.0 * rgb_a[1] / 11.0 + rgb_a[2] / 11.0;
+        let b = (rgb_a[0] + rgb_a[1] - 2.0 * rgb_a[2]) / 9.0;
+        let h = b.atan2(a);
+        // eccentricity factor
+        let e_t = 0.25 * ((h + 2.0).cos() + 3.8);
+        let big_a = (2.0 * rgb_a[0] + rgb_a[1] + 0.05 * rgb_a[2] - 0.305) * vc.n_bb;
+
+        // lightness, chroma, and colorfulness
+        let j = 100.0 * (big_a / vc.a_w).powf(vc.c * vc.z);
+        let t = (50000.0 / 13.0 * vc.n_c * vc.n_bb * e_t * a.hypot(b))
+            / (rgb_a[0] + rgb_a[1] + 21.0 / 20.0 * rgb_a[2]);
+        let c = t.powf(0.9) * (j / 100.0).sqrt() * (1.64 - 0.29f64.powf(vc.n)).powf(0.73);
+        let m = c * vc.f_l.powf(0.25);
+
+        // now compress into the uniform space
+        let j_prime = 1.7 * j / (1.0 + 0.007 * j);
+        let m_prime = (1.0 + 0.0228 * m).ln() / 0.0228;
+        CAM16UCSColor {
+            j: j_prime,
+            a: m_prime * h.cos(),
+            b: m_prime * h.sin(),
+        }
+    }
+    /// Converts from CAM16-UCS back to XYZ, in D65 and then chromatically adapted to the given
+    /// illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let vc = &*SRGB_VIEWING_CONDITIONS;
+        // undo the uniform space compression
+        let j = self.j / (1.7 - 0.007 * self.j);
+        let m = ((0.0228 * self.a.hypot(self.b)).exp() - 1.0) / 0.0228;
+        let h = self.b.atan2(self.a);
+        let c = m / vc.f_l.powf(0.25);
+
+        // back to the opponent dimensions: this follows the CIECAM02 inverse exactly
+        let t = if j == 0.0 {
+            0.0
+        } else {
+            (c / ((j / 100.0).sqrt() * (1.64 - 0.29f64.powf(vc.n)).powf(0.73))).powf(1.0 / 0.9)
+        };
+        let e_t = 0.25 * ((h + 2.0).cos() + 3.8);
+        let big_a = vc.a_w * (j / 100.0).powf(1.0 / (vc.c * vc.z));
+        let p_2 = big_a / vc.n_bb + 0.305;
+        let p_3 = 21.0 / 20.0;
+        let (sin_h, cos_h) = h.sin_cos();
+        let (a, b) = if t == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let p_1 = 50000.0 / 13.0 * vc.n_c * vc.n_bb * e_t / t;
+            if sin_h.abs() >= cos_h.abs() {
+                let p_4 = p_1 / sin_h;
+                let b = p_2 * (2.0 + p_3) * (460.0 / 1403.0)
+                    / (p_4 + (2.0 + p_3) * (220.0 / 1403.0) * (cos_h / sin_h) - 27.0 / 1403.0
+                        + p_3 * (6300.0 / 1403.0));
+                (b * cos_h / sin_h, b)
+            } else {
+                let p_5 = p_1 / cos_h;
+                let a = p_2 * (2.0 + p_3) * (460.0 / 1403.0)
+                    / (p_5 + (2.0 + p_3) * (220.0 / 1403.0)
+                        - (27.0 / 1403.0 - p_3 * (6300.0 / 1403.0)) * (sin_h / cos_h));
+                (a, a * sin_h / cos_h)
+            }
+        };
+
+        let rgb_a = [
+            (460.0 * p_2 + 451.0 * a + 288.0 * b) / 1403.0,
+            (460.0 * p_2 - 891.0 * a - 261.0 * b) / 1403.0,
+            (460.0 * p_2 - 220.0 * a - 6300.0 * b) / 1403.0,
+        ];
+        let rgb = vector![
+            decompress(rgb_a[0], vc.f_l) / vc.d_rgb[0],
+            decompress(rgb_a[1], vc.f_l) / vc.d_rgb[1],
+            decompress(rgb_a[2], vc.f_l) / vc.d_rgb[2]
+        ];
+        let xyz = CAM16_LU.solve(&rgb).expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0] / 100.0,
+            y: xyz[1] / 100.0,
+            z: xyz[2] / 100.0,
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for CAM16UCSColor {
+    fn from(c: Coord) -> CAM16UCSColor {
+        CAM16UCSColor {
+            j: c.x,
+            a: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<CAM16UCSColor> for Coord {
+    fn from(val: CAM16UCSColor) -> Self {
+        Coord {
+            x: val.j,
+            y: val.a,
+            z: val.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+
+    #[test]
+    fn test_cam16ucs_xyz_conversion() {
+        for illuminant in [Illuminant::D50, Illuminant::D65].iter() {
+            let xyz = XYZColor {
+                x: 0.3,
+                y: 0.53,
+                z: 0.65,
+                illuminant: *illuminant,
+            };
+            let cam: CAM16UCSColor = xyz.convert();
+            let xyz2 = cam.to_xyz(*illuminant);
+            assert!((xyz.x - xyz2.x).abs() <= 1e-10);
+            assert!((xyz.y - xyz2.y).abs() <= 1e-10);
+            assert!((xyz.z - xyz2.z).abs() <= 1e-10);
+        }
+        // black is a special case
+        let black: CAM16UCSColor = RGBColor {
+            r: 0.,
+            g: 0.,
+            b: 0.,
+        }
+        .convert();
+        let black_xyz = black.to_xyz(Illuminant::D65);
+        assert!(black_xyz.y.abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_cam16ucs_hues() {
+        // the primaries should land in the right quadrants of the hue circle
+        let hue = |hex: &str| {
+            let cam: CAM16UCSColor = RGBColor::from_hex_code(hex).unwrap().convert();
+            cam.b.atan2(cam.a).to_degrees().rem_euclid(360.)
+        };
+        assert!(hue("#FF0000") > 0. && hue("#FF0000") < 60.);
+        assert!(hue("#FFFF00") > 60. && hue("#FFFF00") < 120.);
+        assert!(hue("#00FF00") > 120. && hue("#00FF00") < 180.);
+        assert!(hue("#0000FF") > 240. && hue("#0000FF") < 300.);
+    }
+
+    #[test]
+    fn test_cam16ucs_distance_correlation() {
+        // compare the CAM16-UCS distance with CIEDE2000 on a spread of color pairs: they should
+        // broadly agree
+        let hexes = [
+            "#000000", "#FFFFFF", "#FF0000", "#00FF00", "#0000FF", "#123456", "#ABCDEF", "#D00A12",
+            "#40AA50", "#FAFA22", "#808080", "#2266AA", "#FFAAFF", "#466223",
+        ];
+        let colors: Vec<RGBColor> = hexes
+            .iter()
+            .map(|h| RGBColor::from_hex_code(h).unwrap())
+            .collect();
+        let mut de2000 = vec![];
+        let mut de_cam = vec![];
+        for (i, c1) in colors.iter().enumerate() {
+            for c2 in colors.iter().skip(i + 1) {
+                de2000.push(c1.distance(c2));
+                de_cam.push(c1.distance_cam16ucs(c2));
+            }
+        }
+        let n = de2000.len() as f64;
+        let mean1 = de2000.iter().sum::<f64>() / n;
+        let mean2 = de_cam.iter().sum::<f64>() / n;
+        let cov: f64 = de2000
+            .iter()
+            .zip(de_cam.iter())
+            .map(|(x, y)| (x - mean1) * (y - mean2))
+            .sum();
+        let var1: f64 = de2000.iter().map(|x| (x - mean1).powi(2)).sum();
+        let var2: f64 = de_cam.iter().map(|y| (y - mean2).powi(2)).sum();
+        let corr = cov / (var1 * var2).sqrt();
+        assert!(corr >= 0.9);
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::cielabcolor::CIELABColor;
 use color::{Color, XYZColor};
+use colorpoint::{lerp_hue, HueMix};
 use coord::Coord;
 use illuminants::Illuminant;
 
@@ -82,6 +83,17 @@ impl Color for CIELCHColor {
     }
 }
 
+impl HueMix for CIELCHColor {
+    /// Mixes luminance and chroma in a straight line, and hue along the shorter arc.
+    fn mix_hue_shortest(self, other: CIELCHColor, weight: f64) -> CIELCHColor {
+        CIELCHColor {
+            l: self.l + weight * (other.l - self.l),
+            c: self.c + weight * (other.c - self.c),
+            h: lerp_hue(self.h, other.h, weight),
+        }
+    }
+}
+
 impl From<Coord> for CIELCHColor {
     fn from(c: Coord) -> CIELCHColor {
         CIELCHColor {
@@ -106,6 +118,7 @@ impl From<CIELCHColor> for Coord {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use colorpoint::Mix;
     use consts::TEST_PRECISION;
 
     #[test]
@@ -134,4 +147,24 @@ mod tests {
         assert!(xyz2.approx_visually_equal(&xyz));
         assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
     }
+
+    #[test]
+    fn test_lch_mix_hue_shortest() {
+        let magenta = CIELCHColor {
+            l: 50.,
+            c: 60.,
+            h: 340.,
+        };
+        let orange = CIELCHColor {
+            l: 70.,
+            c: 40.,
+            h: 60.,
+        };
+        let mixed = magenta.mix_hue_shortest(orange, 0.5);
+        assert!((mixed.h - 20.).abs() <= 1e-10);
+        assert!((mixed.l - 60.).abs() <= 1e-10);
+        assert!((mixed.c - 50.).abs() <= 1e-10);
+        // a plain mix passes through the opposite side of the wheel
+        assert!((magenta.mix(orange).h - 200.).abs() <= 1e-10);
+    }
 }
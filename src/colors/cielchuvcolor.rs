@@ -3,6 +3,7 @@
 
 use super::cieluvcolor::CIELUVColor;
 use color::{Color, XYZColor};
+use colorpoint::{lerp_hue, HueMix};
 use coord::Coord;
 use illuminants::Illuminant;
 
@@ -74,6 +75,17 @@ impl Color for CIELCHuvColor {
     }
 }
 
+impl HueMix for CIELCHuvColor {
+    /// Mixes luminance and chroma in a straight line, and hue along the shorter arc.
+    fn mix_hue_shortest(self, other: CIELCHuvColor, weight: f64) -> CIELCHuvColor {
+        CIELCHuvColor {
+            l: self.l + weight * (other.l - self.l),
+            c: self.c + weight * (other.c - self.c),
+            h: lerp_hue(self.h, other.h, weight),
+        }
+    }
+}
+
 impl From<Coord> for CIELCHuvColor {
     fn from(c: Coord) -> CIELCHuvColor {
         CIELCHuvColor {
@@ -127,4 +139,38 @@ mod tests {
         assert!(xyz.approx_visually_equal(&xyz2));
         assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
     }
+
+    #[test]
+    fn test_cielchuv_black() {
+        let black = CIELCHuvColor {
+            l: 0.,
+            c: 0.,
+            h: 0.,
+        };
+        let xyz = black.to_xyz(Illuminant::D65);
+        assert!(xyz.x.is_finite() && xyz.y.is_finite() && xyz.z.is_finite());
+        assert!(xyz.y.abs() <= 1e-12);
+        let back: CIELCHuvColor = xyz.convert();
+        assert!(back.l == 0. && back.c == 0.);
+    }
+
+    #[test]
+    fn test_lchuv_mix_hue_shortest() {
+        let c1 = CIELCHuvColor {
+            l: 50.,
+            c: 40.,
+            h: 350.,
+        };
+        let c2 = CIELCHuvColor {
+            l: 70.,
+            c: 60.,
+            h: 10.,
+        };
+        let mixed = c1.mix_hue_shortest(c2, 0.5);
+        assert!(mixed.h.abs() <= 1e-10);
+        assert!((mixed.l - 60.).abs() <= TEST_PRECISION);
+        assert!((mixed.c - 50.).abs() <= TEST_PRECISION);
+        assert!(c2.mix_hue_shortest(c1, 0.5).h.abs() <= 1e-10);
+        assert!((c1.mix_hue_shortest(c2, 0.25).h - 355.).abs() <= 1e-10);
+    }
 }
@@ -53,8 +53,12 @@ impl Color for CIELUVColor {
         let u_prime_n = u_func(wp);
         let v_prime_n = v_func(wp);
 
-        let u_prime = u_func(xyz_c);
-        let v_prime = v_func(xyz_c);
+        // black has no chromaticity, and would divide by 0: any would do, so use the white point's
+        let (u_prime, v_prime) = if denom(xyz_c) == 0.0 {
+            (u_prime_n, v_prime_n)
+        } else {
+            (u_func(xyz_c), v_func(xyz_c))
+        };
 
         let delta: f64 = 6.0 / 29.0; // like CIELAB
 
@@ -73,7 +77,9 @@ impl Color for CIELUVColor {
     }
     /// Returns a new `XYZColor` that matches the given color. Note that Scarlet uses CIELUV D50 to
     /// get around compatibility issues, so any other illuminant will be chromatically adapted after
-    /// initial conversion (using the `color_adapt()` function).
+    /// initial conversion (using the `color_adapt()` function). Black, with a lightness of 0 or less,
+    /// gives black no matter what u and v are, and a color with v so negative that its chromaticity
+    /// would be infinitely far away gives the gray of the same lightness, so neither gives NaN.
     fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
         // https://en.wikipedia.org/wiki/CIELUV literally has the equations in order
         // pretty straightforward
@@ -84,8 +90,23 @@ impl Color for CIELUVColor {
         let u_prime_n = u_func(wp);
         let v_prime_n = v_func(wp);
 
-        let u_prime = self.u / (13.0 * self.l) + u_prime_n;
-        let v_prime = self.v / (13.0 * self.l) + v_prime_n;
+        // u and v are scaled by lightness, so there's no way to get chromaticity back from black
+        if self.l <= 0.0 {
+            return XYZColor {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                illuminant,
+            };
+        }
+
+        let mut u_prime = self.u / (13.0 * self.l) + u_prime_n;
+        let mut v_prime = self.v / (13.0 * self.l) + v_prime_n;
+        if v_prime <= 0.0 {
+            // no real color has v' of 0 or less: X and Z would be infinite
+            u_prime = u_prime_n;
+            v_prime = v_prime_n;
+        }
 
         let delta: f64 = 6.0 / 29.0;
 
@@ -160,4 +181,29 @@ mod tests {
         assert!(xyz2.approx_visually_equal(&xyz));
         assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
     }
+
+    #[test]
+    fn test_cieluv_black() {
+        let black = CIELUVColor {
+            l: 0.,
+            u: 0.,
+            v: 0.,
+        };
+        for illuminant in [Illuminant::D50, Illuminant::D65].iter() {
+            let xyz = black.to_xyz(*illuminant);
+            assert!(xyz.x.is_finite() && xyz.y.is_finite() && xyz.z.is_finite());
+            assert!(xyz.y.abs() <= 1e-12 && xyz.x.abs() <= 1e-12 && xyz.z.abs() <= 1e-12);
+        }
+        // and back
+        let luv = CIELUVColor::from_xyz(black.to_xyz(Illuminant::D50));
+        assert!(luv.l == 0. && luv.u == 0. && luv.v == 0.);
+        // a chromaticity that can't exist still gives something finite
+        let xyz = CIELUVColor {
+            l: 50.,
+            u: 0.,
+            v: -200.,
+        }
+        .to_xyz(Illuminant::D50);
+        assert!(xyz.x.is_finite() && xyz.z.is_finite());
+    }
 }
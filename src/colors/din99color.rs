@@ -0,0 +1,180 @@
+//! This module implements the DIN99 color space, defined in the German standard DIN 6176. DIN99 is
+//! a reshaping of CIELAB: lightness is compressed logarithmically, the opponent axes are rotated and
+//! stretched, and chroma is compressed logarithmically as well. The upshot is that plain Euclidean
+//! distance in DIN99 tracks perceived color difference nearly as well as CIEDE2000, at a fraction
+//! of the cost. This implements the original DIN99 with the standard weights kE = kCH = 1: the
+//! later DIN99o and DIN99d refinements use different constants and are not included.
+
+use super::cielabcolor::CIELABColor;
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The angle the CIELAB opponent axes are rotated by, in degrees.
+const ROTATION: f64 = 16.0;
+/// The factor by which the rotated second opponent axis is scaled down.
+const F_SCALE: f64 = 0.7;
+/// The lightness compression constants.
+const L_SCALE: f64 = 105.51;
+const L_COMPRESSION: f64 = 0.0158;
+/// The chroma compression constant.
+const C_COMPRESSION: f64 = 0.045;
+
+/// A color in the DIN99 color space. Like CIELAB, it has a lightness component and two opponent
+/// color axes, but it's been distorted so that the Euclidean distance between two colors is a good
+/// measure of how different they look.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::DIN99Color;
+/// let color1: DIN99Color = RGBColor::from_hex_code("#A0522D").unwrap().convert();
+/// let color2: DIN99Color = RGBColor::from_hex_code("#A3522D").unwrap().convert();
+/// // a small difference in DIN99, just like in CIEDE2000
+/// assert!(color1.euclidean_distance(color2) < 1.);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct DIN99Color {
+    /// The lightness, L99. Ranges from 0 for black to 100 for white.
+    pub l: f64,
+    /// The first opponent color axis, a99. Positive values are reddish, negative values greenish.
+    pub a: f64,
+    /// The second opponent color axis, b99. Positive values are yellowish, negative values bluish.
+    pub b: f64,
+}
+
+impl Color for DIN99Color {
+    /// Converts from XYZ to DIN99 by way of CIELAB, so the same D50 chromatic adaptation applies.
+    fn from_xyz(xyz: XYZColor) -> DIN99Color {
+        let lab = CIELABColor::from_xyz(xyz);
+        let (sin, cos) = ROTATION.to_radians().sin_cos();
+        // rotate and stretch the opponent axes
+        let e = lab.a * cos + lab.b * sin;
+        let f = F_SCALE * (lab.b * cos - lab.a * sin);
+        // now compress chroma, keeping the hue the same
+        let g = e.hypot(f);
+        let c99 = (1.0 + C_COMPRESSION * g).ln() / C_COMPRESSION;
+        let h = f.atan2(e);
+        DIN99Color {
+            l: L_SCALE * (1.0 + L_COMPRESSION * lab.l).ln(),
+            a: c99 * h.cos(),
+            b: c99 * h.sin(),
+        }
+    }
+    /// Converts from DIN99 back to XYZ by way of CIELAB.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // undo each step of from_xyz in reverse order
+        let c99 = self.a.hypot(self.b);
+        let g = ((C_COMPRESSION * c99).exp() - 1.0) / C_COMPRESSION;
+        let h = self.b.atan2(self.a);
+        let e = g * h.cos();
+        let f = g * h.sin() / F_SCALE;
+        let (sin, cos) = ROTATION.to_radians().sin_cos();
+        CIELABColor {
+            l: ((self.l / L_SCALE).exp() - 1.0) / L_COMPRESSION,
+            a: e * cos - f * sin,
+            b: e * sin + f * cos,
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for DIN99Color {
+    fn from(c: Coord) -> DIN99Color {
+        DIN99Color {
+            l: c.x,
+            a: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<DIN99Color> for Coord {
+    fn from(val: DIN99Color) -> Self {
+        Coord {
+            x: val.l,
+            y: val.a,
+            z: val.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colorpoint::ColorPoint;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_din99_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.6,
+            illuminant: Illuminant::D65,
+        };
+        let din99: DIN99Color = xyz.convert();
+        let xyz2: XYZColor = din99.to_xyz(Illuminant::D65);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+        // white should stay at the top of the lightness scale
+        let white: DIN99Color = RGBColor {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+        }
+        .convert();
+        assert!((white.l - 100.).abs() <= 0.01);
+    }
+
+    #[test]
+    fn test_din99_distance_correlation() {
+        // pairs of nearby colors: DIN99 Euclidean distance should track CIEDE2000 more closely than
+        // CIELAB Euclidean distance does
+        let pairs = [
+            ("#FFFF00", "#F0F020"),
+            ("#0000FF", "#1010E0"),
+            ("#FF0000", "#E01808"),
+            ("#808080", "#888080"),
+            ("#123456", "#183A50"),
+            ("#ABCDEF", "#A8D0F0"),
+            ("#40AA50", "#48B058"),
+            ("#D00A12", "#C81020"),
+            ("#FFAAFF", "#F8B0F0"),
+            ("#202020", "#282828"),
+            ("#00FF00", "#10E010"),
+            ("#466223", "#4A6020"),
+        ];
+        let mut de2000 = vec![];
+        let mut de_lab = vec![];
+        let mut de_din = vec![];
+        for &(hex1, hex2) in pairs.iter() {
+            let c1 = RGBColor::from_hex_code(hex1).unwrap();
+            let c2 = RGBColor::from_hex_code(hex2).unwrap();
+            de2000.push(c1.distance(&c2));
+            de_lab.push(
+                c1.convert::<CIELABColor>()
+                    .euclidean_distance(c2.convert::<CIELABColor>()),
+            );
+            de_din.push(
+                c1.convert::<DIN99Color>()
+                    .euclidean_distance(c2.convert::<DIN99Color>()),
+            );
+        }
+        let corr = |xs: &[f64], ys: &[f64]| {
+            let n = xs.len() as f64;
+            let mean_x = xs.iter().sum::<f64>() / n;
+            let mean_y = ys.iter().sum::<f64>() / n;
+            let cov: f64 = xs
+                .iter()
+                .zip(ys.iter())
+                .map(|(x, y)| (x - mean_x) * (y - mean_y))
+                .sum();
+            let var_x: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+            let var_y: f64 = ys.iter().map(|y| (y - mean_y).powi(2)).sum();
+            cov / (var_x * var_y).sqrt()
+        };
+        assert!(corr(&de2000, &de_din) > corr(&de2000, &de_lab));
+    }
+}
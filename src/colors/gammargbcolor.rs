@@ -0,0 +1,162 @@
+//! This module implements RGB with sRGB's primaries and white point, but a pure power-law transfer
+//! function instead of sRGB's exact curve. The sRGB curve is often described as "gamma 2.2", and the
+//! two are close in the midtones, but sRGB's curve has a short linear segment near black and is a
+//! 2.4 power above that, so they disagree most in the shadows. Some older software, displays, and
+//! file formats really do use a pure 2.2 (or 1.8, as on classic Macs) power curve, and mixing the
+//! two up is a common source of subtly wrong dark colors: this type makes the choice explicit.
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use consts::STANDARD_RGB_TRANSFORM as SRGB;
+use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in RGB with sRGB's primaries and D65 white point, encoded with a pure power curve whose
+/// exponent, the gamma, is `GAMMA_HUNDREDTHS / 100`. The default is a gamma of 2.2: use
+/// `GammaRGBColor<180>` for 1.8, for example. A gamma of 2.4 is broadcast video, available as
+/// [`Rec709Color`](../rec709color/type.Rec709Color.html). Components range from 0 to 1 for colors in gamut,
+/// just like [`RGBColor`](../../color/struct.RGBColor.html).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::GammaRGBColor;
+/// // the same dark code values mean less light with a pure 2.2 curve than with sRGB's
+/// let gamma_dark: GammaRGBColor = GammaRGBColor::new(0.05, 0.05, 0.05);
+/// let srgb_dark = RGBColor{r: 0.05, g: 0.05, b: 0.05};
+/// assert!(gamma_dark.lightness() < srgb_dark.lightness() - 1.);
+/// // but white and black are the same
+/// let white: GammaRGBColor = RGBColor{r: 1., g: 1., b: 1.}.convert();
+/// assert!((white.r - 1.).abs() <= 1e-6);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct GammaRGBColor<const GAMMA_HUNDREDTHS: u32 = 220> {
+    /// The red component. Ranges from 0 to 1 for colors in gamut.
+    pub r: f64,
+    /// The green component. Ranges from 0 to 1 for colors in gamut.
+    pub g: f64,
+    /// The blue component. Ranges from 0 to 1 for colors in gamut.
+    pub b: f64,
+}
+
+impl<const GAMMA_HUNDREDTHS: u32> GammaRGBColor<GAMMA_HUNDREDTHS> {
+    /// Creates a new color with the given components.
+    pub fn new(r: f64, g: f64, b: f64) -> GammaRGBColor<GAMMA_HUNDREDTHS> {
+        GammaRGBColor { r, g, b }
+    }
+    /// Gets the gamma of this space: the exponent of its power curve.
+    pub fn gamma() -> f64 {
+        f64::from(GAMMA_HUNDREDTHS) / 100.0
+    }
+}
+
+impl<const GAMMA_HUNDREDTHS: u32> Color for GammaRGBColor<GAMMA_HUNDREDTHS> {
+    /// Converts to this space, adapting to D65 first if needed. Values outside of the gamut are not
+    /// clipped: the power curve is extended to negative values by symmetry.
+    fn from_xyz(xyz: XYZColor) -> GammaRGBColor<GAMMA_HUNDREDTHS> {
+        let xyz_c = xyz.color_adapt(Illuminant::D65);
+        let lin_rgb = *SRGB * vector![xyz_c.x, xyz_c.y, xyz_c.z];
+        let encode = |x: f64| x.signum() * x.abs().powf(Self::gamma().recip());
+        GammaRGBColor {
+            r: encode(lin_rgb[0]),
+            g: encode(lin_rgb[1]),
+            b: encode(lin_rgb[2]),
+        }
+    }
+    /// Converts to XYZ in D65, and then chromatically adapts to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let decode = |x: f64| x.signum() * x.abs().powf(Self::gamma());
+        // use LU decomposition for accuracy
+        let xyz = SRGB_LU
+            .solve(&vector![decode(self.r), decode(self.g), decode(self.b)])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl<const GAMMA_HUNDREDTHS: u32> From<Coord> for GammaRGBColor<GAMMA_HUNDREDTHS> {
+    fn from(c: Coord) -> GammaRGBColor<GAMMA_HUNDREDTHS> {
+        GammaRGBColor {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl<const GAMMA_HUNDREDTHS: u32> From<GammaRGBColor<GAMMA_HUNDREDTHS>> for Coord {
+    fn from(val: GammaRGBColor<GAMMA_HUNDREDTHS>) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl<const GAMMA_HUNDREDTHS: u32> Bound for GammaRGBColor<GAMMA_HUNDREDTHS> {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_gamma_rgb_xyz_conversion() {
+        for illuminant in [Illuminant::D50, Illuminant::D65].iter() {
+            let xyz = XYZColor {
+                x: 0.3,
+                y: 0.4,
+                z: 0.2,
+                illuminant: *illuminant,
+            };
+            let gamma22: GammaRGBColor = xyz.convert();
+            assert!(xyz.distance(&gamma22.to_xyz(*illuminant)) <= TEST_PRECISION);
+            let gamma18: GammaRGBColor<180> = xyz.convert();
+            assert!(xyz.distance(&gamma18.to_xyz(*illuminant)) <= TEST_PRECISION);
+        }
+        assert_eq!(GammaRGBColor::<180>::gamma(), 1.8);
+    }
+
+    #[test]
+    fn test_gamma_rgb_vs_srgb() {
+        // the endpoints and primaries are identical
+        for &(r, g, b) in [(0., 0., 0.), (1., 1., 1.), (1., 0., 0.), (0., 1., 0.)].iter() {
+            let gamma: GammaRGBColor = GammaRGBColor::new(r, g, b);
+            assert!(gamma.distance(&RGBColor { r, g, b }) <= 1e-6);
+        }
+        // the midtones are close
+        let gamma_mid: GammaRGBColor = GammaRGBColor::new(0.5, 0.5, 0.5);
+        let srgb_mid = RGBColor {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        assert!(gamma_mid.distance(&srgb_mid) <= 1.);
+        // but near black they differ: sRGB's linear segment gives more light
+        let gamma_dark: GammaRGBColor = GammaRGBColor::new(0.02, 0.02, 0.02);
+        let srgb_dark = RGBColor {
+            r: 0.02,
+            g: 0.02,
+            b: 0.02,
+        };
+        let y_gamma = gamma_dark.to_xyz(Illuminant::D65).y;
+        let y_srgb = srgb_dark.to_xyz(Illuminant::D65).y;
+        assert!((y_gamma - 0.02f64.powf(2.2)).abs() <= 1e-6);
+        assert!((y_srgb - 0.02 / 12.92).abs() <= 1e-6);
+        assert!(y_srgb > 5. * y_gamma);
+    }
+}
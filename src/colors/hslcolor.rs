@@ -23,6 +23,8 @@ use std::str::FromStr;
 
 use bound::Bound;
 use color::{Color, RGBColor, XYZColor};
+use colorpoint::{lerp_hue, HueMix};
+use colors::hsvcolor::HSVColor;
 use coord::Coord;
 use csscolor::{parse_hsl_hsv_tuple, CSSParseError};
 use illuminants::Illuminant;
@@ -157,6 +159,47 @@ impl Color for HSLColor {
     }
 }
 
+impl HSLColor {
+    /// Converts directly to HSV. Both spaces are transformations of the same sRGB color, so the hue
+    /// is kept as is and only saturation and lightness need to change: this skips the trip through
+    /// XYZ that [`convert`](../../color/trait.Color.html#method.convert) takes, which is faster and
+    /// adds no rounding error.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{HSLColor, HSVColor};
+    /// let pink = HSLColor{h: 330., s: 1., l: 0.75};
+    /// let hsv = pink.to_hsv();
+    /// assert_eq!(hsv.h, 330.);
+    /// assert!((hsv.s - 0.5).abs() <= 1e-10 && (hsv.v - 1.).abs() <= 1e-10);
+    /// ```
+    pub fn to_hsv(&self) -> HSVColor {
+        let value = self.l + self.s * self.l.min(1.0 - self.l);
+        let saturation = if value == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - self.l / value)
+        };
+        HSVColor {
+            h: self.h,
+            s: saturation,
+            v: value,
+        }
+    }
+}
+
+impl HueMix for HSLColor {
+    /// Mixes saturation and lightness in a straight line, and hue along the shorter arc.
+    fn mix_hue_shortest(self, other: HSLColor, weight: f64) -> HSLColor {
+        HSLColor {
+            s: self.s + weight * (other.s - self.s),
+            l: self.l + weight * (other.l - self.l),
+            h: lerp_hue(self.h, other.h, weight),
+        }
+    }
+}
+
 impl From<Coord> for HSLColor {
     fn from(c: Coord) -> HSLColor {
         HSLColor {
@@ -206,6 +249,7 @@ impl FromStr for HSLColor {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use colorpoint::Mix;
     use consts::TEST_PRECISION;
 
     #[test]
@@ -229,6 +273,30 @@ mod tests {
         assert_eq!(lavender_rgb.to_string(), "#6F66CC");
     }
 
+    #[test]
+    fn test_hsl_hsv_direct_conversion() {
+        for i in 0..12 {
+            for j in 0..=4 {
+                for k in 0..=4 {
+                    let hsl = HSLColor {
+                        h: f64::from(i) * 30.,
+                        s: f64::from(j) / 4.,
+                        l: f64::from(k) / 4.,
+                    };
+                    let direct = hsl.to_hsv();
+                    assert!(direct.distance(&hsl) <= 1e-8);
+                    let back = direct.to_hsl();
+                    assert_eq!(back.h, hsl.h);
+                    assert!((back.l - hsl.l).abs() <= 1e-12);
+                    // saturation is meaningless for black and white
+                    if k != 0 && k != 4 {
+                        assert!((back.s - hsl.s).abs() <= 1e-12);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_hsl_string_parsing() {
         let red_hsl: HSLColor = "hsl(0, 120%, 50%)".parse().unwrap();
@@ -241,4 +309,48 @@ mod tests {
         // test error
         assert!("hsl(254%, 0, 0)".parse::<HSLColor>().is_err());
     }
+
+    #[test]
+    fn test_hsl_mix_hue_shortest() {
+        let crimson = HSLColor {
+            h: 350.,
+            s: 1.,
+            l: 0.5,
+        };
+        let vermilion = HSLColor {
+            h: 10.,
+            s: 1.,
+            l: 0.5,
+        };
+        let mixed = crimson.mix_hue_shortest(vermilion, 0.5);
+        assert!(mixed.h.abs() <= 1e-10);
+        assert_eq!(mixed.convert::<RGBColor>().to_string(), "#FF0000");
+        // the same in either order, and endpoints are kept
+        assert!(vermilion.mix_hue_shortest(crimson, 0.5).h.abs() <= 1e-10);
+        assert!((crimson.mix_hue_shortest(vermilion, 0.25).h - 355.).abs() <= 1e-10);
+        assert_eq!(crimson.mix_hue_shortest(vermilion, 0.).h, 350.);
+        assert!((crimson.mix_hue_shortest(vermilion, 1.).h - 10.).abs() <= 1e-10);
+        // when the short way doesn't cross 0, it's the same as Mix
+        let yellow = HSLColor {
+            h: 60.,
+            s: 0.5,
+            l: 0.4,
+        };
+        let blue = HSLColor {
+            h: 240.,
+            s: 1.,
+            l: 0.6,
+        };
+        let teal = HSLColor {
+            h: 180.,
+            s: 0.5,
+            l: 0.4,
+        };
+        let plain = teal.mix_weighted(blue, 0.3);
+        let short = teal.mix_hue_shortest(blue, 0.3);
+        assert!((plain.h - short.h).abs() <= 1e-10);
+        assert!((plain.s - short.s).abs() <= 1e-10 && (plain.l - short.l).abs() <= 1e-10);
+        // opposite hues go the increasing way
+        assert!((yellow.mix_hue_shortest(blue, 0.5).h - 150.).abs() <= 1e-10);
+    }
 }
@@ -11,6 +11,8 @@ use std::str::FromStr;
 
 use bound::Bound;
 use color::{Color, RGBColor, XYZColor};
+use colorpoint::{lerp_hue, HueMix};
+use colors::hslcolor::HSLColor;
 use coord::Coord;
 use csscolor::{parse_hsl_hsv_tuple, CSSParseError};
 use illuminants::Illuminant;
@@ -59,7 +61,7 @@ impl Color for HSVColor {
         // hue is crazy in a hexagon! no more trig functions for us!
         // it's technically the proportion of the length of the hexagon through the point, but it's
         // treated as degrees
-        let hue = if chroma == 0.0 {
+        let mut hue = if chroma == 0.0 {
             // could be anything, undefined according to Wikipedia, in Scarlet just 0 for gray
             0.0
         } else if (max_c - rgb.r).abs() < EPSILON {
@@ -74,6 +76,10 @@ impl Color for HSVColor {
             // same as above, different offset
             ((rgb.r - rgb.g) / chroma) * 60.0 + 240.0
         };
+        // the red sector gives negative hues when blue is larger than green: wrap those around
+        if hue < 0. {
+            hue += 360.;
+        }
 
         // saturation, scientifically speaking, is chroma adjusted for lightness. For HSL, it's
         // defined relative to the maximum chroma, which varies depending on the place on the
@@ -130,6 +136,46 @@ impl Color for HSVColor {
     }
 }
 
+impl HSVColor {
+    /// Converts directly to HSL, keeping the hue and changing only saturation and value: the inverse
+    /// of [`HSLColor::to_hsv`](../hslcolor/struct.HSLColor.html#method.to_hsv). This is faster and
+    /// more precise than going through XYZ with [`convert`](../../color/trait.Color.html#method.convert).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{HSLColor, HSVColor};
+    /// let pink = HSVColor{h: 330., s: 0.5, v: 1.};
+    /// let hsl = pink.to_hsl();
+    /// assert_eq!(hsl.h, 330.);
+    /// assert!((hsl.s - 1.).abs() <= 1e-10 && (hsl.l - 0.75).abs() <= 1e-10);
+    /// ```
+    pub fn to_hsl(&self) -> HSLColor {
+        let lightness = self.v * (1.0 - self.s / 2.0);
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            (self.v - lightness) / lightness.min(1.0 - lightness)
+        };
+        HSLColor {
+            h: self.h,
+            s: saturation,
+            l: lightness,
+        }
+    }
+}
+
+impl HueMix for HSVColor {
+    /// Mixes saturation and value in a straight line, and hue along the shorter arc.
+    fn mix_hue_shortest(self, other: HSVColor, weight: f64) -> HSVColor {
+        HSVColor {
+            s: self.s + weight * (other.s - self.s),
+            v: self.v + weight * (other.v - self.v),
+            h: lerp_hue(self.h, other.h, weight),
+        }
+    }
+}
+
 impl From<Coord> for HSVColor {
     fn from(c: Coord) -> HSVColor {
         HSVColor {
@@ -200,6 +246,45 @@ mod tests {
         assert_eq!(lavender_rgb.to_string(), "#6E66EC");
     }
 
+    #[test]
+    fn test_hsv_hsl_direct_conversion() {
+        for i in 0..12 {
+            for j in 0..=4 {
+                for k in 0..=4 {
+                    let hsv = HSVColor {
+                        h: f64::from(i) * 30. + 15.,
+                        s: f64::from(j) / 4.,
+                        v: f64::from(k) / 4.,
+                    };
+                    let direct = hsv.to_hsl();
+                    let round_trip: HSLColor = hsv.convert();
+                    assert!(direct.distance(&round_trip) <= 1e-8);
+                    assert!(direct.distance(&hsv) <= 1e-8);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hsv_hue_nonnegative() {
+        // pinks and magentas have more blue than green, in the red sector
+        let pink = RGBColor {
+            r: 1.,
+            g: 0.2,
+            b: 0.6,
+        };
+        let pink_hsv: HSVColor = pink.convert();
+        assert!(pink_hsv.h >= 0.);
+        assert!((pink_hsv.h - 330.).abs() <= 1e-6);
+        assert!(pink_hsv.distance(&pink) <= 1e-8);
+        let pink_hsl: HSLColor = pink.convert();
+        assert!((pink_hsl.h - 330.).abs() <= 1e-6);
+        for &(r, g, b) in [(1., 0., 0.01), (0.8, 0.1, 0.5), (0.5, 0.4, 0.45)].iter() {
+            let hsv: HSVColor = RGBColor { r, g, b }.convert();
+            assert!(hsv.h >= 0. && hsv.h < 360.);
+        }
+    }
+
     #[test]
     fn test_hsv_string_parsing() {
         let red_hsv: HSVColor = "hsv(0, 120%, 50%)".parse().unwrap();
@@ -212,4 +297,23 @@ mod tests {
         // test error
         assert!("hsv(254%, 0, 0)".parse::<HSVColor>().is_err());
     }
+
+    #[test]
+    fn test_hsv_mix_hue_shortest() {
+        let crimson = HSVColor {
+            h: 350.,
+            s: 1.,
+            v: 1.,
+        };
+        let vermilion = HSVColor {
+            h: 10.,
+            s: 1.,
+            v: 1.,
+        };
+        let mixed = crimson.mix_hue_shortest(vermilion, 0.5);
+        assert!(mixed.h.abs() <= 1e-10);
+        assert_eq!(mixed.convert::<RGBColor>().to_string(), "#FF0000");
+        assert!(vermilion.mix_hue_shortest(crimson, 0.5).h.abs() <= 1e-10);
+        assert!((crimson.mix_hue_shortest(vermilion, 0.25).h - 355.).abs() <= 1e-10);
+    }
 }
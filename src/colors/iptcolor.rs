@@ -0,0 +1,145 @@
+//! This module implements the IPT color space, developed by Ebner and Fairchild in 1998. IPT has a
+//! lightness axis I and two opponent axes, P (protan, red-green) and T (tritan, yellow-blue), much
+//! like CIELAB. What sets it apart is hue linearity: lines of constant hue in IPT stay much closer
+//! to lines of constant *perceived* hue, especially for blues, which CIELAB notoriously bends toward
+//! purple. That makes it a good choice for gamut mapping and for building gradients that shouldn't
+//! change hue. For reference, see [Ebner and Fairchild,
+//! 1998](https://doi.org/10.2352/CIC.1998.6.1.art00003).
+
+use color::{Color, XYZColor};
+use consts::IPT_LMS_TRANSFORM as IPT_LMS;
+use consts::IPT_LMS_TRANSFORM_LU as IPT_LMS_LU;
+use consts::IPT_TRANSFORM as IPT;
+use consts::IPT_TRANSFORM_LU as IPT_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The exponent of the nonlinearity applied to the cone responses.
+const IPT_EXPONENT: f64 = 0.43;
+
+/// A color in the IPT color space. `i` is lightness, `p` is the red-green axis, and `t` is the
+/// yellow-blue axis. IPT is defined relative to D65, so other illuminants are chromatically adapted.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::IPTColor;
+/// # use scarlet::color::XYZColor;
+/// let white: IPTColor = XYZColor::white_point(Illuminant::D65).convert();
+/// // white has full lightness and no hue
+/// assert!((white.i - 1.).abs() <= 1e-3);
+/// assert!(white.p.abs() <= 1e-3 && white.t.abs() <= 1e-3);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct IPTColor {
+    /// The lightness component. Ranges from 0 for black to 1 for white.
+    pub i: f64,
+    /// The red-green opponent axis: positive values are reddish, negative values greenish.
+    pub p: f64,
+    /// The yellow-blue opponent axis: positive values are yellowish, negative values bluish.
+    pub t: f64,
+}
+
+impl Color for IPTColor {
+    /// Converts from XYZ to IPT, adapting to D65 first if needed.
+    fn from_xyz(xyz: XYZColor) -> IPTColor {
+        let xyz_c = xyz.color_adapt(Illuminant::D65);
+        let lms = *IPT_LMS * vector![xyz_c.x, xyz_c.y, xyz_c.z];
+        // the nonlinearity has to preserve sign, as imaginary colors can have negative responses
+        let lms_prime = lms.map(|x| x.signum() * x.abs().powf(IPT_EXPONENT));
+        let ipt = *IPT * lms_prime;
+        IPTColor {
+            i: ipt[0],
+            p: ipt[1],
+            t: ipt[2],
+        }
+    }
+    /// Converts from IPT back to XYZ in D65, and then chromatically adapts to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // use LU decomposition for accuracy
+        let lms_prime = IPT_LU
+            .solve(&vector![self.i, self.p, self.t])
+            .expect("Matrix is invertible.");
+        let lms = lms_prime.map(|x| x.signum() * x.abs().powf(1.0 / IPT_EXPONENT));
+        let xyz = IPT_LMS_LU.solve(&lms).expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for IPTColor {
+    fn from(c: Coord) -> IPTColor {
+        IPTColor {
+            i: c.x,
+            p: c.y,
+            t: c.z,
+        }
+    }
+}
+
+impl From<IPTColor> for Coord {
+    fn from(val: IPTColor) -> Self {
+        Coord {
+            x: val.i,
+            y: val.p,
+            z: val.t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colors::cielabcolor::CIELABColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_ipt_xyz_conversion() {
+        for illuminant in [Illuminant::D50, Illuminant::D65].iter() {
+            let xyz = XYZColor {
+                x: 0.3,
+                y: 0.22,
+                z: 0.5,
+                illuminant: *illuminant,
+            };
+            let ipt: IPTColor = xyz.convert();
+            let xyz2 = ipt.to_xyz(*illuminant);
+            assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+        }
+    }
+
+    #[test]
+    fn test_ipt_blue_hue_linearity() {
+        // CIELAB's lines of constant hue bend badly in the blue region: a line of constant CIELAB
+        // hue running from gray out to pure blue looks noticeably purple at the high-chroma end. IPT
+        // was fit to perceptual constant-hue data, so it should see that line as changing hue a lot.
+        let blue: CIELABColor = RGBColor {
+            r: 0.,
+            g: 0.,
+            b: 1.,
+        }
+        .convert();
+        let ipt_hues: Vec<f64> = (1..6)
+            .map(|i| {
+                let t = f64::from(i) * 0.2;
+                let ipt: IPTColor = CIELABColor {
+                    l: blue.l,
+                    a: blue.a * t,
+                    b: blue.b * t,
+                }
+                .convert();
+                ipt.t.atan2(ipt.p).to_degrees()
+            })
+            .collect();
+        // hue should shift monotonically, and by a lot
+        assert!(ipt_hues.windows(2).all(|w| w[1] < w[0]));
+        assert!(ipt_hues[0] - ipt_hues[4] >= 30.);
+    }
+}
@@ -3,20 +3,36 @@
 //!
 //! [`Color`]: ../color/trait.Color.html
 pub mod adobergbcolor;
+pub mod cam16ucscolor;
 pub mod cielabcolor;
 pub mod cielchcolor;
 pub mod cielchuvcolor;
 pub mod cieluvcolor;
+pub mod din99color;
+pub mod gammargbcolor;
 pub mod hslcolor;
 pub mod hsvcolor;
+pub mod iptcolor;
+pub mod rec709color;
 pub mod rommrgbcolor;
+pub mod ucs1960;
+pub mod yiqcolor;
+pub mod yuvcolor;
 
 // for convenience, use this namespace for the color objects
 pub use self::adobergbcolor::AdobeRGBColor;
+pub use self::cam16ucscolor::CAM16UCSColor;
 pub use self::cielabcolor::CIELABColor;
 pub use self::cielchcolor::CIELCHColor;
 pub use self::cielchuvcolor::CIELCHuvColor;
 pub use self::cieluvcolor::CIELUVColor;
+pub use self::din99color::DIN99Color;
+pub use self::gammargbcolor::GammaRGBColor;
 pub use self::hslcolor::HSLColor;
 pub use self::hsvcolor::HSVColor;
+pub use self::iptcolor::IPTColor;
+pub use self::rec709color::Rec709Color;
 pub use self::rommrgbcolor::ROMMRGBColor;
+pub use self::ucs1960::UCS1960Chromaticity;
+pub use self::yiqcolor::YIQColor;
+pub use self::yuvcolor::YUVColor;
@@ -0,0 +1,80 @@
+//! This module implements Rec. 709 video RGB as it's decoded by a display following ITU-R BT.1886,
+//! the reference transfer function for HDTV. Rec. 709 shares its primaries and white point with
+//! sRGB, so the two spaces cover exactly the same colors, but BT.1886 decodes with a pure 2.4 power
+//! curve where sRGB uses a gentler curve with a linear segment near black. The same code values
+//! therefore look different: video decoded as if it were sRGB comes out too bright and washed out
+//! in the shadows, and vice versa. This uses the BT.1886 curve for an ideal display with a black
+//! level of 0.
+
+use super::gammargbcolor::GammaRGBColor;
+
+/// A color in Rec. 709 RGB, decoded with the BT.1886 transfer function: the standard for broadcast
+/// HD video. With a black level of 0, BT.1886 is a pure 2.4 power curve on sRGB's primaries, so this
+/// is a [`GammaRGBColor`](../gammargbcolor/struct.GammaRGBColor.html) with a gamma of 2.4.
+/// Components range from 0 to 1 for colors in gamut, just like
+/// [`RGBColor`](../../color/struct.RGBColor.html).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::Rec709Color;
+/// // the same code values look darker on a broadcast display than on the web
+/// let video_gray = Rec709Color{r: 0.5, g: 0.5, b: 0.5};
+/// let web_gray = RGBColor{r: 0.5, g: 0.5, b: 0.5};
+/// assert!(video_gray.lightness() < web_gray.lightness());
+/// ```
+pub type Rec709Color = GammaRGBColor<240>;
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::{Color, RGBColor, XYZColor};
+    use consts::TEST_PRECISION;
+    use illuminants::Illuminant;
+
+    #[test]
+    fn test_rec709_xyz_conversion() {
+        for illuminant in [Illuminant::D50, Illuminant::D65].iter() {
+            let xyz = XYZColor {
+                x: 0.3,
+                y: 0.4,
+                z: 0.2,
+                illuminant: *illuminant,
+            };
+            let rec709: Rec709Color = xyz.convert();
+            let xyz2 = rec709.to_xyz(*illuminant);
+            assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+        }
+        assert_eq!(Rec709Color::gamma(), 2.4);
+    }
+
+    #[test]
+    fn test_rec709_vs_srgb() {
+        // the endpoints and primaries are identical
+        for &(r, g, b) in [(0., 0., 0.), (1., 1., 1.), (1., 0., 0.), (0., 0., 1.)].iter() {
+            let rec709 = Rec709Color { r, g, b };
+            let srgb = RGBColor { r, g, b };
+            assert!(rec709.distance(&srgb) <= 1e-6);
+        }
+        // but mid-gray decodes to less light: 0.5^2.4 vs. sRGB's 0.214
+        let rec709_gray = Rec709Color {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        let srgb_gray = RGBColor {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        let y709 = rec709_gray.to_xyz(Illuminant::D65).y;
+        let y_srgb = srgb_gray.to_xyz(Illuminant::D65).y;
+        assert!((y709 - 0.5f64.powf(2.4)).abs() <= 1e-3);
+        assert!((y_srgb - 0.2140).abs() <= 1e-3);
+        assert!(rec709_gray.distance(&srgb_gray) >= 2.);
+        // so the same light needs a higher code value in Rec. 709
+        let converted: Rec709Color = srgb_gray.convert();
+        assert!(converted.r > 0.5);
+    }
+}
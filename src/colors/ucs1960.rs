@@ -0,0 +1,93 @@
+//! This module implements the CIE 1960 UCS (uniform chromaticity scale) diagram. It was the first
+//! attempt at a chromaticity diagram in which distances match how different chromaticities look,
+//! and was superseded by the CIE 1976 u'v' diagram, which stretches its v axis by 3/2. It survives
+//! because correlated color temperature and Duv, the distance of a light from the blackbody locus,
+//! are still defined in it. Unlike the other modules here, this isn't a full color space: a
+//! chromaticity has no brightness, so it can't be converted back into a color.
+
+use color::Color;
+
+/// A chromaticity in the CIE 1960 UCS diagram, with `u = u'` and `v = 2/3 v'` in terms of the CIE
+/// 1976 u'v' coordinates given by [`chromaticity_uv`](../../color/trait.Color.html#method.chromaticity_uv).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::UCS1960Chromaticity;
+/// let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+/// let uv = UCS1960Chromaticity::from_color(&white);
+/// assert!((uv.u - 0.1978).abs() <= 1e-4);
+/// assert!((uv.v - 0.3122).abs() <= 1e-4);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UCS1960Chromaticity {
+    /// The u coordinate, identical to CIE 1976 u'.
+    pub u: f64,
+    /// The v coordinate, two-thirds of CIE 1976 v'.
+    pub v: f64,
+}
+
+impl UCS1960Chromaticity {
+    /// Gets the chromaticity of a color, treating it as a light in a D65 viewing environment like
+    /// [`chromaticity_uv`](../../color/trait.Color.html#method.chromaticity_uv) does.
+    pub fn from_color<T: Color>(color: &T) -> UCS1960Chromaticity {
+        let (u, v) = color.ucs_uv_1960();
+        UCS1960Chromaticity { u, v }
+    }
+    /// Converts from CIE 1976 u'v' coordinates.
+    pub fn from_uv_prime(u_prime: f64, v_prime: f64) -> UCS1960Chromaticity {
+        UCS1960Chromaticity {
+            u: u_prime,
+            v: v_prime * 2.0 / 3.0,
+        }
+    }
+    /// Converts to CIE 1976 u'v' coordinates, as a `(u', v')` pair.
+    pub fn to_uv_prime(&self) -> (f64, f64) {
+        (self.u, self.v * 1.5)
+    }
+    /// Gets the Euclidean distance between two chromaticities in this diagram: the usual measure
+    /// behind Duv and the tolerances in lighting standards.
+    pub fn distance(&self, other: &UCS1960Chromaticity) -> f64 {
+        (self.u - other.u).hypot(self.v - other.v)
+    }
+}
+
+impl From<(f64, f64)> for UCS1960Chromaticity {
+    fn from(uv: (f64, f64)) -> UCS1960Chromaticity {
+        UCS1960Chromaticity { u: uv.0, v: uv.1 }
+    }
+}
+
+impl From<UCS1960Chromaticity> for (f64, f64) {
+    fn from(val: UCS1960Chromaticity) -> Self {
+        (val.u, val.v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+
+    #[test]
+    fn test_ucs1960_vs_uv_prime() {
+        for hex in [
+            "#FFFFFF", "#FF0000", "#00FF00", "#0000FF", "#FFB46B", "#123456",
+        ]
+        .iter()
+        {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let (u_prime, v_prime) = color.chromaticity_uv();
+            let uv = UCS1960Chromaticity::from_color(&color);
+            assert_eq!(uv.u, u_prime);
+            assert!((uv.v - v_prime * 2.0 / 3.0).abs() <= 1e-12);
+            assert_eq!(uv, UCS1960Chromaticity::from_uv_prime(u_prime, v_prime));
+            let (u2, v2) = uv.to_uv_prime();
+            assert!((u2 - u_prime).abs() <= 1e-12 && (v2 - v_prime).abs() <= 1e-12);
+        }
+        let a = UCS1960Chromaticity::from((0.2, 0.3));
+        let b = UCS1960Chromaticity::from((0.23, 0.34));
+        assert!((a.distance(&b) - 0.05).abs() <= 1e-12);
+    }
+}
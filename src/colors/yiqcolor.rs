@@ -0,0 +1,132 @@
+//! This module implements YIQ, the color encoding used by analog NTSC television. YIQ shares its
+//! luma Y with [`YUVColor`](../yuvcolor/struct.YUVColor.html), but its two chroma axes are the U and
+//! V axes rotated by 33 degrees: I lies roughly along the orange-blue direction, to which the eye is
+//! most sensitive, and Q along the purple-green direction, so that NTSC could spend less bandwidth
+//! on Q. Like YUV, it's mostly useful for retro video effects and working with old formats.
+
+use super::yuvcolor::YUVColor;
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The angle, in degrees, between the UV and IQ axes.
+const IQ_ROTATION: f64 = 33.0;
+
+/// A color in the analog NTSC YIQ space, derived from gamma-encoded sRGB.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::YIQColor;
+/// let orange: YIQColor = RGBColor::from_hex_code("#FF8000").unwrap().convert();
+/// // orange lies mostly along the I axis
+/// assert!(orange.i > 0.4 && orange.q.abs() < 0.1);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct YIQColor {
+    /// The luma, identical to YUV's. Ranges from 0 to 1.
+    pub y: f64,
+    /// The in-phase chroma component, running from blue (negative) to orange (positive). Ranges from
+    /// about -0.596 to 0.596.
+    pub i: f64,
+    /// The quadrature chroma component, running from green (negative) to purple (positive). Ranges
+    /// from about -0.523 to 0.523.
+    pub q: f64,
+}
+
+impl Color for YIQColor {
+    /// Converts to YIQ by rotating the chroma axes of YUV.
+    fn from_xyz(xyz: XYZColor) -> YIQColor {
+        let yuv = YUVColor::from_xyz(xyz);
+        let (sin, cos) = IQ_ROTATION.to_radians().sin_cos();
+        YIQColor {
+            y: yuv.y,
+            i: yuv.v * cos - yuv.u * sin,
+            q: yuv.v * sin + yuv.u * cos,
+        }
+    }
+    /// Converts from YIQ back to XYZ by undoing the rotation and going through YUV.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let (sin, cos) = IQ_ROTATION.to_radians().sin_cos();
+        YUVColor {
+            y: self.y,
+            u: self.q * cos - self.i * sin,
+            v: self.i * cos + self.q * sin,
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for YIQColor {
+    fn from(c: Coord) -> YIQColor {
+        YIQColor {
+            y: c.x,
+            i: c.y,
+            q: c.z,
+        }
+    }
+}
+
+impl From<YIQColor> for Coord {
+    fn from(val: YIQColor) -> Self {
+        Coord {
+            x: val.y,
+            y: val.i,
+            z: val.q,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_yiq_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.3,
+            z: 0.2,
+            illuminant: Illuminant::D50,
+        };
+        let yiq: YIQColor = xyz.convert();
+        let xyz2: XYZColor = yiq.convert();
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+
+    #[test]
+    fn test_yiq_primaries() {
+        // the columns of the standard FCC NTSC matrix, which is only given to 3-4 digits
+        let expected = [
+            (0.299, 0.5959, 0.2115),
+            (0.587, -0.2746, -0.5227),
+            (0.114, -0.3213, 0.3112),
+        ];
+        let primaries = [
+            RGBColor {
+                r: 1.,
+                g: 0.,
+                b: 0.,
+            },
+            RGBColor {
+                r: 0.,
+                g: 1.,
+                b: 0.,
+            },
+            RGBColor {
+                r: 0.,
+                g: 0.,
+                b: 1.,
+            },
+        ];
+        for (rgb, &(y, i, q)) in primaries.iter().zip(expected.iter()) {
+            let yiq: YIQColor = rgb.convert();
+            assert!((yiq.y - y).abs() <= 1e-6);
+            assert!((yiq.i - i).abs() <= 1e-3);
+            assert!((yiq.q - q).abs() <= 1e-3);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! This module implements YUV, the color encoding used by analog PAL television. YUV splits a
+//! gamma-encoded RGB color into a luma component Y and two color-difference components, U (blue
+//! minus luma) and V (red minus luma). It's not perceptually uniform or device-independent in any
+//! useful sense: its value today is in video effects and in interoperating with older codecs and
+//! formats. This uses the standard BT.601 luma weights on sRGB.
+
+use color::{Color, RGBColor, XYZColor};
+use consts::YUV_TRANSFORM as YUV;
+use consts::YUV_TRANSFORM_LU as YUV_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the analog YUV space, derived from gamma-encoded sRGB.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::YUVColor;
+/// let gray: YUVColor = RGBColor{r: 0.5, g: 0.5, b: 0.5}.convert();
+/// // grays have no color difference
+/// assert!((gray.y - 0.5).abs() <= 1e-4);
+/// assert!(gray.u.abs() <= 1e-4 && gray.v.abs() <= 1e-4);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct YUVColor {
+    /// The luma, a weighted sum of the gamma-encoded RGB components. Ranges from 0 to 1.
+    pub y: f64,
+    /// The blue color-difference component, scaled from B - Y. Ranges from about -0.436 to 0.436.
+    pub u: f64,
+    /// The red color-difference component, scaled from R - Y. Ranges from about -0.615 to 0.615.
+    pub v: f64,
+}
+
+impl Color for YUVColor {
+    /// Converts to YUV by going through sRGB.
+    fn from_xyz(xyz: XYZColor) -> YUVColor {
+        let rgb = RGBColor::from_xyz(xyz);
+        let yuv = *YUV * vector![rgb.r, rgb.g, rgb.b];
+        YUVColor {
+            y: yuv[0],
+            u: yuv[1],
+            v: yuv[2],
+        }
+    }
+    /// Converts from YUV back to XYZ by way of sRGB. Any illuminant other than D65 is computed using
+    /// chromatic adaptation.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // use LU decomposition for accuracy
+        let rgb = YUV_LU
+            .solve(&vector![self.y, self.u, self.v])
+            .expect("Matrix is invertible.");
+        RGBColor {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for YUVColor {
+    fn from(c: Coord) -> YUVColor {
+        YUVColor {
+            y: c.x,
+            u: c.y,
+            v: c.z,
+        }
+    }
+}
+
+impl From<YUVColor> for Coord {
+    fn from(val: YUVColor) -> Self {
+        Coord {
+            x: val.y,
+            y: val.u,
+            z: val.v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_yuv_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.3,
+            z: 0.2,
+            illuminant: Illuminant::D50,
+        };
+        let yuv: YUVColor = xyz.convert();
+        let xyz2: XYZColor = yuv.convert();
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+
+    #[test]
+    fn test_yuv_primaries() {
+        // the columns of the BT.601 matrix
+        let expected = [
+            (0.299, -0.14713, 0.615),
+            (0.587, -0.28886, -0.51499),
+            (0.114, 0.436, -0.10001),
+        ];
+        let primaries = [
+            RGBColor {
+                r: 1.,
+                g: 0.,
+                b: 0.,
+            },
+            RGBColor {
+                r: 0.,
+                g: 1.,
+                b: 0.,
+            },
+            RGBColor {
+                r: 0.,
+                g: 0.,
+                b: 1.,
+            },
+        ];
+        for (rgb, &(y, u, v)) in primaries.iter().zip(expected.iter()) {
+            let yuv: YUVColor = rgb.convert();
+            assert!((yuv.y - y).abs() <= 1e-6);
+            assert!((yuv.u - u).abs() <= 1e-6);
+            assert!((yuv.v - v).abs() <= 1e-6);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! This file provides constants that are used for matrix multiplication and color space conversion,
-//! along with a function for computing inverses. The reason for this method of doing things instead
-//! of simple multiplications and additions is because the inverses of these transformations become
-//! slightly off, allowing for errors to slowly creep in even when doing things that should not
-//! change the result at all, e.g., converting to an illuminant and back again. Thus, this method
-//! allows for saner checking of constant values and guaranteed precision in inversion.
+//! along with an LU decomposition of each matrix for going the other way. The reason for this method
+//! of doing things instead of simple multiplications and additions is because the inverses of these
+//! transformations become slightly off, allowing for errors to slowly creep in even when doing
+//! things that should not change the result at all, e.g., converting to an illuminant and back
+//! again. Thus, this method allows for saner checking of constant values and guaranteed precision in
+//! inversion. Each decomposition is computed once, the first time it's used, so undoing a transform
+//! costs only a pair of triangular solves per conversion, not a matrix inversion.
 
 // This is the minimum acceptable difference in CIEDE2000 distance between two colors to consider
 // them equal for the purposes of Scarlet's tests. It doesn't belong outside a tests module, but I'm
@@ -51,6 +53,27 @@ lazy_static! {
     };
     pub(crate) static ref BRADFORD_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
     nalgebra::linalg::LU::new(*BRADFORD_TRANSFORM);
+    pub(crate) static ref CAM16_TRANSFORM: Matrix3<f64> = {
+        matrix![00.401288, 00.650173, -0.051461;
+                -0.250268, 01.204414, 00.045854;
+                -0.002079, 00.048952, 00.953127]
+    };
+    pub(crate) static ref CAM16_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*CAM16_TRANSFORM);
+    pub(crate) static ref IPT_LMS_TRANSFORM: Matrix3<f64> = {
+        matrix![00.4002, 00.7075, -0.0807;
+                -0.2280, 01.1500, 00.0612;
+                00.0000, 00.0000, 00.9184]
+    };
+    pub(crate) static ref IPT_LMS_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*IPT_LMS_TRANSFORM);
+    pub(crate) static ref IPT_TRANSFORM: Matrix3<f64> = {
+        matrix![0.4000, 00.4000, 00.2000;
+                4.4550, -4.8510, 00.3960;
+                0.8056, 00.3572, -1.1628]
+    };
+    pub(crate) static ref IPT_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*IPT_TRANSFORM);
     pub(crate) static ref ROMM_RGB_TRANSFORM: Matrix3<f64> = {
         matrix![0.7976749, 0.1351917, 0.0313534;
                 0.2880402, 0.7118741, 0.0000857;
@@ -65,6 +88,13 @@ lazy_static! {
     };
     pub(crate) static ref STANDARD_RGB_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
     nalgebra::linalg::LU::new(*STANDARD_RGB_TRANSFORM);
+    pub(crate) static ref YUV_TRANSFORM: Matrix3<f64> = {
+        matrix![00.29900, 00.58700, 00.11400;
+                -0.14713, -0.28886, 00.43600;
+                00.61500, -0.51499, -0.10001]
+    };
+    pub(crate) static ref YUV_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*YUV_TRANSFORM);
 }
 
 // These next two constants define the X11 color names and hex codes.
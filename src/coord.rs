@@ -146,6 +146,44 @@ impl Coord {
             z: (self.z * weight + (1.0 - weight) * other.z),
         }
     }
+    /// Linearly interpolates between this point and another: `t = 0` gives this point, `t = 1` gives
+    /// the other, and values in between move steadily from one to the other. This is usually easier
+    /// to read than [`weighted_midpoint`](#method.weighted_midpoint), whose weight runs the other way.
+    /// Values of `t` outside of 0 to 1 extrapolate along the same line.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// let start = Coord{x: 0., y: 10., z: 1.};
+    /// let end = Coord{x: 4., y: 0., z: 1.};
+    /// assert_eq!(start.lerp(&end, 0.), start);
+    /// assert_eq!(start.lerp(&end, 1.), end);
+    /// assert_eq!(start.lerp(&end, 0.25), Coord{x: 1., y: 7.5, z: 1.});
+    /// ```
+    pub fn lerp(&self, other: &Coord, t: f64) -> Coord {
+        Coord {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            z: self.z + (other.z - self.z) * t,
+        }
+    }
+    /// Clamps each axis of this point to lie between the matching axes of `min` and `max`, which is
+    /// the nearest point inside the box they define. If `min` is greater than `max` on some axis, that
+    /// axis is set to `max`.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// let point = Coord{x: -0.5, y: 0.5, z: 1.5};
+    /// let min = Coord{x: 0., y: 0., z: 0.};
+    /// let max = Coord{x: 1., y: 1., z: 1.};
+    /// assert_eq!(point.clamp(min, max), Coord{x: 0., y: 0.5, z: 1.});
+    /// ```
+    pub fn clamp(&self, min: Coord, max: Coord) -> Coord {
+        Coord {
+            x: self.x.max(min.x).min(max.x),
+            y: self.y.max(min.y).min(max.y),
+            z: self.z.max(min.z).min(max.z),
+        }
+    }
     /// The Euclidean difference between two 3D points, defined as the square root of the sum of
     /// squares of differences in each axis.
     /// It's very tempting to use this is as an analogue for perceptual difference between two colors,
@@ -188,4 +226,63 @@ impl Coord {
         let n = others.len() + 1;
         others.iter().fold(self, |x, y| x + *y) / n
     }
+    /// The dot product of two points, treated as vectors from the origin: the sum of the products of
+    /// each axis. This is 0 for perpendicular vectors, and for a unit vector it gives the length of
+    /// the other vector's projection onto it.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// let x_axis = Coord{x: 1., y: 0., z: 0.};
+    /// let y_axis = Coord{x: 0., y: 1., z: 0.};
+    /// assert_eq!(x_axis.dot(&y_axis), 0.);
+    /// assert_eq!(x_axis.dot(&x_axis), 1.);
+    /// assert_eq!(Coord{x: 1., y: 2., z: 3.}.dot(&Coord{x: 4., y: -5., z: 6.}), 12.);
+    /// ```
+    pub fn dot(&self, other: &Coord) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    /// The cross product of two points, treated as vectors from the origin: a vector perpendicular to
+    /// both, following the right-hand rule, whose length is the area of the parallelogram they span.
+    /// This is 0 for parallel vectors.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// let x_axis = Coord{x: 1., y: 0., z: 0.};
+    /// let y_axis = Coord{x: 0., y: 1., z: 0.};
+    /// assert_eq!(x_axis.cross(&y_axis), Coord{x: 0., y: 0., z: 1.});
+    /// assert_eq!(y_axis.cross(&x_axis), Coord{x: 0., y: 0., z: -1.});
+    /// assert_eq!(x_axis.cross(&x_axis), Coord{x: 0., y: 0., z: 0.});
+    /// ```
+    pub fn cross(&self, other: &Coord) -> Coord {
+        Coord {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+    /// The length of this point treated as a vector from the origin: its Euclidean distance from
+    /// `(0, 0, 0)`.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// assert_eq!(Coord{x: 2., y: 3., z: 6.}.norm(), 7.);
+    /// ```
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+    /// Scales this point, treated as a vector from the origin, to have a length of 1 while keeping its
+    /// direction.
+    ///
+    /// # Panics
+    /// Panics if this is the origin, which has no direction, as division by 0 does.
+    /// # Example
+    /// ```
+    /// # use scarlet::coord::Coord;
+    /// let unit = Coord{x: 0., y: 3., z: 4.}.normalized();
+    /// assert_eq!(unit, Coord{x: 0., y: 0.6, z: 0.8});
+    /// assert!((unit.norm() - 1.).abs() <= 1e-12);
+    /// ```
+    pub fn normalized(&self) -> Coord {
+        *self / self.norm()
+    }
 }
@@ -70,7 +70,6 @@ pub(crate) fn parse_rgb_str(num: &str) -> Result<(u8, u8, u8), CSSParseError> {
 
     // test for disallowed characters
     if chars.iter().any(|&c| !"0123456789+-,. %".contains(c)) {
-        println!("hi");
         return Err(CSSParseError::InvalidColorSyntax);
     }
     // this now requires a very specific format: three commas, a parenthesis at the end, and spaces
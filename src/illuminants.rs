@@ -5,11 +5,17 @@
 //! photographically from the CIE standard itself. These are normalized so that the Y (luminance)
 //! value is 100.
 
+use visual_gamut::read_cie_spectral_data;
+
 /// A listing of the supported CIE standard illuminants, standards that describe a particular set of
 /// lighting conditions. The most common ones for computers are D50 and D65, differing kinds of
 /// daylight. Other ones may be added as time goes on, but they won't be removed and backwards
 /// compatibility won't break without warning.
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Illuminants can be serialized with Serde. A `Custom` illuminant stores its array exactly as given,
+/// not normalized, so it round-trips unchanged: [`white_point`](#method.white_point) normalizes it
+/// when it's read, and gives the same result before and after.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Illuminant {
     /// The CIE D50 standard illuminant. See [this
     /// page](https://en.wikipedia.org/wiki/Standard_illuminant#Illuminant_series_D) for more
@@ -30,7 +36,8 @@ pub enum Illuminant {
     /// Represents a light of any given hue, as an array `[X, Y, Z]` in CIE 1931 space. This does not
     /// allow one to replicate any illuminant, but it does allow for custom illuminants and the
     /// ability to chromatically adapt to unique lighting conditions, like dark shade or colored
-    /// light.
+    /// light. The array can be at any scale: it's normalized so that Y is 1 whenever the white
+    /// point is needed.
     Custom([f64; 3]),
 }
 
@@ -45,8 +52,69 @@ pub(crate) static ILLUMINANT_WHITE_POINTS: [[f64; 3]; 4] = [
     [0.94972, 1.00000, 1.22638],
 ];
 
+/// The three basis functions S0, S1, and S2 of the CIE daylight model, tabulated every 10 nm from
+/// 300 to 830 nm. Every D-series illuminant's spectral power distribution is a linear combination of
+/// these, with weights depending only on its correlated color temperature.
+static DAYLIGHT_COMPONENTS: [[f64; 3]; 54] = [
+    [0.04, 0.02, 0.0],   // 300 nm
+    [6.0, 4.5, 2.0],     // 310 nm
+    [29.6, 22.4, 4.0],   // 320 nm
+    [55.3, 42.0, 8.5],   // 330 nm
+    [57.3, 40.6, 7.8],   // 340 nm
+    [61.8, 41.6, 6.7],   // 350 nm
+    [61.5, 38.0, 5.3],   // 360 nm
+    [68.8, 42.4, 6.1],   // 370 nm
+    [63.4, 38.5, 3.0],   // 380 nm
+    [65.8, 35.0, 1.2],   // 390 nm
+    [94.8, 43.4, -1.1],  // 400 nm
+    [104.8, 46.3, -0.5], // 410 nm
+    [105.9, 43.9, -0.7], // 420 nm
+    [96.8, 37.1, -1.2],  // 430 nm
+    [113.9, 36.7, -2.6], // 440 nm
+    [125.6, 35.9, -2.9], // 450 nm
+    [125.5, 32.6, -2.8], // 460 nm
+    [121.3, 27.9, -2.6], // 470 nm
+    [121.3, 24.3, -2.6], // 480 nm
+    [113.5, 20.1, -1.8], // 490 nm
+    [113.1, 16.2, -1.5], // 500 nm
+    [110.8, 13.2, -1.3], // 510 nm
+    [106.5, 8.6, -1.2],  // 520 nm
+    [108.8, 6.1, -1.0],  // 530 nm
+    [105.3, 4.2, -0.5],  // 540 nm
+    [104.4, 1.9, -0.3],  // 550 nm
+    [100.0, 0.0, 0.0],   // 560 nm
+    [96.0, -1.6, 0.2],   // 570 nm
+    [95.1, -3.5, 0.5],   // 580 nm
+    [89.1, -3.5, 2.1],   // 590 nm
+    [90.5, -5.8, 3.2],   // 600 nm
+    [90.3, -7.2, 4.1],   // 610 nm
+    [88.4, -8.6, 4.7],   // 620 nm
+    [84.0, -9.5, 5.1],   // 630 nm
+    [85.1, -10.9, 6.7],  // 640 nm
+    [81.9, -10.7, 7.3],  // 650 nm
+    [82.6, -12.0, 8.6],  // 660 nm
+    [84.9, -14.0, 9.8],  // 670 nm
+    [81.3, -13.6, 10.2], // 680 nm
+    [71.9, -12.0, 8.3],  // 690 nm
+    [74.3, -13.3, 9.6],  // 700 nm
+    [76.4, -12.9, 8.5],  // 710 nm
+    [63.3, -10.6, 7.0],  // 720 nm
+    [71.7, -11.6, 7.6],  // 730 nm
+    [77.0, -12.2, 8.0],  // 740 nm
+    [65.2, -10.2, 6.7],  // 750 nm
+    [47.7, -7.8, 5.2],   // 760 nm
+    [68.6, -11.2, 7.4],  // 770 nm
+    [65.0, -10.4, 6.8],  // 780 nm
+    [66.0, -10.6, 7.0],  // 790 nm
+    [61.0, -9.7, 6.4],   // 800 nm
+    [53.3, -8.3, 5.5],   // 810 nm
+    [58.9, -9.3, 6.1],   // 820 nm
+    [61.9, -9.8, 6.5],   // 830 nm
+];
+
 impl Illuminant {
-    /// Gets the XYZ coordinates of the white point value of the illuminant, normalized so Y = 1.
+    /// Gets the XYZ coordinates of the white point value of the illuminant, normalized so Y = 1. For
+    /// `Custom` illuminants, this divides the stored array by its Y value.
     /// # Example
     ///
     /// ```
@@ -65,4 +133,113 @@ impl Illuminant {
             Illuminant::Custom(xyz) => [xyz[0] / xyz[1], 1.0, xyz[2] / xyz[1]],
         }
     }
+
+    /// Gets the relative spectral power of the illuminant at the given wavelength in nanometers,
+    /// normalized to 100 at 560 nm as the CIE does. The D-series illuminants are computed from the
+    /// CIE daylight model, linearly interpolating its 10 nm table, and are 0 outside of its 300-830
+    /// nm range. Custom illuminants only record a white point, not a spectrum, so they return `None`.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::Illuminant;
+    /// // D65 is bluer than D50, so it has relatively more power at short wavelengths
+    /// let d50 = Illuminant::D50.relative_spd(450.).unwrap();
+    /// let d65 = Illuminant::D65.relative_spd(450.).unwrap();
+    /// assert!(d65 > d50);
+    /// assert!(Illuminant::Custom([1., 1., 1.]).relative_spd(450.).is_none());
+    /// ```
+    pub fn relative_spd(&self, wavelength: f64) -> Option<f64> {
+        // the nominal temperatures are scaled by 1.4388 / 1.4380 after a revision of the second
+        // radiation constant
+        let temp: f64 = match *self {
+            Illuminant::D50 => 5003.,
+            Illuminant::D55 => 5503.,
+            Illuminant::D65 => 6504.,
+            Illuminant::D75 => 7504.,
+            Illuminant::Custom(_) => return None,
+        };
+        if !(300.0..=830.0).contains(&wavelength) {
+            return Some(0.);
+        }
+        // chromaticity of the daylight locus at this temperature
+        let x = if temp <= 7000. {
+            -4.6070e9 / temp.powi(3) + 2.9678e6 / temp.powi(2) + 0.09911e3 / temp + 0.244063
+        } else {
+            -2.0064e9 / temp.powi(3) + 1.9018e6 / temp.powi(2) + 0.24748e3 / temp + 0.237040
+        };
+        let y = -3.0 * x * x + 2.870 * x - 0.275;
+        let m = 0.0241 + 0.2562 * x - 0.7341 * y;
+        let m1 = (-1.3515 - 1.7703 * x + 5.9114 * y) / m;
+        let m2 = (0.0300 - 31.4424 * x + 30.0717 * y) / m;
+        // interpolate the basis functions
+        let pos = (wavelength - 300.) / 10.;
+        let i = (pos.floor() as usize).min(DAYLIGHT_COMPONENTS.len() - 2);
+        let t = pos - i as f64;
+        let s: Vec<f64> = (0..3)
+            .map(|j| DAYLIGHT_COMPONENTS[i][j] * (1. - t) + DAYLIGHT_COMPONENTS[i + 1][j] * t)
+            .collect();
+        Some(s[0] + m1 * s[1] + m2 * s[2])
+    }
+
+    /// Gets an illuminant corresponding to an ideal blackbody radiator (a *Planckian radiator*) at the
+    /// given temperature in kelvin. The spectral power distribution is computed from Planck's law and
+    /// integrated against the CIE 1931 standard observer, so this is exact up to the 5 nm sampling of
+    /// that data. Temperatures are clamped to the range 1000 K to 40000 K, which covers everything
+    /// from candlelight to clear blue sky.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::Illuminant;
+    /// // CIE illuminant A, representing incandescent light, is defined as a 2856 K blackbody
+    /// let wp = Illuminant::blackbody(2856.).white_point(); // [1.09850, 1.00000, 0.35585]
+    /// assert!((wp[0] - 1.09850).abs() <= 1e-3);
+    /// assert!((wp[2] - 0.35585).abs() <= 1e-3);
+    /// ```
+    pub fn blackbody(kelvin: f64) -> Illuminant {
+        let temp = kelvin.clamp(1000., 40000.);
+        // the second radiation constant, hc/k, in meter-kelvins: the first constant scales
+        // everything uniformly, so it's left out because we normalize anyway
+        let c2 = 1.4388e-2;
+        let (wavelengths, xyz_data) = read_cie_spectral_data();
+        let mut xyz = [0.; 3];
+        for (wavelength, cmf) in wavelengths.iter().zip(xyz_data.iter()) {
+            let lambda = f64::from(*wavelength) * 1e-9;
+            let power = 1.0 / (lambda.powi(5) * ((c2 / (lambda * temp)).exp() - 1.0));
+            xyz[0] += power * cmf.x;
+            xyz[1] += power * cmf.y;
+            xyz[2] += power * cmf.z;
+        }
+        Illuminant::Custom([xyz[0] / xyz[1], 1.0, xyz[2] / xyz[1]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_illuminant_serde_round_trip() {
+        let custom = Illuminant::Custom([0.5, 0.5, 0.5]);
+        let json = serde_json::to_string(&custom).unwrap();
+        // the raw array is kept, not the normalized white point
+        assert_eq!(json, r#"{"Custom":[0.5,0.5,0.5]}"#);
+        let back: Illuminant = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, custom);
+        assert_eq!(back.white_point(), custom.white_point());
+        assert_eq!(back.white_point(), [1., 1., 1.]);
+        for illuminant in [
+            Illuminant::D50,
+            Illuminant::D55,
+            Illuminant::D65,
+            Illuminant::D75,
+        ]
+        .iter()
+        {
+            let json = serde_json::to_string(illuminant).unwrap();
+            let back: Illuminant = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, *illuminant);
+        }
+    }
 }
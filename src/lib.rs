@@ -18,15 +18,20 @@ extern crate geo;
 #[macro_use]
 extern crate nalgebra;
 extern crate num;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
-// extern crate termion;
+#[cfg(test)]
+extern crate serde_json;
 #[macro_use]
 extern crate lazy_static;
 
+pub mod alpha;
 pub mod bound;
 pub mod color;
+pub mod colorbrewer;
 pub mod colormap;
 pub mod colorpoint;
 pub mod colors;
@@ -37,6 +42,8 @@ mod cssnumeric;
 pub mod illuminants;
 pub mod material_colors;
 mod matplotlib_cmaps;
+pub mod observer;
+pub mod palette;
 pub mod prelude;
 mod visual_gamut;
 // pub mod doc;
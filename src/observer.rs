@@ -0,0 +1,264 @@
+//! This module exposes the CIE standard observers: the three *color-matching functions* x̄, ȳ, and
+//! z̄ that define how the average human eye responds to each wavelength of light, and from which the
+//! entire CIE XYZ color space is built. Integrating a spectrum against these gives its XYZ
+//! coordinates, which is how Scarlet computes things like
+//! [`XYZColor::from_reflectance`](../color/struct.XYZColor.html#method.from_reflectance) and
+//! [`Illuminant::blackbody`](../illuminants/enum.Illuminant.html#method.blackbody). The 1931 data is
+//! compiled into Scarlet, so it's always available with no file access.
+//!
+//! There are two standard observers. The CIE 1931 2° observer describes colors seen in a small
+//! field of view, like a swatch at arm's length, and is what every color space in Scarlet is built
+//! on. The CIE 1964 10° observer describes large fields of view, and is the standard for judging the
+//! color of large areas like painted walls or textiles. See [`Observer`](enum.Observer.html).
+
+use illuminants::Illuminant;
+
+/// The two CIE standard observers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Observer {
+    /// The CIE 1931 2° standard observer, used throughout Scarlet.
+    #[default]
+    TwoDegree,
+    /// The CIE 1964 10° supplementary standard observer, meant for large color fields. Scarlet uses
+    /// the analytic approximation of [Wyman, Sloan, and Shirley,
+    /// 2013](http://jcgt.org/published/0002/02/01/) instead of the full tables: white points
+    /// integrated with it are accurate to about 1%.
+    TenDegree,
+}
+
+/// White points for the 10° observer, in the order of the `Illuminant` enum, from ASTM E308.
+static TEN_DEGREE_WHITE_POINTS: [[f64; 3]; 4] = [
+    [0.96720, 1.00000, 0.81427],
+    [0.95799, 1.00000, 0.90926],
+    [0.94811, 1.00000, 1.07304],
+    [0.94416, 1.00000, 1.20641],
+];
+
+impl Observer {
+    /// Gets the values of this observer's color-matching functions `[x̄, ȳ, z̄]` at the given
+    /// wavelength in nanometers. For the 2° observer, this is
+    /// [`color_matching_functions`](fn.color_matching_functions.html). Both are 0 outside of 360-830
+    /// nm.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::observer::Observer;
+    /// // the 10° observer is relatively more sensitive to short wavelengths
+    /// let two = Observer::TwoDegree.color_matching_functions(450.);
+    /// let ten = Observer::TenDegree.color_matching_functions(450.);
+    /// assert!(ten[2] > two[2]);
+    /// ```
+    pub fn color_matching_functions(&self, wavelength: f64) -> [f64; 3] {
+        match *self {
+            Observer::TwoDegree => color_matching_functions(wavelength),
+            Observer::TenDegree => {
+                if !(360.0..=830.0).contains(&wavelength) {
+                    return [0.0; 3];
+                }
+                let x = 0.398 * (-1250.0 * ((wavelength + 570.1) / 1014.0).ln().powi(2)).exp()
+                    + 1.132 * (-234.0 * ((1338.0 - wavelength) / 743.5).ln().powi(2)).exp();
+                let y = 1.011 * (-0.5 * ((wavelength - 556.1) / 46.14).powi(2)).exp();
+                let z = 2.060 * (-32.0 * ((wavelength - 265.8) / 180.4).ln().powi(2)).exp();
+                [x, y, z]
+            }
+        }
+    }
+
+    /// Gets the white point of the given illuminant as seen by this observer, normalized so Y = 1.
+    /// For the 2° observer, this is just
+    /// [`Illuminant::white_point`](../illuminants/enum.Illuminant.html#method.white_point). Custom
+    /// illuminants are defined only by their white point, so they're the same for both observers.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::observer::Observer;
+    /// let wp = Observer::TenDegree.white_point(Illuminant::D65);
+    /// assert!((wp[0] - 0.94811).abs() <= 1e-10);
+    /// assert!((wp[2] - 1.07304).abs() <= 1e-10);
+    /// ```
+    pub fn white_point(&self, illuminant: Illuminant) -> [f64; 3] {
+        match (*self, illuminant) {
+            (Observer::TwoDegree, _) | (_, Illuminant::Custom(_)) => illuminant.white_point(),
+            (Observer::TenDegree, Illuminant::D50) => TEN_DEGREE_WHITE_POINTS[0],
+            (Observer::TenDegree, Illuminant::D55) => TEN_DEGREE_WHITE_POINTS[1],
+            (Observer::TenDegree, Illuminant::D65) => TEN_DEGREE_WHITE_POINTS[2],
+            (Observer::TenDegree, Illuminant::D75) => TEN_DEGREE_WHITE_POINTS[3],
+        }
+    }
+}
+
+/// The CIE 1931 2° standard observer, tabulated every 5 nm from 360 to 830 nm. Each row is
+/// `[wavelength, x̄, ȳ, z̄]`, with the wavelength in nanometers. ȳ peaks at 1 at 555 nm.
+pub const CIE_1931_2_DEGREE: [[f64; 4]; 95] = [
+    [360., 0.00012990, 0.00000392, 0.00060610],
+    [365., 0.00023210, 0.00000697, 0.00108600],
+    [370., 0.00041490, 0.00001239, 0.00194600],
+    [375., 0.00074160, 0.00002202, 0.00348600],
+    [380., 0.00136800, 0.00003900, 0.00645000],
+    [385., 0.00223600, 0.00006400, 0.01054999],
+    [390., 0.00424300, 0.00012000, 0.02005001],
+    [395., 0.00765000, 0.00021700, 0.03621000],
+    [400., 0.01431000, 0.00039600, 0.06785001],
+    [405., 0.02319000, 0.00064000, 0.11020000],
+    [410., 0.04351000, 0.00121000, 0.20740000],
+    [415., 0.07763000, 0.00218000, 0.37130000],
+    [420., 0.13438000, 0.00400000, 0.64560000],
+    [425., 0.21477000, 0.00730000, 1.03905010],
+    [430., 0.28390000, 0.01160000, 1.38560000],
+    [435., 0.32850000, 0.01684000, 1.62296000],
+    [440., 0.34828000, 0.02300000, 1.74706000],
+    [445., 0.34806000, 0.02980000, 1.78260000],
+    [450., 0.33620000, 0.03800000, 1.77211000],
+    [455., 0.31870000, 0.04800000, 1.74410000],
+    [460., 0.29080000, 0.06000000, 1.66920000],
+    [465., 0.25110000, 0.07390000, 1.52810000],
+    [470., 0.19536000, 0.09098000, 1.28764000],
+    [475., 0.14210000, 0.11260000, 1.04190000],
+    [480., 0.09564000, 0.13902000, 0.81295010],
+    [485., 0.05795001, 0.16930000, 0.61620000],
+    [490., 0.03201000, 0.20802000, 0.46518000],
+    [495., 0.01470000, 0.25860000, 0.35330000],
+    [500., 0.00490000, 0.32300000, 0.27200000],
+    [505., 0.00240000, 0.40730000, 0.21230000],
+    [510., 0.00930000, 0.50300000, 0.15820000],
+    [515., 0.02910000, 0.60820000, 0.11170000],
+    [520., 0.06327000, 0.71000000, 0.07824999],
+    [525., 0.10960000, 0.79320000, 0.05725001],
+    [530., 0.16550000, 0.86200000, 0.04216000],
+    [535., 0.22574990, 0.91485010, 0.02984000],
+    [540., 0.29040000, 0.95400000, 0.02030000],
+    [545., 0.35970000, 0.98030000, 0.01340000],
+    [550., 0.43344990, 0.99495010, 0.00875000],
+    [555., 0.51205010, 1.00000000, 0.00575000],
+    [560., 0.59450000, 0.99500000, 0.00390000],
+    [565., 0.67840000, 0.97860000, 0.00275000],
+    [570., 0.76210000, 0.95200000, 0.00210000],
+    [575., 0.84250000, 0.91540000, 0.00180000],
+    [580., 0.91630000, 0.87000000, 0.00165000],
+    [585., 0.97860000, 0.81630000, 0.00140000],
+    [590., 1.02630000, 0.75700000, 0.00110000],
+    [595., 1.05670000, 0.69490000, 0.00100000],
+    [600., 1.06220000, 0.63100000, 0.00080000],
+    [605., 1.04560000, 0.56680000, 0.00060000],
+    [610., 1.00260000, 0.50300000, 0.00034000],
+    [615., 0.93840000, 0.44120000, 0.00024000],
+    [620., 0.85444990, 0.38100000, 0.00019000],
+    [625., 0.75140000, 0.32100000, 0.00010000],
+    [630., 0.64240000, 0.26500000, 0.00005000],
+    [635., 0.54190000, 0.21700000, 0.00003000],
+    [640., 0.44790000, 0.17500000, 0.00002000],
+    [645., 0.36080000, 0.13820000, 0.00001000],
+    [650., 0.28350000, 0.10700000, 0.00000000],
+    [655., 0.21870000, 0.08160000, 0.00000000],
+    [660., 0.16490000, 0.06100000, 0.00000000],
+    [665., 0.12120000, 0.04458000, 0.00000000],
+    [670., 0.08740000, 0.03200000, 0.00000000],
+    [675., 0.06360000, 0.02320000, 0.00000000],
+    [680., 0.04677000, 0.01700000, 0.00000000],
+    [685., 0.03290000, 0.01192000, 0.00000000],
+    [690., 0.02270000, 0.00821000, 0.00000000],
+    [695., 0.01584000, 0.00572300, 0.00000000],
+    [700., 0.01135916, 0.00410200, 0.00000000],
+    [705., 0.00811092, 0.00292900, 0.00000000],
+    [710., 0.00579035, 0.00209100, 0.00000000],
+    [715., 0.00410646, 0.00148400, 0.00000000],
+    [720., 0.00289933, 0.00104700, 0.00000000],
+    [725., 0.00204919, 0.00074000, 0.00000000],
+    [730., 0.00143997, 0.00052000, 0.00000000],
+    [735., 0.00099995, 0.00036110, 0.00000000],
+    [740., 0.00069008, 0.00024920, 0.00000000],
+    [745., 0.00047602, 0.00017190, 0.00000000],
+    [750., 0.00033230, 0.00012000, 0.00000000],
+    [755., 0.00023483, 0.00008480, 0.00000000],
+    [760., 0.00016615, 0.00006000, 0.00000000],
+    [765., 0.00011741, 0.00004240, 0.00000000],
+    [770., 0.00008308, 0.00003000, 0.00000000],
+    [775., 0.00005871, 0.00002120, 0.00000000],
+    [780., 0.00004151, 0.00001499, 0.00000000],
+    [785., 0.00002935, 0.00001060, 0.00000000],
+    [790., 0.00002067, 0.00000747, 0.00000000],
+    [795., 0.00001456, 0.00000526, 0.00000000],
+    [800., 0.00001025, 0.00000370, 0.00000000],
+    [805., 0.00000722, 0.00000261, 0.00000000],
+    [810., 0.00000509, 0.00000184, 0.00000000],
+    [815., 0.00000358, 0.00000129, 0.00000000],
+    [820., 0.00000252, 0.00000091, 0.00000000],
+    [825., 0.00000178, 0.00000064, 0.00000000],
+    [830., 0.00000125, 0.00000045, 0.00000000],
+];
+
+/// Gets the values of the CIE 1931 2° color-matching functions `[x̄, ȳ, z̄]` at the given wavelength
+/// in nanometers, linearly interpolating between the 5 nm steps of
+/// [`CIE_1931_2_DEGREE`](constant.CIE_1931_2_DEGREE.html). Outside of the 360-830 nm range of the
+/// data, where the eye is essentially blind, this returns all zeros.
+/// # Example
+///
+/// ```
+/// # use scarlet::observer::color_matching_functions;
+/// // the eye is most sensitive to brightness at 555 nm
+/// let peak = color_matching_functions(555.);
+/// assert!((peak[1] - 1.).abs() <= 1e-6);
+/// // between samples, the values are interpolated
+/// let between = color_matching_functions(552.5);
+/// let (below, above) = (color_matching_functions(550.), color_matching_functions(555.));
+/// assert!((between[0] - (below[0] + above[0]) / 2.).abs() <= 1e-10);
+/// ```
+pub fn color_matching_functions(wavelength: f64) -> [f64; 3] {
+    let first = CIE_1931_2_DEGREE[0][0];
+    let last = CIE_1931_2_DEGREE[CIE_1931_2_DEGREE.len() - 1][0];
+    if !(first..=last).contains(&wavelength) {
+        return [0.0; 3];
+    }
+    let pos = (wavelength - first) / 5.0;
+    let i = (pos.floor() as usize).min(CIE_1931_2_DEGREE.len() - 2);
+    let t = pos - i as f64;
+    let (row, next) = (CIE_1931_2_DEGREE[i], CIE_1931_2_DEGREE[i + 1]);
+    [
+        row[1] * (1.0 - t) + next[1] * t,
+        row[2] * (1.0 - t) + next[2] * t,
+        row[3] * (1.0 - t) + next[3] * t,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_equal_energy_white() {
+        // the color-matching functions are normalized so that equal-energy white has X = Y = Z
+        let sums = CIE_1931_2_DEGREE.iter().fold([0.0; 3], |acc, row| {
+            [acc[0] + row[1], acc[1] + row[2], acc[2] + row[3]]
+        });
+        assert!((sums[0] / sums[1] - 1.).abs() <= 1e-3);
+        assert!((sums[2] / sums[1] - 1.).abs() <= 1e-3);
+    }
+
+    #[test]
+    fn test_color_matching_functions() {
+        // tabulated wavelengths come back exactly
+        for row in CIE_1931_2_DEGREE.iter() {
+            let cmf = color_matching_functions(row[0]);
+            assert_eq!(cmf, [row[1], row[2], row[3]]);
+        }
+        assert_eq!(color_matching_functions(300.), [0.; 3]);
+        assert_eq!(color_matching_functions(900.), [0.; 3]);
+    }
+
+    #[test]
+    fn test_ten_degree_peak() {
+        // like the 2° observer, the 10° ȳ peaks near 1 at around 555 nm
+        let (peak, max_y) = (360..=830)
+            .map(|w| {
+                (
+                    w,
+                    Observer::TenDegree.color_matching_functions(f64::from(w))[1],
+                )
+            })
+            .fold((0, 0.), |acc, x| if x.1 > acc.1 { x } else { acc });
+        assert!((550..=560).contains(&peak));
+        assert!((max_y - 1.).abs() <= 0.02);
+    }
+}
@@ -0,0 +1,110 @@
+//! This module reads palettes, lists of colors, from the file formats used by image editors, so that
+//! palettes built elsewhere can be used with Scarlet. Currently the Paint.NET text format is
+//! supported; Aseprite can also import and export palettes in this format.
+
+use alpha::Alpha;
+use color::RGBColor;
+use std::error::Error;
+use std::fmt;
+
+/// An error that results from an invalid attempt to read a palette.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum PaletteError {
+    /// This indicates that a color entry was not the expected number of hex digits long.
+    WrongLength,
+    /// This indicates that a color entry contained a character that isn't a hex digit.
+    InvalidHexDigit,
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Palette reading error")
+    }
+}
+
+impl Error for PaletteError {
+    fn description(&self) -> &str {
+        match *self {
+            PaletteError::WrongLength => "Color entry has the wrong number of digits",
+            PaletteError::InvalidHexDigit => "Color entry has an invalid hex digit",
+        }
+    }
+}
+
+/// Reads a palette in the Paint.NET text format, returning its colors in order. Each line holds a
+/// single color as eight hex digits in `AARRGGBB` order, alpha first, in either case. Anything after
+/// a `;` is a comment, and blank lines are ignored.
+/// # Example
+///
+/// ```
+/// # use scarlet::palette::{from_paintnet_str, PaletteError};
+/// # fn try_main() -> Result<(), PaletteError> {
+/// let palette = from_paintnet_str("; a two-color palette\nFFFF0000\n800000ff ; translucent blue\n")?;
+/// assert_eq!(palette.len(), 2);
+/// assert_eq!(palette[0].color.to_string(), "#FF0000");
+/// assert!((palette[1].alpha - 128. / 255.).abs() <= 1e-10);
+/// # Ok(())
+/// # }
+/// # try_main().unwrap();
+/// ```
+pub fn from_paintnet_str(s: &str) -> Result<Vec<Alpha<RGBColor>>, PaletteError> {
+    let mut colors = Vec::new();
+    for line in s.lines() {
+        let entry = match line.find(';') {
+            Some(i) => &line[..i],
+            None => line,
+        }
+        .trim();
+        if entry.is_empty() {
+            continue;
+        }
+        if !entry.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(PaletteError::InvalidHexDigit);
+        }
+        if entry.len() != 8 {
+            return Err(PaletteError::WrongLength);
+        }
+        // all ASCII digits, so slicing by bytes is fine
+        let byte = |i: usize| {
+            u8::from_str_radix(&entry[2 * i..2 * i + 2], 16).expect("Checked for hex digits")
+        };
+        let color = RGBColor::from((byte(1), byte(2), byte(3)));
+        colors.push(Alpha::new(color, f64::from(byte(0)) / 255.0));
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_paintnet_palette() {
+        let palette = "\
+; paint.net Palette File
+; Lines that start with a semicolon are comments
+; Colors are written as 8-digit hexadecimal numbers: aarrggbb
+
+FF000000
+FFFFFFFF ; white
+80FF8000
+00abcdef
+";
+        let colors = from_paintnet_str(palette).unwrap();
+        let hexes: Vec<String> = colors.iter().map(|c| c.color.to_string()).collect();
+        assert_eq!(hexes, vec!["#000000", "#FFFFFF", "#FF8000", "#ABCDEF"]);
+        let alphas: Vec<f64> = colors.iter().map(|c| c.alpha).collect();
+        assert_eq!(alphas, vec![1.0, 1.0, 128.0 / 255.0, 0.0]);
+        assert!(from_paintnet_str("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_paintnet_errors() {
+        assert_eq!(from_paintnet_str("FF0000"), Err(PaletteError::WrongLength));
+        assert_eq!(
+            from_paintnet_str("FFFF0000\nFFGG0000"),
+            Err(PaletteError::InvalidHexDigit)
+        );
+    }
+}
@@ -7,5 +7,5 @@
 
 pub use bound::Bound;
 pub use color::{Color, RGBColor, RGBParseError};
-pub use colorpoint::ColorPoint;
+pub use colorpoint::{ColorPoint, HueMix, Mix, PigmentMix};
 pub use illuminants::Illuminant;
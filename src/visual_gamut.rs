@@ -2,38 +2,19 @@
 //! visible by the human eye to a given color.
 use color::XYZColor;
 use illuminants::Illuminant;
-
-use super::csv;
-
-use std::path::Path;
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Record {
-    wavelength: u16,
-    xbar: f64,
-    ybar: f64,
-    zbar: f64,
-}
+use observer::CIE_1931_2_DEGREE;
 
 // first, read in spectral color data
 pub fn read_cie_spectral_data() -> (Vec<u16>, Vec<XYZColor>) {
-    let mut wavelengths = vec![];
-    let mut xyz_data = vec![];
-    let path = Path::new("cie-1931-standard-matching.csv");
-    let mut reader = match csv::Reader::from_path(path) {
-        Err(e) => panic!("CIE spectral data could not be read: {}", e.to_string()),
-        Ok(rdr) => rdr,
-    };
-    for result in reader.deserialize() {
-        // we should panic on bad data: this file is supplied by us!
-        let record: Record = result.unwrap();
-        wavelengths.push(record.wavelength);
-        xyz_data.push(XYZColor {
-            x: record.xbar,
-            y: record.ybar,
-            z: record.zbar,
+    let wavelengths = CIE_1931_2_DEGREE.iter().map(|row| row[0] as u16).collect();
+    let xyz_data = CIE_1931_2_DEGREE
+        .iter()
+        .map(|row| XYZColor {
+            x: row[1],
+            y: row[2],
+            z: row[3],
             illuminant: Illuminant::D50,
-        });
-    }
+        })
+        .collect();
     (wavelengths, xyz_data)
 }